@@ -1,32 +1,302 @@
 //! Command-line argument parsing for GPUi Shell.
 
+use std::path::PathBuf;
+
+/// Usage text printed by `--help` and on argument errors.
+pub const USAGE: &str = "\
+Usage: gpuishell [options] [command]
+
+Commands:
+  (none)                    Start the shell, or toggle the launcher if it is running
+  daemon                    Start the shell; fail if it is already running
+  launcher [--input <text>] Toggle the launcher of the running shell
+  msg <command> [args]      Send a command to the running shell (see `gpuishell msg`)
+  config check [path]       Validate a config file without starting the shell
+  config dump-default       Print the default config.toml
+
+Options:
+  -c, --config <path>       Use <path> instead of ~/.config/gpuishell/config.toml
+  -i, --input <text>        Prefill the launcher with <text>
+  -V, --version             Print version
+  -h, --help                Print this help";
+
+/// What this invocation should do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Start the shell, or toggle the launcher of the running instance.
+    Default { input: Option<String> },
+    /// Start the shell, refusing to signal a running instance.
+    Daemon,
+    /// Toggle the launcher of the running instance.
+    Launcher { input: Option<String> },
+    /// Send an IPC command to the running instance.
+    Msg(Vec<String>),
+    /// Validate a config file.
+    ConfigCheck { path: Option<PathBuf> },
+    /// Print the default config.
+    ConfigDumpDefault,
+    /// Print usage.
+    Help,
+    /// Print the version.
+    Version,
+}
+
 /// Command-line arguments.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Args {
-    /// Optional input to prefill in the launcher.
-    pub input: Option<String>,
+    pub command: Command,
+    /// Config file given with `--config`.
+    pub config: Option<PathBuf>,
 }
 
 impl Args {
-    /// Parse command-line arguments from `std::env::args()`.
+    /// Parse command-line arguments from `std::env::args()`, exiting with a
+    /// usage error if they are invalid.
     pub fn parse() -> Self {
-        let args: Vec<String> = std::env::args().collect();
+        match Self::try_parse(std::env::args().skip(1)) {
+            Ok(args) => args,
+            Err(err) => {
+                eprintln!("Error: {err}\n\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    /// Parse arguments (without the program name).
+    pub fn try_parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parser = Parser {
+            args: args.into_iter().collect::<Vec<_>>().into_iter(),
+            config: None,
+        };
+        let command = parser.command()?;
+        Ok(Args {
+            command,
+            config: parser.config,
+        })
+    }
+}
+
+struct Parser {
+    args: std::vec::IntoIter<String>,
+    config: Option<PathBuf>,
+}
+
+impl Parser {
+    fn command(&mut self) -> Result<Command, String> {
+        let mut input = None;
+
+        while let Some(arg) = self.args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "-h" | "--help" | "help" => return Ok(Command::Help),
+                "-V" | "--version" => return Ok(Command::Version),
+                "-c" | "--config" => self.config = Some(self.value(flag, inline)?.into()),
+                "-i" | "--input" => input = Some(self.value(flag, inline)?),
+                "daemon" => return self.daemon(),
+                "launcher" => return self.launcher(),
+                "msg" => return Ok(Command::Msg(self.args.by_ref().collect())),
+                "config" => return self.config_command(),
+                other => return Err(unexpected(other)),
+            }
+        }
+
+        Ok(Command::Default { input })
+    }
+
+    fn daemon(&mut self) -> Result<Command, String> {
+        while let Some(arg) = self.args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "-h" | "--help" => return Ok(Command::Help),
+                "-c" | "--config" => self.config = Some(self.value(flag, inline)?.into()),
+                other => return Err(unexpected(other)),
+            }
+        }
+        Ok(Command::Daemon)
+    }
+
+    fn launcher(&mut self) -> Result<Command, String> {
         let mut input = None;
+        while let Some(arg) = self.args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "-h" | "--help" => return Ok(Command::Help),
+                "-i" | "--input" => input = Some(self.value(flag, inline)?),
+                other => return Err(unexpected(other)),
+            }
+        }
+        Ok(Command::Launcher { input })
+    }
 
-        let mut i = 1;
-        while i < args.len() {
-            if args[i] == "--input" || args[i] == "-i" {
-                if i + 1 < args.len() {
-                    input = Some(args[i + 1].clone());
-                    i += 2;
-                } else {
-                    eprintln!("Error: --input requires a value");
-                    std::process::exit(1);
+    fn config_command(&mut self) -> Result<Command, String> {
+        let command = match self.args.next().as_deref() {
+            Some("check") => {
+                let mut path = None;
+                while let Some(arg) = self.args.next() {
+                    let (flag, inline) = split_flag(&arg);
+                    match flag {
+                        "-h" | "--help" => return Ok(Command::Help),
+                        "-c" | "--config" => {
+                            self.config = Some(self.value(flag, inline)?.into());
+                        }
+                        other if !other.starts_with('-') && path.is_none() => {
+                            path = Some(PathBuf::from(other));
+                        }
+                        other => return Err(unexpected(other)),
+                    }
+                }
+                Command::ConfigCheck {
+                    path: path.or_else(|| self.config.clone()),
                 }
-            } else {
-                i += 1;
             }
+            Some("dump-default") => Command::ConfigDumpDefault,
+            Some("-h" | "--help") => return Ok(Command::Help),
+            Some(other) => return Err(format!("unknown config command: {other}")),
+            None => return Err("expected `config check` or `config dump-default`".to_string()),
+        };
+
+        match self.args.next() {
+            Some(arg) => Err(unexpected(&arg)),
+            None => Ok(command),
         }
+    }
+
+    /// Value of a flag, either inline (`--flag=value`) or the next argument.
+    fn value(&mut self, flag: &str, inline: Option<&str>) -> Result<String, String> {
+        match inline {
+            Some(value) => Ok(value.to_string()),
+            None => self
+                .args
+                .next()
+                .ok_or_else(|| format!("{flag} requires a value")),
+        }
+    }
+}
+
+/// Split `--flag=value` into its flag and inline value.
+fn split_flag(arg: &str) -> (&str, Option<&str>) {
+    match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+        _ => (arg, None),
+    }
+}
+
+fn unexpected(arg: &str) -> String {
+    if arg.starts_with('-') {
+        format!("unknown option: {arg}")
+    } else {
+        format!("unexpected argument: {arg}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::try_parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn command(args: &[&str]) -> Command {
+        parse(args).unwrap().command
+    }
+
+    #[test]
+    fn test_default_command() {
+        assert_eq!(command(&[]), Command::Default { input: None });
+        assert_eq!(
+            command(&["--input", "fire"]),
+            Command::Default {
+                input: Some("fire".to_string())
+            }
+        );
+        assert_eq!(
+            command(&["--input=;win"]),
+            Command::Default {
+                input: Some(";win".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_subcommands() {
+        assert_eq!(command(&["daemon"]), Command::Daemon);
+        assert_eq!(
+            command(&["launcher", "-i", "calc"]),
+            Command::Launcher {
+                input: Some("calc".to_string())
+            }
+        );
+        assert_eq!(
+            command(&["msg", "volume", "set", "40"]),
+            Command::Msg(vec!["volume".into(), "set".into(), "40".into()])
+        );
+        assert_eq!(
+            command(&["config", "dump-default"]),
+            Command::ConfigDumpDefault
+        );
+        assert_eq!(command(&["--version"]), Command::Version);
+        assert_eq!(command(&["daemon", "--help"]), Command::Help);
+    }
+
+    #[test]
+    fn test_msg_keeps_flags_for_the_command() {
+        assert_eq!(
+            command(&["msg", "launcher", "--input"]),
+            Command::Msg(vec!["launcher".into(), "--input".into()])
+        );
+    }
+
+    #[test]
+    fn test_config_flag() {
+        let args = parse(&["-c", "/tmp/a.toml", "daemon"]).unwrap();
+        assert_eq!(args.command, Command::Daemon);
+        assert_eq!(args.config, Some(PathBuf::from("/tmp/a.toml")));
+
+        let args = parse(&["daemon", "--config=/tmp/b.toml"]).unwrap();
+        assert_eq!(args.config, Some(PathBuf::from("/tmp/b.toml")));
+    }
+
+    #[test]
+    fn test_config_check_path() {
+        assert_eq!(
+            command(&["config", "check", "/tmp/a.toml"]),
+            Command::ConfigCheck {
+                path: Some(PathBuf::from("/tmp/a.toml"))
+            }
+        );
+        assert_eq!(
+            command(&["--config", "/tmp/b.toml", "config", "check"]),
+            Command::ConfigCheck {
+                path: Some(PathBuf::from("/tmp/b.toml"))
+            }
+        );
+        assert_eq!(
+            command(&["config", "check"]),
+            Command::ConfigCheck { path: None }
+        );
+    }
 
-        Args { input }
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse(&["--bogus"]).unwrap_err(), "unknown option: --bogus");
+        assert_eq!(
+            parse(&["frobnicate"]).unwrap_err(),
+            "unexpected argument: frobnicate"
+        );
+        assert_eq!(
+            parse(&["--config"]).unwrap_err(),
+            "--config requires a value"
+        );
+        assert_eq!(
+            parse(&["daemon", "extra"]).unwrap_err(),
+            "unexpected argument: extra"
+        );
+        assert_eq!(
+            parse(&["config", "dump-default", "extra"]).unwrap_err(),
+            "unexpected argument: extra"
+        );
+        assert!(parse(&["config"]).is_err());
+        assert!(parse(&["config", "lint"]).is_err());
     }
 }
@@ -1,24 +1,66 @@
 //! Status bar using layer shell for Wayland.
 //!
-//! This module provides a configurable shell bar for any screen edge.
+//! This module provides a configurable shell bar for any screen edge. Each
+//! output gets its own bar window; `[[bar.outputs]]` entries can change its
+//! layout or disable it per output.
+//!
+//! Depending on `bar.mode` a bar reserves space for itself, overlays
+//! windows, or collapses to a thin strip until the pointer touches it.
+//! Bars also collapse while the output shows a fullscreen window.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures_signals::signal::SignalExt;
+use futures_util::StreamExt;
 
 use gpui::{
-    AnyElement, App, Bounds, Context, DisplayId, FontWeight, Size, Window,
-    WindowBackgroundAppearance, WindowBounds, WindowKind, WindowOptions, div, layer_shell::*,
-    point, prelude::*, px,
+    AnyElement, AnyWindowHandle, App, Bounds, Context, DisplayId, FontWeight, Global, Pixels,
+    Point, Size, Window, WindowBackgroundAppearance, WindowBounds, WindowId, WindowKind,
+    WindowOptions, div, layer_shell::*, point, prelude::*, px,
 };
+use services::Monitor;
 use ui::{ActiveTheme, spacing};
 
-use super::config::BarPosition;
+use super::config::{BarConfig, BarPosition};
 use super::modules::Widget;
-use crate::config::{ActiveConfig, Config};
+use crate::config::{ActiveConfig, BarMode};
+use crate::state::{AppState, watch};
+
+/// Thickness of the strip a hidden bar collapses to.
+const HIDDEN_STRIP_SIZE: f32 = 2.0;
+/// How long a hidden bar stays revealed after the pointer leaves it.
+const HIDE_DELAY: Duration = Duration::from_millis(600);
+/// How long a new notification reveals an auto-hiding bar.
+const ATTENTION_DURATION: Duration = Duration::from_secs(3);
+/// How often, and how many times, to check whether GPUI's displays caught
+/// up with an output change.
+const DISPLAY_SYNC_INTERVAL: Duration = Duration::from_millis(100);
+const DISPLAY_SYNC_ATTEMPTS: usize = 30;
 
 /// The main bar view.
 struct Bar {
+    window: AnyWindowHandle,
     position: BarPosition,
+    mode: BarMode,
+    size: f32,
     start_widgets: Vec<Widget>,
     center_widgets: Vec<Widget>,
     end_widgets: Vec<Widget>,
+    /// Pointer is over the bar.
+    hovered: bool,
+    /// A workspace on this output requested attention.
+    urgent: bool,
+    /// A recent notification is being announced.
+    notified: bool,
+    /// This output shows a fullscreen window.
+    fullscreen: bool,
+    /// Newest notification seen, to detect new ones.
+    last_notification: Option<u32>,
+    /// Invalidates pending hide timers.
+    hover_generation: u64,
+    /// Invalidates pending attention timers.
+    attention_generation: u64,
 }
 
 #[derive(Clone, Copy)]
@@ -29,15 +71,159 @@ enum SectionAlign {
 }
 
 impl Bar {
-    /// Create a bar with configuration.
-    fn new(cx: &mut Context<Self>) -> Self {
-        let config = cx.config().bar.clone();
-        let position = config.position;
-        Self {
-            position,
-            start_widgets: Widget::create_many(&config.start, cx),
-            center_widgets: Widget::create_many(&config.center, cx),
-            end_widgets: Widget::create_many(&config.end, cx),
+    /// Create a bar with configuration for the given output.
+    fn new(
+        config: BarConfig,
+        output: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let compositor = AppState::compositor(cx).clone();
+        let state = compositor.get();
+        let fullscreen = state.has_fullscreen_window(output.as_deref());
+        let urgent = Self::has_urgent_workspace(&state, output.as_deref());
+
+        watch(cx, compositor.subscribe(), move |this, state, cx| {
+            let fullscreen = state.has_fullscreen_window(output.as_deref());
+            let urgent = Self::has_urgent_workspace(&state, output.as_deref());
+            if fullscreen != this.fullscreen || urgent != this.urgent {
+                let was_hidden = this.is_hidden();
+                this.fullscreen = fullscreen;
+                this.urgent = urgent;
+                this.update_visibility(was_hidden, cx);
+            }
+        });
+
+        let notifications = AppState::notification(cx).clone();
+        let last_notification = notifications.get().notifications.iter().map(|n| n.id).max();
+        if config.mode == BarMode::Autohide {
+            watch(cx, notifications.subscribe(), |this, data, cx| {
+                let newest = data.notifications.iter().map(|n| n.id).max();
+                if newest > this.last_notification {
+                    this.last_notification = newest;
+                    if !data.dnd {
+                        this.announce(cx);
+                    }
+                }
+            });
+        }
+
+        let bar = Self {
+            window: window.window_handle(),
+            position: config.position,
+            mode: config.mode,
+            size: config.size,
+            start_widgets: Widget::create_many(&config.start, false, cx),
+            center_widgets: Widget::create_many(&config.center, false, cx),
+            end_widgets: Widget::create_many(&config.end, false, cx),
+            hovered: false,
+            urgent,
+            notified: false,
+            fullscreen,
+            last_notification,
+            hover_generation: 0,
+            attention_generation: 0,
+        };
+        bar.resize(window);
+        bar
+    }
+
+    fn has_urgent_workspace(state: &services::CompositorState, output: Option<&str>) -> bool {
+        state
+            .workspaces
+            .iter()
+            .any(|ws| ws.urgent && output.is_none_or(|name| ws.monitor == name))
+    }
+
+    /// Whether the bar is collapsed to its hover strip.
+    fn is_hidden(&self) -> bool {
+        if self.hovered {
+            return false;
+        }
+        // Fullscreen windows hide the bar in every mode; only the pointer
+        // brings it back.
+        self.fullscreen || (self.mode == BarMode::Autohide && !self.urgent && !self.notified)
+    }
+
+    /// Reveal the bar for a while to announce a new notification.
+    fn announce(&mut self, cx: &mut Context<Self>) {
+        let was_hidden = self.is_hidden();
+        self.notified = true;
+        self.attention_generation += 1;
+        let generation = self.attention_generation;
+        self.update_visibility(was_hidden, cx);
+
+        cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(ATTENTION_DURATION).await;
+            let _ = this.update(cx, |this, cx| {
+                if this.attention_generation == generation {
+                    let was_hidden = this.is_hidden();
+                    this.notified = false;
+                    this.update_visibility(was_hidden, cx);
+                }
+            });
+        })
+        .detach();
+    }
+
+    fn on_hover(&mut self, hovered: &bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.hover_generation += 1;
+        if *hovered {
+            let was_hidden = self.is_hidden();
+            self.hovered = true;
+            if was_hidden {
+                self.resize(window);
+            }
+            cx.notify();
+            return;
+        }
+
+        // Collapse after a short delay so brushing past the edge does not
+        // make the bar flicker.
+        let generation = self.hover_generation;
+        cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(HIDE_DELAY).await;
+            let _ = this.update(cx, |this, cx| {
+                if this.hover_generation == generation {
+                    let was_hidden = this.is_hidden();
+                    this.hovered = false;
+                    this.update_visibility(was_hidden, cx);
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Re-render, resizing the window if the bar was shown or hidden since
+    /// `was_hidden` was taken.
+    fn update_visibility(&mut self, was_hidden: bool, cx: &mut Context<Self>) {
+        if self.is_hidden() != was_hidden {
+            // Observers run outside the window, so resize it once the
+            // current update is done.
+            let handle = self.window;
+            let bar = cx.entity();
+            cx.defer(move |cx| {
+                let _ = handle.update(cx, |_, window, cx| bar.read(cx).resize(window));
+            });
+        }
+        cx.notify();
+    }
+
+    /// Resize the layer surface to the strip or the full bar thickness.
+    fn resize(&self, window: &mut Window) {
+        let thickness = if self.is_hidden() {
+            HIDDEN_STRIP_SIZE
+        } else {
+            self.size
+        };
+        let viewport = window.viewport_size();
+        let size = if self.position.is_vertical() {
+            Size::new(px(thickness), viewport.height)
+        } else {
+            Size::new(viewport.width, px(thickness))
+        };
+        if viewport != size {
+            window.resize(size);
         }
     }
 
@@ -87,6 +273,14 @@ impl Bar {
 
 impl Render for Bar {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.is_hidden() {
+            return div()
+                .id("bar")
+                .size_full()
+                .on_hover(cx.listener(Self::on_hover))
+                .into_any_element();
+        }
+
         let theme = cx.theme();
         let is_vertical = self.position.is_vertical();
 
@@ -97,7 +291,9 @@ impl Render for Bar {
         let end_elements: Vec<AnyElement> = self.end_widgets.iter().map(|w| w.render()).collect();
 
         let root = div()
+            .id("bar")
             .size_full()
+            .on_hover(cx.listener(Self::on_hover))
             .flex()
             .text_size(theme.font_sizes.sm)
             .font_weight(FontWeight::MEDIUM)
@@ -131,6 +327,7 @@ impl Render for Bar {
                     SectionAlign::End,
                     end_elements,
                 ))
+                .into_any_element()
         } else {
             root.items_center()
                 .px(px(spacing::SM))
@@ -155,13 +352,43 @@ impl Render for Bar {
                     SectionAlign::End,
                     end_elements,
                 ))
+                .into_any_element()
         }
     }
 }
 
+/// Output and resolved configuration of each open bar window.
+#[derive(Default)]
+struct BarWindows(HashMap<WindowId, BarWindow>);
+
+impl Global for BarWindows {}
+
+struct BarWindow {
+    display_id: Option<DisplayId>,
+    output: Option<String>,
+    position: BarPosition,
+}
+
+/// Screen edge of the bar shown in `window`.
+///
+/// Widgets use this instead of `bar.position`, which per-output settings
+/// may override.
+pub fn position(window: &Window, cx: &App) -> BarPosition {
+    cx.try_global::<BarWindows>()
+        .and_then(|bars| bars.0.get(&window.window_handle().window_id()))
+        .map_or(cx.config().bar.position, |bar| bar.position)
+}
+
+/// Name of the output the bar in `window` is on, if known.
+pub fn output(window: &Window, cx: &App) -> Option<String> {
+    cx.try_global::<BarWindows>()
+        .and_then(|bars| bars.0.get(&window.window_handle().window_id()))
+        .and_then(|bar| bar.output.clone())
+}
+
 /// Returns window options for the bar.
 pub fn window_options(
-    // config: &BarConfig,
+    config: &BarConfig,
     display_id: Option<DisplayId>,
     cx: &App,
 ) -> WindowOptions {
@@ -170,25 +397,33 @@ pub fn window_options(
         .or_else(|| cx.primary_display())
         .map(|display| display.bounds().size)
         .unwrap_or_else(|| Size::new(px(1920.), px(1080.)));
-    let config = cx.config();
-    let (window_size, anchor) = match config.bar.position {
+    // Auto-hiding bars start collapsed; the view resizes them on reveal.
+    let thickness = match config.mode {
+        BarMode::Autohide => HIDDEN_STRIP_SIZE,
+        BarMode::Exclusive | BarMode::Overlay => config.size,
+    };
+    let (window_size, anchor) = match config.position {
         BarPosition::Left => (
-            Size::new(px(config.bar.size), display_size.height),
+            Size::new(px(thickness), display_size.height),
             Anchor::LEFT | Anchor::TOP | Anchor::BOTTOM,
         ),
         BarPosition::Right => (
-            Size::new(px(config.bar.size), display_size.height),
+            Size::new(px(thickness), display_size.height),
             Anchor::RIGHT | Anchor::TOP | Anchor::BOTTOM,
         ),
         BarPosition::Top => (
-            Size::new(display_size.width, px(config.bar.size)),
+            Size::new(display_size.width, px(thickness)),
             Anchor::LEFT | Anchor::RIGHT | Anchor::TOP,
         ),
         BarPosition::Bottom => (
-            Size::new(display_size.width, px(config.bar.size)),
+            Size::new(display_size.width, px(thickness)),
             Anchor::LEFT | Anchor::RIGHT | Anchor::BOTTOM,
         ),
     };
+    let exclusive_zone = match config.mode {
+        BarMode::Exclusive => Some(px(config.size)),
+        BarMode::Overlay | BarMode::Autohide => None,
+    };
 
     WindowOptions {
         display_id,
@@ -203,7 +438,7 @@ pub fn window_options(
             namespace: "bar".to_string(),
             layer: Layer::Top,
             anchor,
-            exclusive_zone: Some(px(config.bar.size)),
+            exclusive_zone,
             margin: None,
             keyboard_interactivity: KeyboardInteractivity::None,
             ..Default::default()
@@ -213,12 +448,22 @@ pub fn window_options(
 }
 
 /// Initialize the bar using the current global config.
+///
+/// Bars follow output hotplug: whenever the compositor's set of monitors
+/// changes, bars are opened on new displays and closed on removed ones.
 pub fn init(cx: &mut App) {
-    cx.observe_global::<Config>(|cx| {
-        tracing::info!("Config changed; reloading bar windows");
-        reload(cx);
-    })
-    .detach();
+    cx.set_global(BarWindows::default());
+    let mut monitors = AppState::compositor(cx)
+        .subscribe()
+        .map(|state| {
+            state
+                .monitors
+                .into_iter()
+                .map(|m| m.name)
+                .collect::<Vec<_>>()
+        })
+        .dedupe_cloned()
+        .to_stream();
 
     cx.spawn(async move |cx| {
         // Small delay to allow Wayland to enumerate displays
@@ -228,51 +473,159 @@ pub fn init(cx: &mut App) {
 
         tracing::info!("Bar opened");
 
-        cx.update(|cx: &mut App| {
-            let displays = cx.displays();
-
-            if displays.is_empty() {
-                // No displays enumerated yet, open on default display
-                tracing::info!("No displays found, opening bar on default display");
-                open_with_config(None, cx);
-            } else {
-                tracing::info!("Opening bar on {} displays", displays.len());
-                for d in displays {
-                    tracing::info!("Opening bar on display {:?}", d.id());
-                    open_with_config(Some(d.id()), cx);
+        cx.update(sync_bar_windows);
+
+        while let Some(names) = monitors.next().await {
+            tracing::info!("Outputs changed: {:?}", names);
+            // GPUI learns about outputs independently of the compositor's
+            // IPC; wait until it lists as many displays before matching them.
+            for _ in 0..DISPLAY_SYNC_ATTEMPTS {
+                if cx.update(|cx| cx.displays().len() == names.len()) {
+                    break;
                 }
+                cx.background_executor().timer(DISPLAY_SYNC_INTERVAL).await;
             }
-        })
+            cx.update(sync_bar_windows);
+        }
     })
     .detach();
 }
 
-/// Open the bar with custom configuration.
-pub fn open_with_config(display_id: Option<DisplayId>, cx: &mut App) -> bool {
-    match cx.open_window(window_options(display_id, cx), move |_, cx| {
-        cx.new(Bar::new)
-    }) {
-        Ok(_) => true,
-        Err(err) => {
-            tracing::warn!("Failed to open bar window: {}", err);
-            false
+/// Open bars on displays that have none and close bars whose display is
+/// gone.
+fn sync_bar_windows(cx: &mut App) {
+    let targets = display_outputs(cx);
+    let handles: Vec<_> = cx
+        .windows()
+        .into_iter()
+        .filter(|h| h.downcast::<Bar>().is_some())
+        .collect();
+
+    let bars = cx.default_global::<BarWindows>();
+    // Forget bars whose window was closed by the compositor.
+    bars.0
+        .retain(|id, _| handles.iter().any(|h| h.window_id() == *id));
+    let open: Vec<_> = handles
+        .into_iter()
+        .map(|handle| {
+            let display_id = bars.0.get(&handle.window_id()).and_then(|b| b.display_id);
+            (handle, display_id)
+        })
+        .collect();
+
+    if targets.is_empty() {
+        if open.is_empty() {
+            tracing::info!("No displays found, opening bar on default display");
+            open_with_config(None, None, cx);
+        }
+        return;
+    }
+
+    for (handle, display_id) in &open {
+        if !targets.iter().any(|(id, _)| id == display_id) {
+            tracing::info!("Closing bar on removed display {:?}", display_id);
+            cx.default_global::<BarWindows>()
+                .0
+                .remove(&handle.window_id());
+            let _ = handle.update(cx, |_, window, _| window.remove_window());
+        }
+    }
+
+    for (display_id, output) in targets {
+        if !open.iter().any(|(_, id)| *id == display_id) {
+            tracing::info!("Opening bar on display {:?} ({:?})", display_id, output);
+            open_with_config(display_id, output, cx);
+        }
+    }
+}
+
+/// Open the bars configured for `output` on a display.
+///
+/// Returns `false` if no bar is enabled on that output or no window could
+/// be opened.
+pub fn open_with_config(
+    display_id: Option<DisplayId>,
+    output: Option<String>,
+    cx: &mut App,
+) -> bool {
+    let bars = cx.config().bar.bars_for_output(output.as_deref());
+    if bars.is_empty() {
+        tracing::info!("Bar disabled on output {:?}", output);
+        return false;
+    }
+
+    let mut opened = false;
+    for config in bars {
+        let options = window_options(&config, display_id, cx);
+        let output = output.clone();
+        match cx.open_window(options, move |window, cx| {
+            let bar = BarWindow {
+                display_id,
+                output: output.clone(),
+                position: config.position,
+            };
+            cx.default_global::<BarWindows>()
+                .0
+                .insert(window.window_handle().window_id(), bar);
+            cx.new(|cx| Bar::new(config, output, window, cx))
+        }) {
+            Ok(_) => opened = true,
+            Err(err) => tracing::warn!("Failed to open bar window: {}", err),
         }
     }
+    opened
+}
+
+/// Match displays to compositor outputs.
+///
+/// Displays do not carry output names, so they are matched by their origin
+/// in the global layout (see [`is_monitor_origin`]). A display that matches
+/// no output gets `None`, unless there is exactly one of each.
+fn display_outputs(cx: &App) -> Vec<(Option<DisplayId>, Option<String>)> {
+    let displays = cx.displays();
+    let monitors = AppState::compositor(cx).get().monitors;
+    let single = displays.len() == 1 && monitors.len() == 1;
+
+    displays
+        .iter()
+        .map(|display| {
+            let origin = display.bounds().origin;
+            let output = monitors
+                .iter()
+                .find(|m| is_monitor_origin(origin, m))
+                .or_else(|| monitors.first().filter(|_| single))
+                .map(|m| m.name.clone());
+            (Some(display.id()), output)
+        })
+        .collect()
+}
+
+/// Whether a display at `origin` shows `monitor`.
+///
+/// Depending on the compositor, display origins are in logical or in
+/// physical pixels, so both are accepted. Fractional scales leave rounding
+/// differences of up to a pixel.
+fn is_monitor_origin(origin: Point<Pixels>, monitor: &Monitor) -> bool {
+    let near =
+        |value: Pixels, expected: f32| value >= px(expected - 1.0) && value <= px(expected + 1.0);
+    let (x, y) = (monitor.x as f32, monitor.y as f32);
+    (near(origin.x, x) && near(origin.y, y))
+        || (near(origin.x, x * monitor.scale) && near(origin.y, y * monitor.scale))
 }
 
 fn open_all_bar_windows(cx: &mut App) -> usize {
     let mut opened = 0usize;
-    let displays = cx.displays();
-    if displays.is_empty() {
+    let targets = display_outputs(cx);
+    if targets.is_empty() {
         tracing::info!("No displays found, opening bar on default display");
-        if open_with_config(None, cx) {
+        if open_with_config(None, None, cx) {
             opened += 1;
         }
     } else {
-        tracing::info!("Opening bar on {} displays", displays.len());
-        for d in displays {
-            tracing::info!("Opening bar on display {:?}", d.id());
-            if open_with_config(Some(d.id()), cx) {
+        tracing::info!("Opening bar on {} displays", targets.len());
+        for (display_id, output) in targets {
+            tracing::info!("Opening bar on display {:?} ({:?})", display_id, output);
+            if open_with_config(display_id, output, cx) {
                 opened += 1;
             }
         }
@@ -289,12 +642,56 @@ pub fn reload(cx: &mut App) {
         .collect();
 
     let opened = open_all_bar_windows(cx);
-    if opened == 0 {
+    // Keep the old bars if none opened, unless outputs were disabled on purpose.
+    let any_disabled = cx
+        .config()
+        .bar
+        .outputs
+        .iter()
+        .any(|o| o.enabled == Some(false));
+    if opened == 0 && !any_disabled {
         tracing::warn!("Bar reload skipped closing old windows because no new bar window opened");
         return;
     }
 
     for handle in old_windows {
+        cx.default_global::<BarWindows>()
+            .0
+            .remove(&handle.window_id());
         let _ = handle.update(cx, |_, window, _| window.remove_window());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(x: i32, y: i32, scale: f32) -> Monitor {
+        Monitor {
+            x,
+            y,
+            scale,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_monitor_origin() {
+        let origin = |x: f32, y: f32| point(px(x), px(y));
+
+        assert!(is_monitor_origin(origin(0.0, 0.0), &monitor(0, 0, 1.5)));
+        assert!(is_monitor_origin(
+            origin(1920.0, 0.0),
+            &monitor(1920, 0, 1.0)
+        ));
+        assert!(!is_monitor_origin(origin(1920.0, 0.0), &monitor(0, 0, 1.0)));
+
+        // 2560 physical px at scale 1.5 puts the next output at 1706.67.
+        let right = monitor(1707, 0, 1.5);
+        assert!(is_monitor_origin(origin(1707.0, 0.0), &right));
+        assert!(is_monitor_origin(origin(1706.0, 0.0), &right));
+        assert!(is_monitor_origin(origin(2560.0, 0.0), &right));
+        assert!(!is_monitor_origin(origin(1920.0, 0.0), &right));
+        assert!(!is_monitor_origin(origin(1707.0, 1080.0), &right));
+    }
+}
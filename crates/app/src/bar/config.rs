@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::modules::{
-    ActiveWindowConfig, BatteryConfig, ClockConfig, KeyboardLayoutConfig, LauncherBtnConfig,
-    MprisConfig, SettingsConfig, SysInfoConfig, TrayConfig, WorkspacesConfig,
+    ActiveWindowConfig, BatteryConfig, ClockConfig, CustomConfig, KeyboardLayoutConfig,
+    LauncherBtnConfig, MprisConfig, SettingsConfig, SubmapConfig, SysInfoConfig, TaskbarConfig,
+    TrayConfig, WorkspacesConfig,
 };
 
 /// Bar screen position.
@@ -27,6 +30,19 @@ impl BarPosition {
     }
 }
 
+/// How the bar shares the screen with windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BarMode {
+    /// Reserve space so windows are laid out next to the bar.
+    #[default]
+    Exclusive,
+    /// Draw above windows without reserving space.
+    Overlay,
+    /// Collapse to a thin strip until hovered or a widget needs attention.
+    Autohide,
+}
+
 /// Status bar configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -35,6 +51,8 @@ pub struct BarConfig {
     pub size: f32,
     /// Screen edge where the bar is placed.
     pub position: BarPosition,
+    /// Whether the bar reserves space, overlays windows or hides itself.
+    pub mode: BarMode,
     /// Start section widgets (left for horizontal, top for vertical).
     pub start: Vec<String>,
     /// Center section widgets.
@@ -43,6 +61,91 @@ pub struct BarConfig {
     pub end: Vec<String>,
     /// Bar modules
     pub modules: ModulesConfig,
+    /// Per-output overrides, matched by output name.
+    pub outputs: Vec<BarOutputConfig>,
+    /// Additional bars, e.g. a bottom dock next to the main bar.
+    pub extra: Vec<ExtraBarConfig>,
+    /// Named widget groups, placed in a section as `group/<name>`.
+    pub groups: BTreeMap<String, WidgetGroupConfig>,
+}
+
+/// An additional bar (`[[bar.extra]]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtraBarConfig {
+    /// Main axis thickness in px.
+    pub size: f32,
+    /// Screen edge where the bar is placed.
+    pub position: BarPosition,
+    /// Bar mode; the main bar's mode when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<BarMode>,
+    pub start: Vec<String>,
+    pub center: Vec<String>,
+    pub end: Vec<String>,
+    /// Outputs to show the bar on; all outputs when empty.
+    pub outputs: Vec<String>,
+}
+
+impl Default for ExtraBarConfig {
+    fn default() -> Self {
+        Self {
+            size: 32.0,
+            position: BarPosition::Bottom,
+            mode: None,
+            start: Vec::new(),
+            center: Vec::new(),
+            end: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+}
+
+/// Several widgets rendered inside one pill (`[bar.groups.<name>]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WidgetGroupConfig {
+    /// Widgets in the group, in order.
+    pub widgets: Vec<String>,
+    /// Space between widgets in px.
+    pub spacing: f32,
+    /// Text placed between widgets (none when empty).
+    pub separator: String,
+}
+
+impl Default for WidgetGroupConfig {
+    fn default() -> Self {
+        Self {
+            widgets: Vec::new(),
+            spacing: 4.0,
+            separator: String::new(),
+        }
+    }
+}
+
+/// Bar settings for a single output (`[[bar.outputs]]`).
+///
+/// Unset fields fall back to the top-level `[bar]` values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BarOutputConfig {
+    /// Output name, e.g. `eDP-1` or `HDMI-A-1`.
+    pub name: String,
+    /// Whether to open a bar on this output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<BarPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<BarMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<Vec<String>>,
 }
 
 /// Bar module configurations.
@@ -52,6 +155,8 @@ pub struct ModulesConfig {
     pub clock: ClockConfig,
     pub battery: BatteryConfig,
     pub workspaces: WorkspacesConfig,
+    pub taskbar: TaskbarConfig,
+    pub submap: SubmapConfig,
     pub tray: TrayConfig,
     pub sysinfo: SysInfoConfig,
     pub mpris: MprisConfig,
@@ -59,6 +164,8 @@ pub struct ModulesConfig {
     pub keyboard_layout: KeyboardLayoutConfig,
     pub launcher_btn: LauncherBtnConfig,
     pub settings: SettingsConfig,
+    /// Custom widgets by name, placed in a section as `custom/<name>`.
+    pub custom: BTreeMap<String, CustomConfig>,
 }
 
 impl Default for ModulesConfig {
@@ -67,6 +174,8 @@ impl Default for ModulesConfig {
             clock: ClockConfig::default(),
             battery: BatteryConfig::default(),
             workspaces: WorkspacesConfig::default(),
+            taskbar: TaskbarConfig::default(),
+            submap: SubmapConfig::default(),
             tray: TrayConfig::default(),
             sysinfo: SysInfoConfig::default(),
             mpris: MprisConfig::default(),
@@ -74,6 +183,7 @@ impl Default for ModulesConfig {
             keyboard_layout: KeyboardLayoutConfig::default(),
             launcher_btn: LauncherBtnConfig::default(),
             settings: SettingsConfig::default(),
+            custom: BTreeMap::new(),
         }
     }
 }
@@ -83,6 +193,7 @@ impl Default for BarConfig {
         Self {
             size: 32.0,
             position: BarPosition::Left,
+            mode: BarMode::Exclusive,
             start: vec!["LauncherBtn".into(), "Workspaces".into(), "SysInfo".into()],
             center: vec!["ActiveWindow".into()],
             end: vec![
@@ -94,6 +205,9 @@ impl Default for BarConfig {
                 "Settings".into(),
             ],
             modules: ModulesConfig::default(),
+            outputs: Vec::new(),
+            extra: Vec::new(),
+            groups: BTreeMap::new(),
         }
     }
 }
@@ -103,4 +217,143 @@ impl BarConfig {
     pub fn is_vertical(&self) -> bool {
         self.position.is_vertical()
     }
+
+    /// The bar configuration for an output, or `None` if the bar is
+    /// disabled there.
+    pub fn for_output(&self, output: Option<&str>) -> Option<BarConfig> {
+        let mut config = self.clone();
+        let Some(overrides) = output.and_then(|name| self.outputs.iter().find(|o| o.name == name))
+        else {
+            return Some(config);
+        };
+
+        if overrides.enabled == Some(false) {
+            return None;
+        }
+        if let Some(size) = overrides.size {
+            config.size = size;
+        }
+        if let Some(position) = overrides.position {
+            config.position = position;
+        }
+        if let Some(mode) = overrides.mode {
+            config.mode = mode;
+        }
+        if let Some(start) = &overrides.start {
+            config.start = start.clone();
+        }
+        if let Some(center) = &overrides.center {
+            config.center = center.clone();
+        }
+        if let Some(end) = &overrides.end {
+            config.end = end.clone();
+        }
+        Some(config)
+    }
+
+    /// Configurations of all bars to open on an output: the main bar
+    /// (unless disabled there) followed by matching extra bars.
+    pub fn bars_for_output(&self, output: Option<&str>) -> Vec<BarConfig> {
+        let extras = self.extra.iter().filter(|extra| {
+            extra.outputs.is_empty()
+                || output.is_some_and(|name| extra.outputs.iter().any(|o| o == name))
+        });
+        let extras = extras.map(|extra| BarConfig {
+            size: extra.size,
+            position: extra.position,
+            mode: extra.mode.unwrap_or(self.mode),
+            start: extra.start.clone(),
+            center: extra.center.clone(),
+            end: extra.end.clone(),
+            ..self.clone()
+        });
+        self.for_output(output).into_iter().chain(extras).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(raw: &str) -> BarConfig {
+        toml::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn test_for_output_without_overrides() {
+        let config = bar("size = 40\nstart = [\"Clock\"]");
+        for output in [None, Some("DP-1")] {
+            let bar = config.for_output(output).unwrap();
+            assert_eq!(bar.size, 40.0);
+            assert_eq!(bar.start, vec!["Clock"]);
+        }
+    }
+
+    #[test]
+    fn test_for_output_applies_overrides() {
+        let config = bar(r#"
+            size = 40
+            position = "left"
+            start = ["Clock"]
+            end = ["Battery"]
+
+            [[outputs]]
+            name = "DP-1"
+            position = "top"
+            start = ["Workspaces"]
+
+            [[outputs]]
+            name = "HDMI-A-1"
+            enabled = false
+            "#);
+
+        let bar = config.for_output(Some("DP-1")).unwrap();
+        assert_eq!(bar.size, 40.0);
+        assert_eq!(bar.position, BarPosition::Top);
+        assert_eq!(bar.start, vec!["Workspaces"]);
+        assert_eq!(bar.end, vec!["Battery"]);
+
+        assert!(config.for_output(Some("HDMI-A-1")).is_none());
+        let bar = config.for_output(Some("eDP-1")).unwrap();
+        assert_eq!(bar.position, BarPosition::Left);
+    }
+
+    #[test]
+    fn test_bars_for_output() {
+        let config = bar(r#"
+            mode = "overlay"
+
+            [[outputs]]
+            name = "HDMI-A-1"
+            enabled = false
+
+            [[extra]]
+            end = ["Clock"]
+
+            [[extra]]
+            position = "top"
+            mode = "autohide"
+            outputs = ["DP-1"]
+            "#);
+
+        let bars = config.bars_for_output(Some("DP-1"));
+        let layout: Vec<_> = bars.iter().map(|bar| (bar.position, bar.mode)).collect();
+        assert_eq!(
+            layout,
+            vec![
+                (BarPosition::Left, BarMode::Overlay),
+                (BarPosition::Bottom, BarMode::Overlay),
+                (BarPosition::Top, BarMode::Autohide),
+            ]
+        );
+        assert_eq!(bars[1].end, vec!["Clock"]);
+
+        // Disabling the main bar leaves the extra bars for every output.
+        let bars = config.bars_for_output(Some("HDMI-A-1"));
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].position, BarPosition::Bottom);
+
+        // Without an output name, only extra bars for all outputs are added.
+        assert_eq!(config.bars_for_output(None).len(), 2);
+    }
 }
@@ -8,6 +8,7 @@ use services::CompositorState;
 use ui::{ActiveTheme, radius, spacing};
 
 use super::style;
+use crate::bar;
 use crate::config::ActiveConfig;
 use crate::state::AppState;
 use crate::state::watch;
@@ -16,11 +17,12 @@ use crate::state::watch;
 pub struct ActiveWindow {
     _compositor: services::CompositorSubscriber,
     state: CompositorState,
+    grouped: bool,
 }
 
 impl ActiveWindow {
     /// Create a new active window widget.
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         let compositor = AppState::compositor(cx).clone();
         let state = compositor.get();
 
@@ -33,6 +35,7 @@ impl ActiveWindow {
         Self {
             _compositor: compositor,
             state,
+            grouped,
         }
     }
 
@@ -146,9 +149,10 @@ impl ActiveWindow {
 }
 
 impl Render for ActiveWindow {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
         let has_window_text = self
             .state
             .active_window
@@ -205,13 +209,15 @@ impl Render for ActiveWindow {
                 .items_center()
                 .justify_center()
                 .gap(px(style::CHIP_GAP))
-                .px(px(spacing::MD))
-                .py(px(style::CHIP_PADDING_Y))
                 .max_w(px(460.0))
-                .rounded(px(radius::SM))
-                .bg(interactive_default)
-                .border_1()
-                .border_color(border_subtle)
+                .when(!grouped, |el| {
+                    el.px(px(spacing::MD))
+                        .py(px(style::CHIP_PADDING_Y))
+                        .rounded(px(radius::SM))
+                        .bg(interactive_default)
+                        .border_1()
+                        .border_color(border_subtle)
+                })
                 .text_size(style::label_size(theme, false))
                 .text_color(theme.text.primary)
                 .overflow_hidden()
@@ -5,9 +5,10 @@ pub use config::BatteryConfig;
 
 use gpui::{Context, Window, div, prelude::*, px};
 use services::{BatteryState, UPowerData};
-use ui::{ActiveTheme, radius};
+use ui::ActiveTheme;
 
 use super::style;
+use crate::bar;
 use crate::config::ActiveConfig;
 use crate::state::AppState;
 use crate::state::watch;
@@ -15,11 +16,12 @@ use crate::state::watch;
 /// A battery widget that displays the current battery percentage and status.
 pub struct Battery {
     data: UPowerData,
+    grouped: bool,
 }
 
 impl Battery {
     /// Create a new battery widget.
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         let subscriber = AppState::upower(cx).clone();
         let initial_data = subscriber.get();
 
@@ -29,7 +31,10 @@ impl Battery {
             cx.notify();
         });
 
-        Battery { data: initial_data }
+        Battery {
+            data: initial_data,
+            grouped,
+        }
     }
 
     /// Get the battery icon based on current state.
@@ -85,9 +90,10 @@ impl Battery {
 }
 
 impl Render for Battery {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
 
         let config = &cx.config().bar.modules.battery;
         let icon = if config.show_icon {
@@ -145,9 +151,7 @@ impl Render for Battery {
                 .flex_col()
                 .items_center()
                 .gap(px(style::CHIP_GAP))
-                .px(px(style::chip_padding_x(true)))
-                .py(px(style::CHIP_PADDING_Y))
-                .rounded(px(radius::SM))
+                .map(|el| style::chip(el, true, grouped))
                 // Battery icon
                 .when_some(icon, |el, icon| {
                     el.child(
@@ -172,9 +176,7 @@ impl Render for Battery {
                 .flex()
                 .items_center()
                 .gap(px(style::CHIP_GAP))
-                .px(px(style::chip_padding_x(false)))
-                .py(px(style::CHIP_PADDING_Y))
-                .rounded(px(radius::SM))
+                .map(|el| style::chip(el, false, grouped))
                 // Battery icon
                 .when_some(icon, |el, icon| {
                     el.child(
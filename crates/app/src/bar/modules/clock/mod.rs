@@ -6,17 +6,20 @@ pub use config::ClockConfig;
 use chrono::Local;
 use gpui::{Context, Window, div, prelude::*, px};
 use std::time::Duration;
-use ui::{ActiveTheme, radius};
+use ui::ActiveTheme;
 
 use super::style;
+use crate::bar;
 use crate::config::ActiveConfig;
 
 /// A clock widget that updates every second.
-pub struct Clock;
+pub struct Clock {
+    grouped: bool,
+}
 
 impl Clock {
     /// Create a new clock widget that auto-updates.
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         // Spawn a timer to update the clock every second.
         cx.spawn(async move |this, cx| {
             loop {
@@ -26,7 +29,7 @@ impl Clock {
         })
         .detach();
 
-        Clock
+        Clock { grouped }
     }
 
     fn formatted_time_horizontal(&self, format: &str) -> String {
@@ -46,9 +49,10 @@ impl Clock {
 }
 
 impl Render for Clock {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
         let config = &cx.config().bar.modules.clock;
 
         if is_vertical {
@@ -62,9 +66,7 @@ impl Render for Clock {
                 .flex_col()
                 .items_center()
                 .gap(px(style::CHIP_GAP))
-                .px(px(style::chip_padding_x(true)))
-                .py(px(style::CHIP_PADDING_Y))
-                .rounded(px(radius::SM))
+                .map(|el| style::chip(el, true, grouped))
                 .children(lines.into_iter().enumerate().map(|(idx, line)| {
                     div()
                         .text_size(style::label_size(theme, true))
@@ -81,9 +83,7 @@ impl Render for Clock {
                 .flex()
                 .items_center()
                 .gap(px(style::CHIP_GAP))
-                .px(px(style::chip_padding_x(false)))
-                .py(px(style::CHIP_PADDING_Y))
-                .rounded(px(radius::SM))
+                .map(|el| style::chip(el, false, grouped))
                 .text_size(style::label_size(theme, false))
                 .text_color(theme.text.primary)
                 .child(self.formatted_time_horizontal(&config.format_horizontal))
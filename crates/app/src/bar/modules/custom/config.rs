@@ -0,0 +1,37 @@
+//! Custom script module configuration.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration of one custom widget (`[bar.modules.custom.<name>]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomConfig {
+    /// Command run through `sh -c`.
+    pub exec: String,
+    /// Seconds between runs. Without it, `exec` is a long-running process
+    /// that prints one update per line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<u64>,
+    /// Seconds to wait before restarting a long-running process that
+    /// exited; it is not restarted when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_interval: Option<u64>,
+    /// Icon shown before the text.
+    pub icon: String,
+    /// Icons picked by the reported percentage, lowest first.
+    pub format_icons: Vec<String>,
+    /// Background color (hex) per reported class.
+    pub colors: BTreeMap<String, String>,
+    /// Command run on left click.
+    pub on_click: String,
+    /// Command run on right click.
+    pub on_click_right: String,
+    /// Command run on middle click.
+    pub on_click_middle: String,
+    /// Command run when scrolling up.
+    pub on_scroll_up: String,
+    /// Command run when scrolling down.
+    pub on_scroll_down: String,
+}
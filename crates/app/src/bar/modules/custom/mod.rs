@@ -0,0 +1,193 @@
+//! Custom widget showing the output of a user command.
+//!
+//! Commands either run on an interval or keep running and print one update
+//! per line. Updates are plain text or JSON objects with `text`,
+//! `tooltip`, `class` and `percentage` fields.
+
+mod config;
+mod script;
+pub use config::CustomConfig;
+
+use futures_util::StreamExt;
+use gpui::{Context, ElementId, Hsla, MouseButton, ScrollWheelEvent, Window, div, prelude::*, px};
+use ui::{ActiveTheme, Colorize};
+
+use self::script::{CustomOutput, Script};
+use super::style;
+use crate::bar;
+use crate::config::ActiveConfig;
+use crate::control_center::tooltip::control_center_tooltip;
+
+/// Prefix that refers to a `[bar.modules.custom.<name>]` entry in a bar
+/// section.
+pub const CUSTOM_PREFIX: &str = "custom/";
+
+/// Widget driven by a user command.
+pub struct Custom {
+    name: String,
+    output: CustomOutput,
+    _script: Option<Script>,
+    grouped: bool,
+}
+
+impl Custom {
+    /// Create the custom widget `name` and start its command.
+    pub fn new(name: &str, grouped: bool, cx: &mut Context<Self>) -> Self {
+        let config = cx
+            .config()
+            .bar
+            .modules
+            .custom
+            .get(name)
+            .cloned()
+            .unwrap_or_default();
+
+        let script = if config.exec.trim().is_empty() {
+            tracing::warn!("Custom widget {} has no command", name);
+            None
+        } else {
+            let (script, mut updates) = Script::spawn(&config);
+            cx.spawn(async move |this, cx| {
+                while let Some(output) = updates.next().await {
+                    let updated = this.update(cx, |this, cx| {
+                        if this.output != output {
+                            this.output = output;
+                            cx.notify();
+                        }
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+            })
+            .detach();
+            Some(script)
+        };
+
+        Self {
+            name: name.to_string(),
+            output: CustomOutput::default(),
+            _script: script,
+            grouped,
+        }
+    }
+
+    fn config<'a>(&self, cx: &'a Context<Self>) -> Option<&'a CustomConfig> {
+        cx.config().bar.modules.custom.get(&self.name)
+    }
+
+    /// Run one of the configured action commands.
+    fn run(command: &str) {
+        let command = command.trim();
+        if command.is_empty() {
+            return;
+        }
+
+        let command = command.to_string();
+        std::thread::spawn(move || {
+            if let Err(err) = std::process::Command::new("sh")
+                .args(["-c", &command])
+                .status()
+            {
+                tracing::warn!("Failed to run '{}': {}", command, err);
+            }
+        });
+    }
+
+    fn icon(&self, config: &CustomConfig) -> String {
+        match self.output.percentage {
+            Some(percentage) if !config.format_icons.is_empty() => {
+                let last = config.format_icons.len() - 1;
+                let index = (percentage as usize * last + 50) / 100;
+                config.format_icons[index.min(last)].clone()
+            }
+            _ => config.icon.clone(),
+        }
+    }
+}
+
+impl Render for Custom {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let id = ElementId::Name(format!("custom-{}", self.name).into());
+        let Some(config) = self.config(cx).cloned() else {
+            return div().id(id);
+        };
+        // Nothing to show until the command prints something.
+        let icon = self.icon(&config);
+        if self.output.text.is_empty() && icon.is_empty() {
+            return div().id(id);
+        }
+
+        let theme = cx.theme();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
+        let class_color =
+            self.output
+                .class
+                .iter()
+                .find_map(|class| match config.colors.get(class) {
+                    Some(color) => Hsla::parse_hex(color).ok(),
+                    None => match class.as_str() {
+                        "warning" => Some(theme.status.warning),
+                        "critical" => Some(theme.status.error),
+                        _ => None,
+                    },
+                });
+        let background = class_color.unwrap_or(theme.interactive.default);
+        let foreground = if class_color.is_some() {
+            theme.bg.primary
+        } else {
+            theme.text.primary
+        };
+        let interactive_hover = theme.interactive.hover;
+        let icon_size = style::icon(is_vertical);
+        let text_size = style::label_size(theme, is_vertical);
+        let has_action = !config.on_click.trim().is_empty()
+            || !config.on_click_right.trim().is_empty()
+            || !config.on_click_middle.trim().is_empty();
+
+        div()
+            .id(id)
+            .flex()
+            .when(is_vertical, |this| this.flex_col())
+            .items_center()
+            .gap(px(style::CHIP_GAP))
+            .map(|el| style::chip(el, is_vertical, grouped))
+            .when(!grouped, |el| el.bg(background))
+            .text_color(foreground)
+            .when(has_action, |this| {
+                this.cursor_pointer().when(class_color.is_none(), |this| {
+                    this.hover(move |s| s.bg(interactive_hover))
+                })
+            })
+            .when_some(self.output.tooltip.clone(), |this, tooltip| {
+                this.tooltip(control_center_tooltip(tooltip))
+            })
+            .on_mouse_down(MouseButton::Left, {
+                let command = config.on_click.clone();
+                move |_, _, _| Self::run(&command)
+            })
+            .on_mouse_down(MouseButton::Right, {
+                let command = config.on_click_right.clone();
+                move |_, _, _| Self::run(&command)
+            })
+            .on_mouse_down(MouseButton::Middle, {
+                let command = config.on_click_middle.clone();
+                move |_, _, _| Self::run(&command)
+            })
+            .on_scroll_wheel(move |event: &ScrollWheelEvent, _, _| {
+                let delta = event.delta.pixel_delta(px(1.0));
+                if delta.y > px(0.5) {
+                    Self::run(&config.on_scroll_up);
+                } else if delta.y < px(-0.5) {
+                    Self::run(&config.on_scroll_down);
+                }
+            })
+            .when(!icon.is_empty(), |this| {
+                this.child(div().text_size(px(icon_size)).child(icon))
+            })
+            .when(!self.output.text.is_empty(), |this| {
+                this.child(div().text_size(text_size).child(self.output.text.clone()))
+            })
+    }
+}
@@ -0,0 +1,305 @@
+//! Running custom widget commands and parsing their output.
+
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use rustix::process::{Pid, Signal, kill_process_group};
+
+use super::CustomConfig;
+
+/// One update printed by a custom widget command.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CustomOutput {
+    pub text: String,
+    pub tooltip: Option<String>,
+    pub class: Vec<String>,
+    /// Percentage in `0..=100`, used to pick an icon.
+    pub percentage: Option<u32>,
+}
+
+impl CustomOutput {
+    /// Parse one line of output: a JSON object or plain text.
+    pub fn parse_line(line: &str) -> Self {
+        let line = line.trim();
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(serde_json::Value::Object(object)) => Self::from_json(&object),
+            _ => Self {
+                text: line.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Parse the output of one interval run: a JSON object, or the text
+    /// on the first line and the tooltip on the second.
+    pub fn parse(output: &str) -> Self {
+        if let Ok(serde_json::Value::Object(object)) = serde_json::from_str(output.trim()) {
+            return Self::from_json(&object);
+        }
+        let mut lines = output.lines();
+        Self {
+            text: lines.next().unwrap_or_default().trim().to_string(),
+            tooltip: lines
+                .next()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty()),
+            ..Default::default()
+        }
+    }
+
+    fn from_json(object: &serde_json::Map<String, serde_json::Value>) -> Self {
+        let string = |key: &str| object.get(key).and_then(|v| v.as_str()).map(String::from);
+        // `class` may be a single class or a list of classes.
+        let class = match object.get("class") {
+            Some(serde_json::Value::String(class)) => vec![class.clone()],
+            Some(serde_json::Value::Array(classes)) => classes
+                .iter()
+                .filter_map(|c| c.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            text: string("text").unwrap_or_default(),
+            tooltip: string("tooltip").filter(|t| !t.is_empty()),
+            class,
+            percentage: object
+                .get("percentage")
+                .and_then(|v| v.as_f64())
+                .map(|p| p.round().clamp(0.0, 100.0) as u32),
+        }
+    }
+}
+
+/// Keeps a custom command running; stops it when dropped.
+pub struct Script {
+    stopped: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl Script {
+    /// Start the command of `config` on a background thread.
+    ///
+    /// Every update is sent to the returned receiver.
+    pub fn spawn(config: &CustomConfig) -> (Self, mpsc::UnboundedReceiver<CustomOutput>) {
+        let (tx, rx) = mpsc::unbounded();
+        let script = Self {
+            stopped: Arc::new(AtomicBool::new(false)),
+            child: Arc::new(Mutex::new(None)),
+        };
+
+        let exec = config.exec.clone();
+        let interval = config.interval;
+        let restart_interval = config.restart_interval;
+        let stopped = script.stopped.clone();
+        let child = script.child.clone();
+        thread::spawn(move || match interval {
+            Some(seconds) => run_interval(
+                &exec,
+                Duration::from_secs(seconds.max(1)),
+                &stopped,
+                &child,
+                &tx,
+            ),
+            None => run_continuous(&exec, restart_interval, &stopped, &child, &tx),
+        });
+
+        (script, rx)
+    }
+}
+
+impl Drop for Script {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        stop(&self.child);
+    }
+}
+
+/// Start `exec` in its own process group and keep it in `slot`.
+///
+/// Returns the command's stdout, or `None` if it failed to start or the
+/// script was stopped meanwhile.
+fn start(exec: &str, stopped: &AtomicBool, slot: &Mutex<Option<Child>>) -> Option<ChildStdout> {
+    let spawned = Command::new("sh")
+        .args(["-c", exec])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(err) => {
+            tracing::warn!("Failed to run custom widget '{}': {}", exec, err);
+            return None;
+        }
+    };
+
+    let stdout = child.stdout.take();
+    let mut slot = slot.lock().unwrap();
+    // Checked under the lock: a drop during the spawn found the slot empty,
+    // so nobody else will stop this child.
+    if stopped.load(Ordering::SeqCst) {
+        kill(child);
+        return None;
+    }
+    *slot = Some(child);
+    stdout
+}
+
+/// Stop the command in `slot`, if there is one.
+fn stop(slot: &Mutex<Option<Child>>) {
+    if let Some(child) = slot.lock().unwrap().take() {
+        kill(child);
+    }
+}
+
+/// Kill the process group of `child`, so anything the command started goes
+/// too, and reap the child.
+fn kill(mut child: Child) {
+    let _ = kill_process_group(Pid::from_child(&child), Signal::KILL);
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Read `stdout` until it closes, or `None` if it is still open after
+/// `timeout`.
+fn read_output(mut stdout: ChildStdout, timeout: Duration) -> Option<String> {
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = stdout.read_to_end(&mut output);
+        let _ = done_tx.send(output);
+    });
+    done_rx
+        .recv_timeout(timeout)
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output).into_owned())
+}
+
+/// Run `exec` every `interval`. A run still going after `interval` is
+/// killed.
+fn run_interval(
+    exec: &str,
+    interval: Duration,
+    stopped: &AtomicBool,
+    slot: &Mutex<Option<Child>>,
+    tx: &mpsc::UnboundedSender<CustomOutput>,
+) {
+    while !stopped.load(Ordering::SeqCst) {
+        if let Some(stdout) = start(exec, stopped, slot) {
+            let output = read_output(stdout, interval);
+            stop(slot);
+            match output {
+                Some(output) => {
+                    if tx.unbounded_send(CustomOutput::parse(&output)).is_err() {
+                        return;
+                    }
+                }
+                None => tracing::warn!(
+                    "Custom widget '{}' timed out after {}s",
+                    exec,
+                    interval.as_secs()
+                ),
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn run_continuous(
+    exec: &str,
+    restart_interval: Option<u64>,
+    stopped: &AtomicBool,
+    slot: &Mutex<Option<Child>>,
+    tx: &mpsc::UnboundedSender<CustomOutput>,
+) {
+    while !stopped.load(Ordering::SeqCst) {
+        if let Some(stdout) = start(exec, stopped, slot) {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if tx.unbounded_send(CustomOutput::parse_line(&line)).is_err() {
+                    break;
+                }
+            }
+            stop(slot);
+        }
+
+        if stopped.load(Ordering::SeqCst) || tx.is_closed() {
+            return;
+        }
+        let Some(seconds) = restart_interval else {
+            tracing::warn!("Custom widget '{}' exited", exec);
+            return;
+        };
+        thread::sleep(Duration::from_secs(seconds.max(1)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_plain_text() {
+        assert_eq!(
+            CustomOutput::parse_line("  12°C \n"),
+            CustomOutput {
+                text: "12°C".to_string(),
+                ..Default::default()
+            }
+        );
+        // JSON that is not an object is shown as text.
+        assert_eq!(CustomOutput::parse_line("[1, 2]").text, "[1, 2]");
+    }
+
+    #[test]
+    fn test_parse_line_json() {
+        let output = CustomOutput::parse_line(
+            r#"{"text": "VPN", "tooltip": "", "class": "connected", "percentage": 42.6}"#,
+        );
+        assert_eq!(
+            output,
+            CustomOutput {
+                text: "VPN".to_string(),
+                tooltip: None,
+                class: vec!["connected".to_string()],
+                percentage: Some(43),
+            }
+        );
+
+        let output = CustomOutput::parse_line(r#"{"class": ["a", 1, "b"], "percentage": 250}"#);
+        assert_eq!(output.text, "");
+        assert_eq!(output.class, vec!["a", "b"]);
+        assert_eq!(output.percentage, Some(100));
+    }
+
+    #[test]
+    fn test_parse_text_and_tooltip() {
+        assert_eq!(
+            CustomOutput::parse("3 updates\n  firefox, mesa, linux  \nignored\n"),
+            CustomOutput {
+                text: "3 updates".to_string(),
+                tooltip: Some("firefox, mesa, linux".to_string()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(CustomOutput::parse("text\n\n").tooltip, None);
+        assert_eq!(CustomOutput::parse(""), CustomOutput::default());
+    }
+
+    #[test]
+    fn test_parse_multiline_json() {
+        let output = CustomOutput::parse("{\n  \"text\": \"on\",\n  \"tooltip\": \"oncall\"\n}\n");
+        assert_eq!(output.text, "on");
+        assert_eq!(output.tooltip.as_deref(), Some("oncall"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Widget groups: several widgets rendered inside one pill.
+//!
+//! Member widgets render bare (see [`style::chip`]), so the group's pill
+//! is the only chrome.
+
+use gpui::{Context, Window, div, prelude::*, px};
+use ui::ActiveTheme;
+
+use super::Widget;
+use super::style;
+use crate::bar;
+use crate::config::ActiveConfig;
+
+/// Prefix that refers to a `[bar.groups.<name>]` entry in a bar section.
+pub const GROUP_PREFIX: &str = "group/";
+
+/// A named group of widgets sharing one pill.
+pub struct WidgetGroup {
+    name: String,
+    widgets: Vec<Widget>,
+}
+
+impl WidgetGroup {
+    /// Create the group `name` from the bar config.
+    pub fn new(name: &str, cx: &mut Context<Self>) -> Self {
+        let names: Vec<String> = cx
+            .config()
+            .bar
+            .groups
+            .get(name)
+            .map(|group| group.widgets.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|widget| {
+                // Groups do not nest.
+                let nested = widget.starts_with(GROUP_PREFIX);
+                if nested {
+                    tracing::warn!("Ignoring group {} inside group {}", widget, name);
+                }
+                !nested
+            })
+            .collect();
+
+        // Members drop their own chip chrome and share the group's pill.
+        Self {
+            name: name.to_string(),
+            widgets: Widget::create_many(&names, true, cx),
+        }
+    }
+}
+
+impl Render for WidgetGroup {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.widgets.is_empty() {
+            return div();
+        }
+
+        let theme = cx.theme();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let group = cx.config().bar.groups.get(&self.name);
+        let spacing = group.map_or(style::CHIP_GAP, |g| g.spacing);
+        let separator = group.map(|g| g.separator.clone()).unwrap_or_default();
+        let text_muted = theme.text.muted;
+        let text_size = style::label_size(theme, is_vertical);
+
+        let mut children = Vec::with_capacity(self.widgets.len() * 2);
+        for (i, widget) in self.widgets.iter().enumerate() {
+            if i > 0 && !separator.is_empty() {
+                children.push(
+                    div()
+                        .text_size(text_size)
+                        .text_color(text_muted)
+                        .child(separator.clone())
+                        .into_any_element(),
+                );
+            }
+            children.push(widget.render());
+        }
+
+        style::pill(theme, is_vertical)
+            .gap(px(spacing))
+            .children(children)
+    }
+}
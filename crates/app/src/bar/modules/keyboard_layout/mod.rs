@@ -5,9 +5,10 @@ pub use config::KeyboardLayoutConfig;
 
 use gpui::{Context, MouseButton, Window, div, prelude::*, px};
 use services::{CompositorCommand, CompositorState};
-use ui::{ActiveTheme, radius};
+use ui::ActiveTheme;
 
 use super::style;
+use crate::bar;
 use crate::config::ActiveConfig;
 use crate::state::AppState;
 use crate::state::watch;
@@ -18,11 +19,12 @@ const KEYBOARD_ICON: &str = "󰌌";
 pub struct KeyboardLayout {
     compositor: services::CompositorSubscriber,
     state: CompositorState,
+    grouped: bool,
 }
 
 impl KeyboardLayout {
     /// Create a new KeyboardLayout widget.
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         let compositor = AppState::compositor(cx).clone();
         let state = compositor.get();
 
@@ -36,7 +38,11 @@ impl KeyboardLayout {
             },
         );
 
-        Self { compositor, state }
+        Self {
+            compositor,
+            state,
+            grouped,
+        }
     }
 
     /// Cycle to the next keyboard layout.
@@ -140,9 +146,10 @@ impl KeyboardLayout {
 }
 
 impl Render for KeyboardLayout {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
         let config = &cx.config().bar.modules.keyboard_layout;
         let short_name = self.short_layout_name();
         let icon = if config.show_flag {
@@ -166,11 +173,9 @@ impl Render for KeyboardLayout {
             .when(is_vertical, |this| this.flex_col())
             .items_center()
             .gap(px(style::CHIP_GAP))
-            .px(px(style::chip_padding_x(is_vertical)))
-            .py(px(style::CHIP_PADDING_Y))
-            .rounded(px(radius::SM))
+            .map(|el| style::chip(el, is_vertical, grouped))
             .cursor_pointer()
-            .bg(interactive_default)
+            .when(!grouped, |el| el.bg(interactive_default))
             .hover(move |s| s.bg(interactive_hover))
             .active(move |s| s.bg(interactive_active))
             // Click to cycle layout
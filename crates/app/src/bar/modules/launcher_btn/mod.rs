@@ -5,27 +5,31 @@ pub use config::LauncherBtnConfig;
 
 use crate::launcher;
 use gpui::{Context, MouseButton, Window, div, prelude::*, px};
-use ui::{ActiveTheme, radius};
+use ui::ActiveTheme;
 
 use super::style;
+use crate::bar;
 use crate::config::ActiveConfig;
 
 /// A button widget that opens the launcher when clicked.
-pub struct LauncherBtn;
+pub struct LauncherBtn {
+    grouped: bool,
+}
 
 const LAUNCHER_ICON: &str = "󰀻";
 
 impl LauncherBtn {
     /// Create a new launcher button.
-    pub fn new(_cx: &mut Context<Self>) -> Self {
-        LauncherBtn
+    pub fn new(grouped: bool, _cx: &mut Context<Self>) -> Self {
+        LauncherBtn { grouped }
     }
 }
 
 impl Render for LauncherBtn {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
         let config = &cx.config().bar.modules.launcher_btn;
         let icon = if config.icon.trim().is_empty() {
             LAUNCHER_ICON.to_string()
@@ -44,11 +48,9 @@ impl Render for LauncherBtn {
             .flex()
             .items_center()
             .justify_center()
-            .px(px(style::chip_padding_x(is_vertical)))
-            .py(px(style::CHIP_PADDING_Y))
-            .rounded(px(radius::SM))
+            .map(|el| style::chip(el, is_vertical, grouped))
             .cursor_pointer()
-            .bg(interactive_default)
+            .when(!grouped, |el| el.bg(interactive_default))
             .hover(move |s| s.bg(interactive_hover))
             .active(move |s| s.bg(interactive_active))
             .on_mouse_down(
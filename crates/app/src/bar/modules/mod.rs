@@ -3,24 +3,32 @@
 mod active_window;
 mod battery;
 mod clock;
+mod custom;
+mod group;
 mod keyboard_layout;
 mod launcher_btn;
 mod mpris;
 mod registry;
 pub mod settings;
 pub(crate) mod style;
+mod submap;
 pub mod sysinfo;
+mod taskbar;
 mod tray;
 mod workspaces;
 
 pub use active_window::{ActiveWindow, ActiveWindowConfig};
 pub use battery::{Battery, BatteryConfig};
 pub use clock::{Clock, ClockConfig};
+pub use custom::{CUSTOM_PREFIX, Custom, CustomConfig};
+pub use group::{GROUP_PREFIX, WidgetGroup};
 pub use keyboard_layout::{KeyboardLayout, KeyboardLayoutConfig};
 pub use launcher_btn::{LauncherBtn, LauncherBtnConfig};
 pub use mpris::{Mpris, MprisConfig};
 pub use registry::Widget;
 pub use settings::{Settings, SettingsConfig};
+pub use submap::{Submap, SubmapConfig};
 pub use sysinfo::{SysInfo, SysInfoConfig};
+pub use taskbar::{Taskbar, TaskbarConfig};
 pub use tray::{Tray, TrayConfig};
 pub use workspaces::{Workspaces, WorkspacesConfig};
@@ -5,10 +5,11 @@ pub use config::MprisConfig;
 
 use gpui::{App, Context, MouseButton, Size, Window, div, prelude::*, px};
 use services::{MprisData, PlaybackStatus};
-use ui::{ActiveTheme, radius};
+use ui::ActiveTheme;
 
 use super::style;
-use crate::config::{ActiveConfig, Config};
+use crate::bar;
+use crate::config::ActiveConfig;
 use crate::panel::{PanelConfig, panel_placement_from_event, toggle_panel};
 use crate::state::AppState;
 use crate::state::watch;
@@ -26,10 +27,11 @@ mod icons {
 pub struct Mpris {
     subscriber: services::MprisSubscriber,
     data: MprisData,
+    grouped: bool,
 }
 
 impl Mpris {
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         let subscriber = AppState::mpris(cx).clone();
         let data = subscriber.get();
 
@@ -38,15 +40,18 @@ impl Mpris {
             cx.notify();
         });
 
-        Self { subscriber, data }
+        Self {
+            subscriber,
+            data,
+            grouped,
+        }
     }
 
     fn toggle_panel(&self, event: &gpui::MouseDownEvent, window: &Window, cx: &mut App) {
         let subscriber = self.subscriber.clone();
-        let config = Config::global(cx);
         let panel_size = Size::new(px(380.0), px(420.0));
         let (anchor, margin) =
-            panel_placement_from_event(config.bar.position, event, window, cx, panel_size);
+            panel_placement_from_event(bar::position(window, cx), event, window, cx, panel_size);
         let config = PanelConfig {
             width: 380.0,
             height: 420.0,
@@ -99,9 +104,10 @@ impl Mpris {
 }
 
 impl Render for Mpris {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
         let config = &cx.config().bar.modules.mpris;
         let icon = self.icon();
         let label = self.label();
@@ -119,12 +125,10 @@ impl Render for Mpris {
             .when(is_vertical, |this| this.flex_col())
             .items_center()
             .gap(px(style::CHIP_GAP))
-            .px(px(style::chip_padding_x(is_vertical)))
-            .py(px(style::CHIP_PADDING_Y))
+            .map(|el| style::chip(el, is_vertical, grouped))
             .max_w(px(config.max_width))
-            .rounded(px(radius::SM))
             .cursor_pointer()
-            .bg(interactive_default)
+            .when(!grouped, |el| el.bg(interactive_default))
             .hover(move |s| s.bg(interactive_hover))
             .active(move |s| s.bg(interactive_active))
             .on_mouse_down(
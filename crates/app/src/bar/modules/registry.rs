@@ -3,15 +3,109 @@
 //! This module provides a registry pattern that allows widgets to be created
 //! by name, enabling configuration-driven bar layouts.
 
-use gpui::{AnyElement, Context, Entity, prelude::*};
+use gpui::{AnyElement, App, Context, Entity, prelude::*};
+use services::{CompositorBackend, ServiceStatus};
 
+use crate::config::ActiveConfig;
 use crate::notification::NotificationWidget;
+use crate::state::AppState;
 
 use super::{
-    ActiveWindow, Battery, Clock, KeyboardLayout, LauncherBtn, Mpris, Settings, SysInfo, Tray,
-    Workspaces,
+    ActiveWindow, Battery, CUSTOM_PREFIX, Clock, Custom, GROUP_PREFIX, KeyboardLayout, LauncherBtn,
+    Mpris, Settings, Submap, SysInfo, Taskbar, Tray, WidgetGroup, Workspaces,
 };
 
+/// Built-in widget types, resolved from their config names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WidgetKind {
+    ActiveWindow,
+    Clock,
+    Battery,
+    Workspaces,
+    Taskbar,
+    KeyboardLayout,
+    Submap,
+    Tray,
+    SysInfo,
+    LauncherBtn,
+    Mpris,
+    Notification,
+    Settings,
+}
+
+impl WidgetKind {
+    /// Resolve a widget name, including aliases.
+    fn from_name(name: &str) -> Option<Self> {
+        let kind = match name {
+            "ActiveWindow" | "WindowTitle" => Self::ActiveWindow,
+            "Clock" => Self::Clock,
+            "Battery" => Self::Battery,
+            "Workspaces" => Self::Workspaces,
+            "Taskbar" | "Windows" => Self::Taskbar,
+            "KeyboardLayout" => Self::KeyboardLayout,
+            "Submap" | "Mode" => Self::Submap,
+            "Systray" | "Tray" => Self::Tray,
+            "SysInfo" => Self::SysInfo,
+            "LauncherBtn" | "Launcher" => Self::LauncherBtn,
+            "Mpris" | "Media" | "Player" => Self::Mpris,
+            "Notification" | "Notifications" => Self::Notification,
+            "Settings" | "Info" | "ControlCenter" => Self::Settings,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Whether the service backing this widget is available.
+    ///
+    /// Widgets whose service could not start are left out of the bar.
+    fn is_available(self, cx: &App) -> bool {
+        let status = match self {
+            Self::ActiveWindow | Self::Workspaces | Self::Taskbar | Self::KeyboardLayout => {
+                AppState::compositor(cx).status()
+            }
+            // Niri has no keybind modes to show.
+            Self::Submap => {
+                let compositor = AppState::compositor(cx);
+                if compositor.backend() == Some(CompositorBackend::Niri) {
+                    return false;
+                }
+                compositor.status()
+            }
+            Self::Battery => AppState::upower(cx).status(),
+            Self::Tray => AppState::tray(cx).status(),
+            Self::Mpris => AppState::mpris(cx).status(),
+            Self::Clock
+            | Self::SysInfo
+            | Self::LauncherBtn
+            | Self::Notification
+            | Self::Settings => return true,
+        };
+        status != ServiceStatus::Unavailable
+    }
+
+    fn create<V: 'static>(self, grouped: bool, cx: &mut Context<V>) -> Widget {
+        match self {
+            Self::ActiveWindow => Widget::ActiveWindow(cx.new(|cx| ActiveWindow::new(grouped, cx))),
+            Self::Clock => Widget::Clock(cx.new(|cx| Clock::new(grouped, cx))),
+            Self::Battery => Widget::Battery(cx.new(|cx| Battery::new(grouped, cx))),
+            Self::Workspaces => Widget::Workspaces(cx.new(Workspaces::new)),
+            Self::Taskbar => Widget::Taskbar(cx.new(Taskbar::new)),
+            Self::KeyboardLayout => {
+                Widget::KeyboardLayout(cx.new(|cx| KeyboardLayout::new(grouped, cx)))
+            }
+            Self::Submap => Widget::Submap(cx.new(|cx| Submap::new(grouped, cx))),
+            Self::Tray => Widget::Tray(cx.new(Tray::new)),
+            Self::SysInfo => Widget::SysInfo(cx.new(|cx| SysInfo::new(grouped, cx))),
+            Self::LauncherBtn => Widget::LauncherBtn(cx.new(|cx| LauncherBtn::new(grouped, cx))),
+            Self::Mpris => Widget::Mpris(cx.new(|cx| Mpris::new(grouped, cx))),
+            Self::Notification => {
+                Widget::Notification(cx.new(|cx| NotificationWidget::new(grouped, cx)))
+            }
+            Self::Settings => Widget::Settings(cx.new(|cx| Settings::new(grouped, cx))),
+        }
+    }
+}
+
 /// Wrapper enum for all possible widget types.
 ///
 /// Each variant holds an Entity handle to a specific widget type.
@@ -22,13 +116,17 @@ pub enum Widget {
     Clock(Entity<Clock>),
     Battery(Entity<Battery>),
     Workspaces(Entity<Workspaces>),
+    Taskbar(Entity<Taskbar>),
     KeyboardLayout(Entity<KeyboardLayout>),
+    Submap(Entity<Submap>),
     Tray(Entity<Tray>),
     SysInfo(Entity<SysInfo>),
     LauncherBtn(Entity<LauncherBtn>),
     Notification(Entity<NotificationWidget>),
     Settings(Entity<Settings>),
     Mpris(Entity<Mpris>),
+    Group(Entity<WidgetGroup>),
+    Custom(Entity<Custom>),
 }
 
 impl Widget {
@@ -41,46 +139,77 @@ impl Widget {
             Widget::Clock(e) => e.clone().into_any_element(),
             Widget::Battery(e) => e.clone().into_any_element(),
             Widget::Workspaces(e) => e.clone().into_any_element(),
+            Widget::Taskbar(e) => e.clone().into_any_element(),
             Widget::KeyboardLayout(e) => e.clone().into_any_element(),
+            Widget::Submap(e) => e.clone().into_any_element(),
             Widget::Tray(e) => e.clone().into_any_element(),
             Widget::SysInfo(e) => e.clone().into_any_element(),
             Widget::LauncherBtn(e) => e.clone().into_any_element(),
             Widget::Notification(e) => e.clone().into_any_element(),
             Widget::Settings(e) => e.clone().into_any_element(),
             Widget::Mpris(e) => e.clone().into_any_element(),
+            Widget::Group(e) => e.clone().into_any_element(),
+            Widget::Custom(e) => e.clone().into_any_element(),
         }
     }
 
+    /// Whether `name` refers to a known widget, a widget group or a custom
+    /// widget.
+    ///
+    /// Group and custom widget names are not checked against the config.
+    pub fn is_known(name: &str) -> bool {
+        WidgetKind::from_name(name).is_some()
+            || name.starts_with(GROUP_PREFIX)
+            || name.starts_with(CUSTOM_PREFIX)
+    }
+
     /// Create a widget by name.
     ///
-    /// Returns `None` if the widget name is unknown.
-    pub fn create<V: 'static>(name: &str, cx: &mut Context<V>) -> Option<Widget> {
-        match name {
-            "ActiveWindow" | "WindowTitle" => Some(Widget::ActiveWindow(cx.new(ActiveWindow::new))),
-            "Clock" => Some(Widget::Clock(cx.new(Clock::new))),
-            "Battery" => Some(Widget::Battery(cx.new(Battery::new))),
-            "Workspaces" => Some(Widget::Workspaces(cx.new(Workspaces::new))),
-            "KeyboardLayout" => Some(Widget::KeyboardLayout(cx.new(KeyboardLayout::new))),
-            "Systray" | "Tray" => Some(Widget::Tray(cx.new(Tray::new))),
-            "SysInfo" => Some(Widget::SysInfo(cx.new(SysInfo::new))),
-            "LauncherBtn" | "Launcher" => Some(Widget::LauncherBtn(cx.new(LauncherBtn::new))),
-            "Mpris" | "Media" | "Player" => Some(Widget::Mpris(cx.new(Mpris::new))),
-            "Notification" | "Notifications" => {
-                Some(Widget::Notification(cx.new(NotificationWidget::new)))
+    /// Members of a [`WidgetGroup`] are created `grouped`, so they render
+    /// inside the group's pill without chrome of their own.
+    ///
+    /// Returns `None` if the widget name is unknown or its service is
+    /// unavailable.
+    pub fn create<V: 'static>(name: &str, grouped: bool, cx: &mut Context<V>) -> Option<Widget> {
+        if let Some(kind) = WidgetKind::from_name(name) {
+            if !kind.is_available(cx) {
+                tracing::info!("Hiding {} widget: service unavailable", name);
+                return None;
             }
-            "Settings" | "Info" | "ControlCenter" => Some(Widget::Settings(cx.new(Settings::new))),
-            _ => {
-                tracing::warn!("Unknown widget: {}", name);
-                None
+            return Some(kind.create(grouped, cx));
+        }
+
+        if let Some(group) = name.strip_prefix(GROUP_PREFIX) {
+            if !cx.config().bar.groups.contains_key(group) {
+                tracing::warn!("Unknown widget group: {}", group);
+                return None;
             }
+            return Some(Widget::Group(cx.new(|cx| WidgetGroup::new(group, cx))));
         }
+
+        if let Some(custom) = name.strip_prefix(CUSTOM_PREFIX) {
+            if !cx.config().bar.modules.custom.contains_key(custom) {
+                tracing::warn!("Unknown custom widget: {}", custom);
+                return None;
+            }
+            return Some(Widget::Custom(
+                cx.new(|cx| Custom::new(custom, grouped, cx)),
+            ));
+        }
+
+        tracing::warn!("Unknown widget: {}", name);
+        None
     }
 
     /// Create multiple widgets from a config list.
-    pub fn create_many<V: 'static>(widgets: &[String], cx: &mut Context<V>) -> Vec<Widget> {
+    pub fn create_many<V: 'static>(
+        widgets: &[String],
+        grouped: bool,
+        cx: &mut Context<V>,
+    ) -> Vec<Widget> {
         widgets
             .iter()
-            .filter_map(|widget| Widget::create(widget, cx))
+            .filter_map(|widget| Widget::create(widget, grouped, cx))
             .collect()
     }
 }
@@ -2,7 +2,7 @@
 //!
 //! This widget displays:
 //! - Privacy indicators (mic, webcam, screenshare) when active
-//! - Volume icon
+//! - Volume icon (scroll to change the volume)
 //! - WiFi/Network icon
 //! - Bluetooth icon (when connected)
 //! - Power profile icon
@@ -10,22 +10,23 @@
 //!
 //! Clicking opens the Control Center panel.
 
-use gpui::{Context, MouseButton, Size, Window, div, prelude::*, px};
+use gpui::{Context, MouseButton, ScrollWheelEvent, Size, Window, div, prelude::*, px};
 use services::{
-    ActiveConnectionInfo, AudioData, BluetoothData, BluetoothState, NetworkData, PrivacyData,
-    UPowerData,
+    ActiveConnectionInfo, AudioCommand, AudioData, BluetoothData, BluetoothState, NetworkData,
+    PrivacyData, ServiceStatus, UPowerData,
 };
-use ui::{ActiveTheme, radius};
+use ui::ActiveTheme;
 
 mod config;
 pub use config::SettingsConfig;
 
 use super::style;
-use crate::config::{ActiveConfig, Config};
+use crate::bar;
+use crate::config::ActiveConfig;
 use crate::control_center::{
-    CONTROL_CENTER_PANEL_HEIGHT_COLLAPSED, CONTROL_CENTER_PANEL_WIDTH, ControlCenter,
+    self, CONTROL_CENTER_PANEL_HEIGHT_COLLAPSED, CONTROL_CENTER_PANEL_WIDTH,
 };
-use crate::panel::{PanelConfig, panel_placement_from_event, toggle_panel};
+use crate::panel::panel_placement_from_event;
 use crate::state::{AppState, watch};
 
 /// Nerd Font icons for status display.
@@ -56,16 +57,20 @@ mod icons {
 /// Settings widget for the bar that shows system status icons.
 pub struct Settings {
     audio: AudioData,
+    /// Whether PulseAudio could be reached; the volume icon is hidden if not.
+    audio_available: bool,
     bluetooth: BluetoothData,
     network: NetworkData,
     privacy: PrivacyData,
     upower: UPowerData,
+    grouped: bool,
 }
 
 impl Settings {
     /// Create a new settings widget.
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         let audio = AppState::audio(cx).get();
+        let audio_available = AppState::audio(cx).status() != ServiceStatus::Unavailable;
         let bluetooth = AppState::bluetooth(cx).get();
         let network = AppState::network(cx).get();
         let privacy = AppState::privacy(cx).get();
@@ -77,6 +82,16 @@ impl Settings {
             cx.notify();
         });
 
+        // Hide the volume icon when the audio service fails to start
+        watch(
+            cx,
+            AppState::audio(cx).subscribe_status(),
+            |this, status, cx| {
+                this.audio_available = status != ServiceStatus::Unavailable;
+                cx.notify();
+            },
+        );
+
         // Subscribe to bluetooth updates
         watch(cx, AppState::bluetooth(cx).subscribe(), |this, data, cx| {
             this.bluetooth = data;
@@ -103,33 +118,24 @@ impl Settings {
 
         Settings {
             audio,
+            audio_available,
             bluetooth,
             network,
             privacy,
             upower,
+            grouped,
         }
     }
 
     /// Toggle the control center panel.
     fn toggle_panel(&self, event: &gpui::MouseDownEvent, window: &Window, cx: &mut gpui::App) {
-        let config = Config::global(cx);
         let panel_size = Size::new(
             px(CONTROL_CENTER_PANEL_WIDTH),
             px(CONTROL_CENTER_PANEL_HEIGHT_COLLAPSED),
         );
         let (anchor, margin) =
-            panel_placement_from_event(config.bar.position, event, window, cx, panel_size);
-        let config = PanelConfig {
-            width: CONTROL_CENTER_PANEL_WIDTH,
-            height: CONTROL_CENTER_PANEL_HEIGHT_COLLAPSED,
-            anchor,
-            margin,
-            namespace: "control-center".to_string(),
-        };
-
-        toggle_panel("control-center", config, cx, move |cx| {
-            ControlCenter::new(cx)
-        });
+            panel_placement_from_event(bar::position(window, cx), event, window, cx, panel_size);
+        control_center::toggle_at(anchor, margin, cx);
     }
 
     /// Get privacy indicator icons (only when active).
@@ -245,9 +251,10 @@ impl Settings {
 }
 
 impl Render for Settings {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
 
         let privacy_icons = self.privacy_icons(is_vertical);
         let has_privacy = !privacy_icons.is_empty();
@@ -259,6 +266,7 @@ impl Render for Settings {
         let battery_text = self.battery_text(is_vertical);
         let icon_size = style::icon(is_vertical);
         let text_size = style::label_size(theme, is_vertical);
+        let volume_step = cx.config().audio.step_delta();
 
         // Get the battery icon color
         let battery_color = match &self.upower.battery {
@@ -290,11 +298,9 @@ impl Render for Settings {
             .when(is_vertical, |this| this.flex_col())
             .items_center()
             .gap(px(style::CHIP_GAP))
-            .px(px(style::chip_padding_x(is_vertical)))
-            .py(px(style::CHIP_PADDING_Y))
-            .rounded(px(radius::SM))
+            .map(|el| style::chip(el, is_vertical, grouped))
             .cursor_pointer()
-            .bg(interactive_default)
+            .when(!grouped, |el| el.bg(interactive_default))
             .hover(move |s| s.bg(interactive_hover))
             .active(move |s| s.bg(interactive_active))
             .on_mouse_down(
@@ -319,12 +325,26 @@ impl Render for Settings {
                 )
             })
             // Volume icon
-            .child(
-                div()
-                    .text_size(px(icon_size))
-                    .text_color(text_primary)
-                    .child(volume_icon),
-            )
+            .when(self.audio_available, |el| {
+                el.child(
+                    div()
+                        .id("settings-volume")
+                        .text_size(px(icon_size))
+                        .text_color(text_primary)
+                        .on_scroll_wheel(move |event: &ScrollWheelEvent, _, cx| {
+                            let delta = event.delta.pixel_delta(px(1.0));
+                            let command = if delta.y > px(0.5) {
+                                AudioCommand::AdjustSinkVolume(volume_step)
+                            } else if delta.y < px(-0.5) {
+                                AudioCommand::AdjustSinkVolume(-volume_step)
+                            } else {
+                                return;
+                            };
+                            AppState::audio(cx).dispatch(command);
+                        })
+                        .child(volume_icon),
+                )
+            })
             // Network icon
             .child(
                 div()
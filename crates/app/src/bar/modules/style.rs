@@ -2,7 +2,8 @@
 //!
 //! Font sizes should be accessed from `theme.font_sizes` (xs/sm for vertical, sm/md for horizontal).
 
-use ui::{icon_size, spacing};
+use gpui::{Div, div, prelude::*, px};
+use ui::{icon_size, radius, spacing};
 
 /// Common gap used inside compact bar widgets.
 pub const CHIP_GAP: f32 = spacing::XS;
@@ -33,6 +34,33 @@ pub fn chip_padding_x(is_vertical: bool) -> f32 {
     }
 }
 
+/// Padding and rounding of a standalone widget chip.
+///
+/// Widgets inside a [group](super::WidgetGroup) render bare, without
+/// padding or background, and share the group's pill instead.
+pub fn chip<E: Styled>(el: E, is_vertical: bool, grouped: bool) -> E {
+    if grouped {
+        el
+    } else {
+        el.px(px(chip_padding_x(is_vertical)))
+            .py(px(CHIP_PADDING_Y))
+            .rounded(px(radius::SM))
+    }
+}
+
+/// Container with the standard pill look of compact bar widgets.
+pub fn pill(theme: &ui::Theme, is_vertical: bool) -> Div {
+    div()
+        .flex()
+        .when(is_vertical, |this| this.flex_col())
+        .items_center()
+        .gap(px(CHIP_GAP))
+        .px(px(chip_padding_x(is_vertical)))
+        .py(px(CHIP_PADDING_Y))
+        .rounded(px(radius::SM))
+        .bg(theme.interactive.default)
+}
+
 /// Icon size tuned for bar density.
 #[inline(always)]
 pub fn icon(is_vertical: bool) -> f32 {
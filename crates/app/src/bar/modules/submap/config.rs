@@ -0,0 +1,27 @@
+//! Submap module configuration.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Submap (keybind mode) module configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubmapConfig {
+    /// Icon shown before the mode label.
+    pub icon: String,
+    /// Label per mode name; modes without an entry show their name.
+    pub labels: BTreeMap<String, String>,
+    /// Background color (hex) per mode name; others use the accent color.
+    pub colors: BTreeMap<String, String>,
+}
+
+impl Default for SubmapConfig {
+    fn default() -> Self {
+        Self {
+            icon: "󰌌".to_string(),
+            labels: BTreeMap::new(),
+            colors: BTreeMap::new(),
+        }
+    }
+}
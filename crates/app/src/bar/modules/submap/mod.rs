@@ -0,0 +1,80 @@
+//! Submap widget showing the active compositor keybind mode.
+
+mod config;
+pub use config::SubmapConfig;
+
+use gpui::{Context, Hsla, Window, div, prelude::*, px};
+use ui::{ActiveTheme, Colorize};
+
+use super::style;
+use crate::bar;
+use crate::config::ActiveConfig;
+use crate::state::AppState;
+use crate::state::watch;
+
+/// Submap widget that displays the active keybind mode (e.g. resize).
+///
+/// Renders nothing while no mode is active.
+pub struct Submap {
+    submap: Option<String>,
+    grouped: bool,
+}
+
+impl Submap {
+    /// Create a new Submap widget.
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
+        let compositor = AppState::compositor(cx).clone();
+        let submap = compositor.get().submap;
+
+        // Subscribe to compositor state changes
+        watch(cx, compositor.subscribe(), |this, new_state, cx| {
+            if this.submap != new_state.submap {
+                this.submap = new_state.submap;
+                cx.notify();
+            }
+        });
+
+        Self { submap, grouped }
+    }
+}
+
+impl Render for Submap {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(mode) = self.submap.clone() else {
+            return div().id("submap");
+        };
+
+        let theme = cx.theme();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
+        let config = &cx.config().bar.modules.submap;
+        let label = config.labels.get(&mode).cloned().unwrap_or(mode.clone());
+        let label = if is_vertical {
+            label.chars().take(3).collect::<String>().to_uppercase()
+        } else {
+            label
+        };
+        let background = config
+            .colors
+            .get(&mode)
+            .and_then(|color| Hsla::parse_hex(color).ok())
+            .unwrap_or(theme.accent.primary);
+        let foreground = theme.bg.primary;
+        let icon_size = style::icon(is_vertical);
+        let text_size = style::label_size(theme, is_vertical);
+
+        div()
+            .id("submap")
+            .flex()
+            .when(is_vertical, |this| this.flex_col())
+            .items_center()
+            .gap(px(style::CHIP_GAP))
+            .map(|el| style::chip(el, is_vertical, grouped))
+            .when(!grouped, |el| el.bg(background))
+            .text_color(foreground)
+            .when(!config.icon.is_empty(), |this| {
+                this.child(div().text_size(px(icon_size)).child(config.icon.clone()))
+            })
+            .child(div().text_size(text_size).child(label))
+    }
+}
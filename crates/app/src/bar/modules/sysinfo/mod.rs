@@ -5,13 +5,14 @@
 use crate::panel::{PanelConfig, toggle_panel};
 use gpui::{App, Context, MouseButton, Size, Window, div, prelude::*, px};
 use services::SysInfoData;
-use ui::{ActiveTheme, radius};
+use ui::ActiveTheme;
 
 mod config;
 pub use config::SysInfoConfig;
 
 use super::style;
-use crate::config::{ActiveConfig, Config};
+use crate::bar;
+use crate::config::ActiveConfig;
 use crate::panel::panel_placement_from_event;
 use crate::state::AppState;
 use crate::state::watch;
@@ -51,11 +52,12 @@ pub mod icons {
 pub struct SysInfo {
     subscriber: services::SysInfoSubscriber,
     data: SysInfoData,
+    grouped: bool,
 }
 
 impl SysInfo {
     /// Create a new SysInfo widget.
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         let subscriber = AppState::sysinfo(cx).clone();
         let initial_data = subscriber.get();
 
@@ -68,15 +70,15 @@ impl SysInfo {
         SysInfo {
             subscriber,
             data: initial_data,
+            grouped,
         }
     }
 
     fn toggle_panel(&mut self, event: &gpui::MouseDownEvent, window: &Window, cx: &mut App) {
         let subscriber = self.subscriber.clone();
-        let config = Config::global(cx);
         let panel_size = Size::new(px(350.0), px(450.0));
         let (anchor, margin) =
-            panel_placement_from_event(config.bar.position, event, window, cx, panel_size);
+            panel_placement_from_event(bar::position(window, cx), event, window, cx, panel_size);
         let config = PanelConfig {
             width: 350.0,
             height: 450.0,
@@ -112,9 +114,10 @@ impl SysInfo {
 }
 
 impl Render for SysInfo {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
 
         let cpu_usage = self.data.cpu_usage;
         let memory_usage = self.data.memory_usage;
@@ -139,11 +142,9 @@ impl Render for SysInfo {
             .when(is_vertical, |this| this.flex_col())
             .items_center()
             .gap(px(style::CHIP_GAP))
-            .px(px(style::chip_padding_x(is_vertical)))
-            .py(px(style::CHIP_PADDING_Y))
-            .rounded(px(radius::SM))
+            .map(|el| style::chip(el, is_vertical, grouped))
             .cursor_pointer()
-            .bg(interactive_default)
+            .when(!grouped, |el| el.bg(interactive_default))
             .hover(move |s| s.bg(interactive_hover))
             .active(move |s| s.bg(interactive_active))
             .on_mouse_down(
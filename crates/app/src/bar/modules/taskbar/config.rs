@@ -0,0 +1,27 @@
+//! Taskbar module configuration.
+
+use serde::{Deserialize, Serialize};
+
+/// Taskbar module configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskbarConfig {
+    pub icon_size: f32,
+    /// Only list windows on the active workspace.
+    pub current_workspace_only: bool,
+    /// Show window titles next to icons (horizontal bars only).
+    pub show_titles: bool,
+    /// Maximum title length in characters (0 = unlimited).
+    pub max_title_length: usize,
+}
+
+impl Default for TaskbarConfig {
+    fn default() -> Self {
+        Self {
+            icon_size: 18.0,
+            current_workspace_only: false,
+            show_titles: false,
+            max_title_length: 20,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Taskbar widget listing open windows as application icons.
+
+mod config;
+pub use config::TaskbarConfig;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use gpui::{App, Context, ElementId, MouseButton, Window, div, img, prelude::*, px};
+use services::{CompositorCommand, CompositorState};
+use ui::{ActiveTheme, radius};
+
+use super::style;
+use crate::bar;
+use crate::config::ActiveConfig;
+use crate::state::AppState;
+use crate::state::watch;
+
+/// Glyph shown for windows without a resolvable icon.
+const FALLBACK_ICON: &str = "󰣆";
+
+/// Widget that shows one icon per open window.
+pub struct Taskbar {
+    compositor: services::CompositorSubscriber,
+    state: CompositorState,
+    /// Resolved icon paths by window class.
+    icons: HashMap<String, Option<PathBuf>>,
+}
+
+impl Taskbar {
+    /// Create a new taskbar widget.
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let compositor = AppState::compositor(cx).clone();
+        let state = compositor.get();
+
+        // Subscribe to compositor state changes
+        watch(cx, compositor.subscribe(), |this, new_state, cx| {
+            this.state = new_state;
+            cx.notify();
+        });
+
+        Self {
+            compositor,
+            state,
+            icons: HashMap::new(),
+        }
+    }
+
+    fn dispatch(&self, command: CompositorCommand) {
+        if let Err(e) = self.compositor.dispatch(command) {
+            tracing::error!("Failed to dispatch taskbar command: {}", e);
+        }
+    }
+
+    /// Icon path for a window class, resolved once and cached.
+    fn icon_for(&mut self, class: &str, cx: &App) -> Option<PathBuf> {
+        if let Some(icon) = self.icons.get(class) {
+            return icon.clone();
+        }
+        let icon = AppState::applications(cx).icon_for_class(class);
+        self.icons.insert(class.to_string(), icon.clone());
+        icon
+    }
+
+    fn label(title: &str, max_length: usize) -> String {
+        let title = title.trim();
+        match title.char_indices().nth(max_length) {
+            Some((cutoff, _)) if max_length > 0 => format!("{}…", &title[..cutoff]),
+            _ => title.to_string(),
+        }
+    }
+}
+
+impl Render for Taskbar {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let config = cx.config().bar.modules.taskbar.clone();
+        let icon_size = config.icon_size;
+        let item_size = icon_size.max(style::TRAY_ITEM_SIZE);
+        let show_titles = config.show_titles && !is_vertical;
+
+        // Pre-compute colors for closures
+        let interactive_default = theme.interactive.default;
+        let interactive_hover = theme.interactive.hover;
+        let accent_selection = theme.accent.selection;
+        let status_warning = theme.status.warning;
+        let text_primary = theme.text.primary;
+        let text_secondary = theme.text.secondary;
+        let text_size = style::label_size(theme, is_vertical);
+
+        let active_id = self.state.active_window.as_ref().map(|w| w.address.clone());
+        let active_workspace = self.state.active_workspace_id;
+        let windows: Vec<_> = self
+            .state
+            .windows
+            .iter()
+            .filter(|w| !config.current_workspace_only || w.workspace_id == active_workspace)
+            .cloned()
+            .collect();
+
+        let items: Vec<_> = windows
+            .into_iter()
+            .map(|window| {
+                let icon = self.icon_for(&window.class, cx);
+                let is_active = active_id.as_deref() == Some(window.id.as_str());
+                let focus_id = window.id.clone();
+                let close_id = window.id.clone();
+                let title = Self::label(&window.title, config.max_title_length);
+
+                div()
+                    .id(ElementId::Name(format!("taskbar-{}", window.id).into()))
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .gap(px(style::CHIP_GAP))
+                    .min_w(px(item_size))
+                    .h(px(item_size))
+                    .when(show_titles, |this| {
+                        this.px(px(style::chip_padding_x(is_vertical)))
+                    })
+                    .rounded(px(radius::SM))
+                    .cursor_pointer()
+                    .bg(if window.urgent {
+                        status_warning
+                    } else if is_active {
+                        accent_selection
+                    } else {
+                        interactive_default
+                    })
+                    .hover(move |s| s.bg(interactive_hover))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, _cx| {
+                            this.dispatch(CompositorCommand::FocusWindow(focus_id.clone()));
+                        }),
+                    )
+                    .on_mouse_down(
+                        MouseButton::Middle,
+                        cx.listener(move |this, _event, _window, _cx| {
+                            this.dispatch(CompositorCommand::CloseWindow(close_id.clone()));
+                        }),
+                    )
+                    .child(match icon {
+                        Some(path) => div()
+                            .size(px(icon_size))
+                            .child(img(path).size_full())
+                            .into_any_element(),
+                        None => div()
+                            .text_size(px(icon_size))
+                            .text_color(text_secondary)
+                            .child(FALLBACK_ICON)
+                            .into_any_element(),
+                    })
+                    .when(show_titles && !title.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_size(text_size)
+                                .text_color(text_primary)
+                                .child(title),
+                        )
+                    })
+            })
+            .collect();
+
+        div()
+            .id("taskbar")
+            .flex()
+            .when(is_vertical, |this| this.flex_col())
+            .items_center()
+            .gap(px(style::CHIP_GAP))
+            .children(items)
+    }
+}
@@ -11,7 +11,8 @@ use services::{MenuLayout, MenuLayoutProps, TrayCommand, TrayData, TrayIcon, Tra
 use ui::{ActiveTheme, radius, spacing};
 
 use super::style;
-use crate::config::{ActiveConfig, Config};
+use crate::bar;
+use crate::config::ActiveConfig;
 use crate::state::AppState;
 use crate::state::watch;
 
@@ -49,10 +50,14 @@ impl Tray {
             let panel_id = format!("systray-{}", item.name);
             let subscriber = self.subscriber.clone();
             let item_name = item.name.clone();
-            let config = Config::global(cx);
             let panel_size = Size::new(px(250.0), px(400.0));
-            let (anchor, margin) =
-                panel_placement_from_event(config.bar.position, event, window, cx, panel_size);
+            let (anchor, margin) = panel_placement_from_event(
+                bar::position(window, cx),
+                event,
+                window,
+                cx,
+                panel_size,
+            );
 
             let config = PanelConfig {
                 width: 250.0,
@@ -89,9 +94,9 @@ impl Tray {
 }
 
 impl Render for Tray {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
         let items: Vec<_> = self.data.items.clone();
         let config = &cx.config().bar.modules.tray;
         let icon_size = config.icon_size;
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 pub struct WorkspacesConfig {
     pub show_icons: bool,
     pub show_numbers: bool,
+    /// Only show the workspaces of the output the bar is on.
+    pub current_monitor_only: bool,
 }
 
 impl Default for WorkspacesConfig {
@@ -15,6 +17,7 @@ impl Default for WorkspacesConfig {
         Self {
             show_icons: true,
             show_numbers: true,
+            current_monitor_only: false,
         }
     }
 }
@@ -8,6 +8,7 @@ use services::{CompositorCommand, CompositorState};
 use ui::{ActiveTheme, radius};
 
 use super::style;
+use crate::bar;
 use crate::config::ActiveConfig;
 use crate::state::AppState;
 use crate::state::watch;
@@ -33,12 +34,14 @@ impl Workspaces {
         Self { compositor, state }
     }
 
-    /// Handle clicking on a workspace to focus it.
+    /// Handle clicking on a workspace to focus it, jumping straight to the
+    /// window that requested attention if there is one.
     fn focus_workspace(&self, workspace_id: i32) {
-        if let Err(e) = self
-            .compositor
-            .dispatch(CompositorCommand::FocusWorkspace(workspace_id))
-        {
+        let command = match self.state.urgent_window_on_workspace(workspace_id) {
+            Some(window) => CompositorCommand::FocusWindow(window.id.clone()),
+            None => CompositorCommand::FocusWorkspace(workspace_id),
+        };
+        if let Err(e) = self.compositor.dispatch(command) {
             tracing::error!("Failed to focus workspace {}: {}", workspace_id, e);
         }
     }
@@ -94,19 +97,35 @@ impl Workspaces {
 }
 
 impl Render for Workspaces {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
-        let is_vertical = cx.config().bar.is_vertical();
-        let active_workspace_id = self.state.active_workspace_id;
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let mut active_workspace_id = self.state.active_workspace_id;
         let config = &cx.config().bar.modules.workspaces;
         let show_numbers = config.show_numbers;
         let show_icons = config.show_icons;
 
+        // Limit to this bar's output, highlighting the workspace shown there.
+        let output = config
+            .current_monitor_only
+            .then(|| bar::output(window, cx))
+            .flatten();
+        let workspaces = match &output {
+            Some(name) => {
+                if let Some(monitor) = self.state.monitor_by_name(name) {
+                    active_workspace_id = Some(monitor.active_workspace_id);
+                }
+                self.state.workspaces_for_monitor(name)
+            }
+            None => self.state.regular_workspaces(),
+        };
+
         // Pre-compute colors for closures
         let accent_primary = theme.accent.primary;
         let accent_hover = theme.accent.hover;
         let interactive_default = theme.interactive.default;
         let interactive_hover = theme.interactive.hover;
+        let status_warning = theme.status.warning;
         let bg_primary = theme.bg.primary;
         let text_secondary = theme.text.secondary;
         let text_muted = theme.text.muted;
@@ -129,76 +148,72 @@ impl Render for Workspaces {
                     }
                 }),
             )
-            .children(
-                self.state
-                    .workspaces
-                    .iter()
-                    .filter(|ws| !ws.is_special)
-                    .map(|ws| {
-                        let workspace_id = ws.id;
-                        let is_active = active_workspace_id == Some(ws.id);
-                        let has_windows = ws.windows > 0;
-                        let label =
-                            Self::workspace_label(ws, is_vertical, show_numbers, show_icons);
-
-                        div()
-                            .id(format!("workspace-{}", ws.id))
-                            .flex()
-                            .items_center()
-                            .justify_center()
-                            .when(is_vertical, |this| {
-                                this.w(if is_active {
-                                    px(style::WORKSPACE_PILL_WIDTH_ACTIVE)
+            .children(workspaces.into_iter().map(|ws| {
+                let workspace_id = ws.id;
+                let is_active = active_workspace_id == Some(ws.id);
+                let has_windows = ws.windows > 0;
+                let is_urgent = ws.urgent && !is_active;
+                let label = Self::workspace_label(ws, is_vertical, show_numbers, show_icons);
+
+                div()
+                    .id(format!("workspace-{}", ws.id))
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .when(is_vertical, |this| {
+                        this.w(if is_active {
+                            px(style::WORKSPACE_PILL_WIDTH_ACTIVE)
+                        } else {
+                            px(style::WORKSPACE_PILL_WIDTH)
+                        })
+                        .h(px(style::WORKSPACE_PILL_HEIGHT))
+                    })
+                    .when(!is_vertical, |this| {
+                        this.w(if is_active {
+                            px(style::WORKSPACE_PILL_WIDTH_HORIZONTAL_ACTIVE)
+                        } else {
+                            px(style::WORKSPACE_PILL_WIDTH_HORIZONTAL)
+                        })
+                        .h(px(style::WORKSPACE_PILL_HEIGHT))
+                    })
+                    .rounded(px(radius::SM))
+                    .cursor_pointer()
+                    .bg(if is_active {
+                        accent_primary
+                    } else if is_urgent {
+                        status_warning
+                    } else if has_windows {
+                        interactive_default
+                    } else {
+                        transparent
+                    })
+                    .hover(move |s| {
+                        if is_active {
+                            s.bg(accent_hover)
+                        } else {
+                            s.bg(interactive_hover)
+                        }
+                    })
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, _cx| {
+                            this.focus_workspace(workspace_id);
+                        }),
+                    )
+                    .when(!label.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_size(style::label_size(theme, is_vertical))
+                                .text_color(if is_active || is_urgent {
+                                    bg_primary
+                                } else if has_windows {
+                                    text_secondary
                                 } else {
-                                    px(style::WORKSPACE_PILL_WIDTH)
+                                    text_muted
                                 })
-                                .h(px(style::WORKSPACE_PILL_HEIGHT))
-                            })
-                            .when(!is_vertical, |this| {
-                                this.w(if is_active {
-                                    px(style::WORKSPACE_PILL_WIDTH_HORIZONTAL_ACTIVE)
-                                } else {
-                                    px(style::WORKSPACE_PILL_WIDTH_HORIZONTAL)
-                                })
-                                .h(px(style::WORKSPACE_PILL_HEIGHT))
-                            })
-                            .rounded(px(radius::SM))
-                            .cursor_pointer()
-                            .bg(if is_active {
-                                accent_primary
-                            } else if has_windows {
-                                interactive_default
-                            } else {
-                                transparent
-                            })
-                            .hover(move |s| {
-                                if is_active {
-                                    s.bg(accent_hover)
-                                } else {
-                                    s.bg(interactive_hover)
-                                }
-                            })
-                            .on_mouse_down(
-                                MouseButton::Left,
-                                cx.listener(move |this, _event, _window, _cx| {
-                                    this.focus_workspace(workspace_id);
-                                }),
-                            )
-                            .when(!label.is_empty(), |this| {
-                                this.child(
-                                    div()
-                                        .text_size(style::label_size(theme, is_vertical))
-                                        .text_color(if is_active {
-                                            bg_primary
-                                        } else if has_windows {
-                                            text_secondary
-                                        } else {
-                                            text_muted
-                                        })
-                                        .child(label),
-                                )
-                            })
-                    }),
-            )
+                                .child(label),
+                        )
+                    })
+            }))
     }
 }
@@ -0,0 +1,30 @@
+//! Audio volume configuration.
+
+use serde::{Deserialize, Serialize};
+
+/// Volume limit and step for the shell's volume controls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioConfig {
+    /// Highest volume the shell sets, as percentage. Values above 100
+    /// boost quiet devices.
+    pub max_volume: u16,
+    /// Volume change per scroll tick and +/- button press, as percentage.
+    pub step: u8,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            max_volume: 100,
+            step: 5,
+        }
+    }
+}
+
+impl AudioConfig {
+    /// The step as a volume delta, kept within 1-100.
+    pub fn step_delta(&self) -> i8 {
+        self.step.clamp(1, 100) as i8
+    }
+}
@@ -0,0 +1,12 @@
+//! Brightness device configuration.
+
+use serde::{Deserialize, Serialize};
+
+/// Which brightness devices the shell controls besides backlights.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrightnessConfig {
+    /// Control external monitors over DDC/CI (requires `ddcutil`).
+    /// Turning it off requires a restart.
+    pub ddc: bool,
+}
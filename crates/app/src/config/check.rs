@@ -0,0 +1,492 @@
+//! Offline validation of `config.toml` and `theme.toml`.
+//!
+//! Used by `gpuishell config check` to catch mistakes before the shell
+//! loads a file: syntax and type errors with their location, keys the
+//! shell would silently ignore, and values that only fail at runtime.
+//! Problems in included files and host overlays are reported against the
+//! file they occur in.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::Config;
+use super::persistence::locate_type_error;
+use super::sources::{self, INCLUDE_KEY, SourceError, line_column};
+use super::theme::config::StoredTheme;
+use crate::bar::modules::{CUSTOM_PREFIX, GROUP_PREFIX, Widget};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single problem found in a file.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// File the problem is in.
+    pub path: PathBuf,
+    /// 1-based line and column, when the problem has a location.
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+/// All problems found in a file and the files it pulls in.
+#[derive(Debug)]
+pub struct Report {
+    pub path: PathBuf,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, severity: Severity, location: Option<(usize, usize)>, message: String) {
+        let path = self.path.clone();
+        self.push_in(severity, path, location, message);
+    }
+
+    fn push_in(
+        &mut self,
+        severity: Severity,
+        path: PathBuf,
+        location: Option<(usize, usize)>,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            path,
+            location,
+            message,
+        });
+    }
+
+    fn push_source_error(&mut self, err: SourceError) {
+        self.push_in(Severity::Error, err.path, err.location, err.message);
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            let severity = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            match diagnostic.location {
+                Some((line, column)) => writeln!(
+                    f,
+                    "{}:{}:{}: {}: {}",
+                    diagnostic.path.display(),
+                    line,
+                    column,
+                    severity,
+                    diagnostic.message
+                )?,
+                None => writeln!(
+                    f,
+                    "{}: {}: {}",
+                    diagnostic.path.display(),
+                    severity,
+                    diagnostic.message
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Source files of a config, used to point diagnostics at the file that
+/// sets a key.
+struct Files<'a> {
+    main: &'a Path,
+    files: &'a [(PathBuf, String)],
+}
+
+impl Files<'_> {
+    /// Location of a dotted key in the file of highest precedence that
+    /// sets it, falling back to the main file.
+    fn locate(&self, key: &[String]) -> (PathBuf, Option<(usize, usize)>) {
+        self.files
+            .iter()
+            .rev()
+            .find_map(|(path, raw)| locate_key(raw, key).map(|loc| (path.clone(), Some(loc))))
+            .unwrap_or_else(|| (self.main.to_path_buf(), None))
+    }
+}
+
+/// Validate a `config.toml` file, its includes and its host overlay.
+pub fn check_config(path: &Path) -> Report {
+    let mut report = Report::new(path);
+    let sources = match sources::read(path) {
+        Ok(sources) => sources,
+        Err(err) => {
+            match err.downcast::<SourceError>() {
+                Ok(err) => report.push_source_error(err),
+                Err(err) => report.push(Severity::Error, None, format!("{err:#}")),
+            }
+            return report;
+        }
+    };
+
+    let config = match toml::Value::Table(sources.merged).try_into::<Config>() {
+        Ok(config) => config,
+        Err(err) => {
+            report.push_source_error(locate_type_error(&sources.files, path, err));
+            return report;
+        }
+    };
+
+    let files = Files {
+        main: path,
+        files: &sources.files,
+    };
+    for (file, raw) in &sources.files {
+        warn_unknown_keys_in(file, raw, &config, &mut report);
+    }
+    check_widgets(&files, &config, &mut report);
+    check_launcher_prefixes(&files, &config, &mut report);
+    check_power_actions(&files, &config, &mut report);
+    check_audio(&files, &config, &mut report);
+    report
+}
+
+/// Validate a `theme.toml` file.
+pub fn check_theme(path: &Path) -> Report {
+    let mut report = Report::new(path);
+    let Some((raw, theme)) = parse::<StoredTheme>(path, &mut report) else {
+        return report;
+    };
+
+    let path = report.path.clone();
+    warn_unknown_keys_in(&path, &raw, &theme, &mut report);
+    if let Err(err) = theme.to_theme() {
+        report.push(Severity::Error, None, format!("{err:#}"));
+    }
+    report
+}
+
+/// Read and deserialize a file, recording syntax and type errors.
+fn parse<T: DeserializeOwned>(path: &Path, report: &mut Report) -> Option<(String, T)> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) => {
+            report.push(Severity::Error, None, format!("failed to read file: {err}"));
+            return None;
+        }
+    };
+
+    match toml::from_str::<T>(&raw) {
+        Ok(value) => Some((raw, value)),
+        Err(err) => {
+            let location = err.span().map(|span| line_column(&raw, span.start));
+            report.push(Severity::Error, location, err.message().to_string());
+            None
+        }
+    }
+}
+
+/// Warn about keys in `file` that deserialization ignored.
+///
+/// The parsed value is serialized back and compared against the file:
+/// any key that does not survive the round trip is unknown.
+fn warn_unknown_keys_in<T: Serialize>(file: &Path, raw: &str, parsed: &T, report: &mut Report) {
+    let (Ok(mut written), Ok(toml::Value::Table(known))) = (
+        toml::from_str::<toml::Table>(raw),
+        toml::Value::try_from(parsed),
+    ) else {
+        return;
+    };
+    written.remove(INCLUDE_KEY);
+
+    let mut unknown = Vec::new();
+    collect_unknown(&written, &known, &mut Vec::new(), &mut unknown);
+    for path in unknown {
+        report.push_in(
+            Severity::Warning,
+            file.to_path_buf(),
+            locate_key(raw, &path),
+            format!("unknown key `{}`", path.join(".")),
+        );
+    }
+}
+
+fn collect_unknown(
+    written: &toml::Table,
+    known: &toml::Table,
+    prefix: &mut Vec<String>,
+    unknown: &mut Vec<Vec<String>>,
+) {
+    for (key, value) in written {
+        prefix.push(key.clone());
+        match (value, known.get(key)) {
+            (_, None) => unknown.push(prefix.clone()),
+            (toml::Value::Table(written), Some(toml::Value::Table(known))) => {
+                collect_unknown(written, known, prefix, unknown);
+            }
+            (toml::Value::Array(written), Some(toml::Value::Array(known))) => {
+                for (written, known) in written.iter().zip(known) {
+                    if let (toml::Value::Table(written), toml::Value::Table(known)) =
+                        (written, known)
+                    {
+                        collect_unknown(written, known, prefix, unknown);
+                    }
+                }
+            }
+            _ => {}
+        }
+        prefix.pop();
+    }
+}
+
+fn check_widgets(files: &Files, config: &Config, report: &mut Report) {
+    let mut sections = vec![
+        ("`bar.start`".to_string(), "start", &config.bar.start),
+        ("`bar.center`".to_string(), "center", &config.bar.center),
+        ("`bar.end`".to_string(), "end", &config.bar.end),
+    ];
+    for output in &config.bar.outputs {
+        let overrides = [
+            ("start", &output.start),
+            ("center", &output.center),
+            ("end", &output.end),
+        ];
+        for (section, widgets) in overrides {
+            if let Some(widgets) = widgets {
+                let label = format!("`{section}` of output `{}`", output.name);
+                sections.push((label, "outputs", widgets));
+            }
+        }
+    }
+
+    for (index, extra) in config.bar.extra.iter().enumerate() {
+        let sections_of_extra = [
+            ("start", &extra.start),
+            ("center", &extra.center),
+            ("end", &extra.end),
+        ];
+        for (section, widgets) in sections_of_extra {
+            let label = format!("`{section}` of extra bar {}", index + 1);
+            sections.push((label, "extra", widgets));
+        }
+    }
+    for (name, group) in &config.bar.groups {
+        sections.push((format!("group `{name}`"), "groups", &group.widgets));
+    }
+
+    for (label, key, widgets) in sections {
+        for name in widgets {
+            let message = if let Some(group) = name.strip_prefix(GROUP_PREFIX) {
+                if config.bar.groups.contains_key(group) {
+                    continue;
+                }
+                format!("unknown widget group `{group}` in {label}")
+            } else if let Some(custom) = name.strip_prefix(CUSTOM_PREFIX) {
+                if config.bar.modules.custom.contains_key(custom) {
+                    continue;
+                }
+                format!("unknown custom widget `{custom}` in {label}")
+            } else if Widget::is_known(name) {
+                continue;
+            } else {
+                format!("unknown widget `{name}` in {label}")
+            };
+            let (path, location) = files.locate(&["bar".to_string(), key.to_string()]);
+            report.push_in(Severity::Error, path, location, message);
+        }
+    }
+}
+
+fn check_launcher_prefixes(files: &Files, config: &Config, report: &mut Report) {
+    let modules = &config.launcher.modules;
+    let prefixes = [
+        ("apps", &modules.apps.prefix),
+        ("shell", &modules.shell.prefix),
+        ("web", &modules.web.prefix),
+        ("workspaces", &modules.workspaces.prefix),
+        ("windows", &modules.windows.prefix),
+        ("wallpaper", &modules.wallpaper.prefix),
+        ("themes", &modules.themes.prefix),
+        ("services", &modules.services.prefix),
+        ("help", &modules.help.prefix),
+    ];
+
+    for (i, (view, prefix)) in prefixes.iter().enumerate() {
+        if let Some((other, _)) = prefixes[..i].iter().find(|(_, p)| p == prefix) {
+            let key = ["launcher", "modules", *view, "prefix"].map(String::from);
+            let (path, location) = files.locate(&key);
+            report.push_in(
+                Severity::Error,
+                path,
+                location,
+                format!("launcher prefix `{prefix}` of `{view}` is already used by `{other}`"),
+            );
+        }
+    }
+}
+
+fn check_power_actions(files: &Files, config: &Config, report: &mut Report) {
+    let actions = &config.control_center.power_actions;
+    let commands = [
+        ("sleep", &actions.sleep),
+        ("reboot", &actions.reboot),
+        ("poweroff", &actions.poweroff),
+    ];
+    for (action, command) in commands {
+        if command.trim().is_empty() {
+            let key = ["control_center", "power_actions", action].map(String::from);
+            let (path, location) = files.locate(&key);
+            report.push_in(
+                Severity::Error,
+                path,
+                location,
+                format!("power action `{action}` has an empty command"),
+            );
+        }
+    }
+}
+
+fn check_audio(files: &Files, config: &Config, report: &mut Report) {
+    let audio = &config.audio;
+    let checks = [
+        (
+            "max_volume",
+            audio.max_volume > 0,
+            "`audio.max_volume` must be above 0".to_string(),
+        ),
+        (
+            "step",
+            (1..=100).contains(&audio.step),
+            format!("`audio.step` must be between 1 and 100, got {}", audio.step),
+        ),
+    ];
+    for (key, valid, message) in checks {
+        if !valid {
+            let (path, location) = files.locate(&["audio", key].map(String::from));
+            report.push_in(Severity::Error, path, location, message);
+        }
+    }
+}
+
+/// Best-effort location of a dotted key, tracking `[table]` headers.
+///
+/// Handles the common layouts (`[a.b]` followed by `c = ...`, or
+/// `[a.b.c]` headers); inline tables are not searched.
+fn locate_key(raw: &str, path: &[String]) -> Option<(usize, usize)> {
+    let mut table: Vec<String> = Vec::new();
+
+    for (index, line) in raw.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let header = header.trim_start_matches('[');
+            let Some(end) = header.find(']') else {
+                continue;
+            };
+            table = split_key(&header[..end]);
+            if table == path {
+                return Some((index + 1, indent + 1));
+            }
+            continue;
+        }
+
+        let Some((key, _)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let mut full = table.clone();
+        full.extend(split_key(key));
+        if full == path {
+            return Some((index + 1, indent + 1));
+        }
+    }
+
+    None
+}
+
+fn split_key(key: &str) -> Vec<String> {
+    key.split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\'').to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unknown(written: &str, known: &str) -> Vec<String> {
+        let written: toml::Table = toml::from_str(written).unwrap();
+        let known: toml::Table = toml::from_str(known).unwrap();
+        let mut unknown = Vec::new();
+        collect_unknown(&written, &known, &mut Vec::new(), &mut unknown);
+        unknown.into_iter().map(|path| path.join(".")).collect()
+    }
+
+    #[test]
+    fn test_collect_unknown_nested() {
+        let known =
+            "watch_config = true\n[bar]\nsize = 32\n[bar.modules.clock]\nformat = \"%H:%M\"";
+        assert!(unknown("[bar]\nsize = 40", known).is_empty());
+        assert_eq!(
+            unknown(
+                "watch_confg = true\n[bar]\nsise = 40\n[bar.modules.clock]\nformt = \"\"",
+                known
+            ),
+            vec!["bar.modules.clock.formt", "bar.sise", "watch_confg"]
+        );
+    }
+
+    #[test]
+    fn test_collect_unknown_in_arrays_of_tables() {
+        let known = "[[bar.outputs]]\nname = \"DP-1\"\n[[bar.outputs]]\nname = \"eDP-1\"";
+        let written =
+            "[[bar.outputs]]\nname = \"DP-1\"\n[[bar.outputs]]\nname = \"eDP-1\"\nsize = 1";
+        assert_eq!(unknown(written, known), vec!["bar.outputs.size"]);
+    }
+
+    #[test]
+    fn test_collect_unknown_ignores_type_changes() {
+        // Type errors are reported by deserialization, not as unknown keys.
+        assert!(unknown("bar = 1", "[bar]\nsize = 32").is_empty());
+    }
+
+    #[test]
+    fn test_locate_key() {
+        let raw =
+            "watch_config = true\n\n[bar]\n  size = 32\n[\"bar\".modules]\nclock.format = \"\"";
+        let path = |key: &str| key.split('.').map(String::from).collect::<Vec<_>>();
+        assert_eq!(locate_key(raw, &path("watch_config")), Some((1, 1)));
+        assert_eq!(locate_key(raw, &path("bar")), Some((3, 1)));
+        assert_eq!(locate_key(raw, &path("bar.size")), Some((4, 3)));
+        assert_eq!(
+            locate_key(raw, &path("bar.modules.clock.format")),
+            Some((6, 1))
+        );
+        assert_eq!(locate_key(raw, &path("osd")), None);
+    }
+}
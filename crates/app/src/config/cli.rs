@@ -0,0 +1,68 @@
+//! Config subcommands that run without starting the shell.
+
+use std::path::PathBuf;
+
+use super::check::{self, Report};
+use super::{Config, persistence};
+
+/// Run `gpuishell config check [path]` and return the process exit code.
+///
+/// Checks the config file and the `theme.toml` next to it, printing every
+/// problem found. Fails only on errors; warnings are informational.
+pub fn check(path: Option<PathBuf>) -> i32 {
+    let path = match path.map_or_else(persistence::config_path, Ok) {
+        Ok(path) => path,
+        Err(err) => {
+            eprintln!("Error: {err:#}");
+            return 1;
+        }
+    };
+
+    let mut reports = vec![check::check_config(&path)];
+    let theme_path = path.with_file_name("theme.toml");
+    if theme_path.exists() {
+        reports.push(check::check_theme(&theme_path));
+    }
+
+    let mut failed = false;
+    for report in &reports {
+        eprint!("{report}");
+        println!("{}", summary(report));
+        failed |= report.error_count() > 0;
+    }
+
+    if failed { 1 } else { 0 }
+}
+
+fn summary(report: &Report) -> String {
+    let plural = |n: usize, word: &str| {
+        if n == 1 {
+            format!("{n} {word}")
+        } else {
+            format!("{n} {word}s")
+        }
+    };
+    match (report.error_count(), report.warning_count()) {
+        (0, 0) => format!("{}: ok", report.path.display()),
+        (errors, warnings) => format!(
+            "{}: {}, {}",
+            report.path.display(),
+            plural(errors, "error"),
+            plural(warnings, "warning")
+        ),
+    }
+}
+
+/// Run `gpuishell config dump-default` and return the process exit code.
+pub fn dump_default() -> i32 {
+    match toml::to_string_pretty(&Config::default()) {
+        Ok(encoded) => {
+            print!("{encoded}");
+            0
+        }
+        Err(err) => {
+            eprintln!("Error: failed to encode default config: {err}");
+            1
+        }
+    }
+}
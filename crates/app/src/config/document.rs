@@ -0,0 +1,259 @@
+//! Format-preserving updates of TOML files.
+//!
+//! Saving edits the existing document instead of serializing the whole
+//! value again, so comments, key order and keys the shell does not know
+//! about survive. Only values that differ from what the file currently
+//! yields are written.
+//!
+//! A config file is only one layer of the loaded config, so changes are
+//! first [rebased](rebase) onto what the file itself sets.
+
+use anyhow::Context;
+use toml_edit::{DocumentMut, Item};
+
+/// Apply the difference between `old` and `new` to the document in `raw`.
+///
+/// `old` is the value the file currently produces when loaded, `new` the
+/// value to persist.
+pub(super) fn update(raw: &str, old: &toml::Table, new: &toml::Table) -> anyhow::Result<String> {
+    let mut doc: DocumentMut = raw.parse().context("Failed to parse existing file")?;
+    update_table(doc.as_item_mut(), old, new)?;
+    Ok(doc.to_string())
+}
+
+/// Carry the changes from `old` to `new` over to `own`, the table a single
+/// layer of the config sets.
+///
+/// `old` and `new` are full merged configs. Values that did not change are
+/// taken from `own` as written, so `${VAR}` references and values from
+/// includes stay as they are. Changes to values that `overrides` (a layer
+/// loaded after this one) sets would be undone on the next load; they are
+/// left out and their dotted keys returned.
+pub(super) fn rebase(
+    own: &toml::Table,
+    old: &toml::Table,
+    new: &toml::Table,
+    overrides: &toml::Table,
+) -> (toml::Table, Vec<String>) {
+    let mut rebased = own.clone();
+    let mut skipped = Vec::new();
+    rebase_table(&mut rebased, old, new, Some(overrides), "", &mut skipped);
+    (rebased, skipped)
+}
+
+fn rebase_table(
+    own: &mut toml::Table,
+    old: &toml::Table,
+    new: &toml::Table,
+    overrides: Option<&toml::Table>,
+    prefix: &str,
+    skipped: &mut Vec<String>,
+) {
+    let dotted = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+
+    for (key, value) in new {
+        let previous = old.get(key);
+        if previous == Some(value) {
+            continue;
+        }
+        let overridden = overrides.and_then(|overrides| overrides.get(key));
+
+        // Descend into tables unless the override replaces them whole.
+        if let (toml::Value::Table(value), Some(toml::Value::Table(previous))) = (value, previous) {
+            let overrides = match overridden {
+                None => Some(None),
+                Some(toml::Value::Table(overrides)) => Some(Some(overrides)),
+                Some(_) => None,
+            };
+            let created = !own.contains_key(key);
+            if let Some(overrides) = overrides
+                && let toml::Value::Table(child) = own
+                    .entry(key.clone())
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            {
+                rebase_table(child, previous, value, overrides, &dotted(key), skipped);
+                if created && child.is_empty() {
+                    own.remove(key);
+                }
+                continue;
+            }
+        }
+
+        if overridden.is_some() {
+            skipped.push(dotted(key));
+        } else {
+            own.insert(key.clone(), value.clone());
+        }
+    }
+
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        if overrides.is_some_and(|overrides| overrides.contains_key(key)) {
+            skipped.push(dotted(key));
+        } else {
+            own.remove(key);
+        }
+    }
+}
+
+fn update_table(item: &mut Item, old: &toml::Table, new: &toml::Table) -> anyhow::Result<()> {
+    // New sub-tables become `[section]` headers only under regular tables;
+    // inside inline tables they have to stay inline.
+    let standard = item.is_table();
+    let Some(table) = item.as_table_like_mut() else {
+        return Ok(());
+    };
+
+    for (key, value) in new {
+        let previous = old.get(key);
+        if previous == Some(value) {
+            continue;
+        }
+
+        if let toml::Value::Table(value) = value {
+            let exists = table.get(key).is_some_and(Item::is_table_like);
+            if !exists && standard {
+                let mut section = toml_edit::Table::new();
+                section.set_implicit(true);
+                table.insert(key, Item::Table(section));
+            }
+            if let Some(child) = table.get_mut(key).filter(|child| child.is_table_like()) {
+                let empty = toml::Table::new();
+                let previous = match previous {
+                    Some(toml::Value::Table(previous)) => previous,
+                    _ => &empty,
+                };
+                update_table(child, previous, value)?;
+                continue;
+            }
+        }
+
+        let mut replacement = to_edit_value(value)?;
+        match table.get_mut(key) {
+            Some(Item::Value(existing)) => {
+                // Keep the comments and spacing around the old value.
+                *replacement.decor_mut() = existing.decor().clone();
+                *existing = replacement;
+            }
+            _ => {
+                table.insert(key, Item::Value(replacement));
+            }
+        }
+    }
+
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        table.remove(key);
+    }
+    Ok(())
+}
+
+fn to_edit_value(value: &toml::Value) -> anyhow::Result<toml_edit::Value> {
+    let mut value: toml_edit::Value = value
+        .to_string()
+        .parse()
+        .context("Failed to encode TOML value")?;
+    value.decor_mut().clear();
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(raw: &str) -> toml::Table {
+        toml::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn test_update_keeps_formatting() {
+        let raw = "# Bar settings\n[bar]\nheight = 32 # pixels\nunknown = true\n";
+        let old = table("[bar]\nheight = 32\nunknown = true");
+        let new = table("[bar]\nheight = 40\nunknown = true\n[osd]\nposition = \"top\"");
+
+        let updated = update(raw, &old, &new).unwrap();
+        assert_eq!(
+            updated,
+            "# Bar settings\n[bar]\nheight = 40 # pixels\nunknown = true\n\n[osd]\nposition = \"top\"\n"
+        );
+    }
+
+    #[test]
+    fn test_update_removes_keys() {
+        let raw = "[bar]\nheight = 32\nwidth = 10\n";
+        let updated = update(
+            raw,
+            &table("[bar]\nheight = 32\nwidth = 10"),
+            &table("[bar]\nheight = 32"),
+        )
+        .unwrap();
+        assert_eq!(updated, "[bar]\nheight = 32\n");
+    }
+
+    #[test]
+    fn test_update_unchanged_is_identity() {
+        let raw = "include = [\"extra.toml\"]\n\n[bar] # main bar\nheight = 32\n";
+        let own = table(raw);
+        assert_eq!(update(raw, &own, &own).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_rebase_keeps_env_references() {
+        let raw = "[bar]\nfont = \"${FONT:-Inter}\"\nheight = 32\n";
+        let own = table(raw);
+        let old = table("[bar]\nfont = \"Inter\"\nheight = 32");
+        let new = table("[bar]\nfont = \"Inter\"\nheight = 40");
+
+        let (rebased, skipped) = rebase(&own, &old, &new, &toml::Table::new());
+        assert!(skipped.is_empty());
+        assert_eq!(
+            update(raw, &own, &rebased).unwrap(),
+            "[bar]\nfont = \"${FONT:-Inter}\"\nheight = 40\n"
+        );
+    }
+
+    #[test]
+    fn test_rebase_skips_overridden_keys() {
+        let raw = "[bar]\nheight = 32\n";
+        let own = table(raw);
+        // The overlay sets the height; the launcher width comes from an include.
+        let overrides = table("[bar]\nheight = 48");
+        let old = table("[bar]\nheight = 48\n[launcher]\nwidth = 600");
+        let new = table("[bar]\nheight = 50\n[launcher]\nwidth = 700");
+
+        let (rebased, skipped) = rebase(&own, &old, &new, &overrides);
+        assert_eq!(skipped, vec!["bar.height".to_string()]);
+        assert_eq!(
+            update(raw, &own, &rebased).unwrap(),
+            "[bar]\nheight = 32\n\n[launcher]\nwidth = 700\n"
+        );
+    }
+
+    #[test]
+    fn test_rebase_skips_tables_replaced_by_override() {
+        let own = table("[bar]\nheight = 32");
+        let overrides = table("bar = 1");
+        let old = table("bar = 1");
+        let new = table("[bar]\nheight = 40");
+
+        let (rebased, skipped) = rebase(&own, &old, &new, &overrides);
+        assert_eq!(skipped, vec!["bar".to_string()]);
+        assert_eq!(rebased, own);
+    }
+
+    #[test]
+    fn test_rebase_does_not_add_empty_sections() {
+        let own = table("[bar]\nheight = 32");
+        let overrides = table("[osd]\nposition = \"top\"");
+        let old = table("[bar]\nheight = 32\n[osd]\nposition = \"top\"");
+        let new = table("[bar]\nheight = 32\n[osd]\nposition = \"bottom\"");
+
+        let (rebased, skipped) = rebase(&own, &old, &new, &overrides);
+        assert_eq!(skipped, vec!["osd.position".to_string()]);
+        assert_eq!(rebased, own);
+    }
+}
@@ -1,6 +1,12 @@
 //! Application configuration stored as a GPUI global.
 
+mod audio;
+mod brightness;
+mod check;
+pub mod cli;
+mod document;
 mod persistence;
+mod sources;
 mod theme;
 
 use gpui::{App, Global};
@@ -8,11 +14,17 @@ use serde::{Deserialize, Serialize};
 use services::FileWatcher;
 use ui::Theme;
 
-pub use crate::bar::config::{BarConfig, BarPosition, ModulesConfig};
+use crate::notification;
+use crate::state::AppState;
+
+pub use crate::bar::config::{BarConfig, BarMode, BarPosition, ModulesConfig};
 pub use crate::control_center::ControlCenterConfig;
 pub use crate::launcher::config::LauncherConfig;
 pub use crate::notification::{NotificationConfig, NotificationPopupPosition};
 pub use crate::osd::{OsdConfig, OsdPosition};
+pub use audio::AudioConfig;
+pub use brightness::BrightnessConfig;
+pub use persistence::set_config_path;
 
 /// Root application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +35,8 @@ pub struct Config {
     pub osd: OsdConfig,
     pub notification: NotificationConfig,
     pub control_center: ControlCenterConfig,
+    pub audio: AudioConfig,
+    pub brightness: BrightnessConfig,
     /// Watch config.toml for changes and hot-reload (requires restart to change).
     pub watch_config: bool,
     /// Watch theme.toml for changes and hot-reload (requires restart to change).
@@ -37,6 +51,8 @@ impl Default for Config {
             osd: OsdConfig::default(),
             notification: NotificationConfig::default(),
             control_center: ControlCenterConfig::default(),
+            audio: AudioConfig::default(),
+            brightness: BrightnessConfig::default(),
             watch_config: true,
             watch_theme: true,
         }
@@ -49,9 +65,18 @@ impl Config {
     /// Initialize the global config.
     pub fn init(cx: &mut App) {
         let config = match persistence::load() {
-            Ok(config) => config,
+            Ok(Some(config)) => config,
+            Ok(None) => {
+                // First launch: write the defaults so there is a file to edit.
+                let config = Config::default();
+                if let Err(err) = persistence::save(&config) {
+                    tracing::warn!("Failed to write default config: {}", err);
+                }
+                config
+            }
             Err(err) => {
-                tracing::warn!("Failed to load config, using defaults: {}", err);
+                tracing::warn!("Failed to load config, using defaults: {:#}", err);
+                notification::notify("Config error, using defaults", format!("{err:#}"), cx);
                 Config::default()
             }
         };
@@ -86,24 +111,70 @@ impl Config {
         *cx.global_mut::<Config>() = config;
     }
 
+    /// Replace the global config and update the parts of the shell whose
+    /// section changed.
+    fn apply(config: Config, cx: &mut App) {
+        let old = cx.global::<Config>();
+        let bar_changed = section_changed(&old.bar, &config.bar);
+        let launcher_changed = section_changed(&old.launcher.modules, &config.launcher.modules);
+        let osd_position = (old.osd.position != config.osd.position).then_some(config.osd.position);
+        let max_volume =
+            (old.audio.max_volume != config.audio.max_volume).then_some(config.audio.max_volume);
+        let enable_ddc = !old.brightness.ddc && config.brightness.ddc;
+
+        Self::replace(config, cx);
+
+        if bar_changed {
+            tracing::info!("Bar config changed; rebuilding bar windows");
+            crate::bar::reload(cx);
+        }
+        if launcher_changed {
+            tracing::info!("Launcher modules changed; recreating launcher views");
+            crate::launcher::reload_views(cx);
+        }
+        if let Some(position) = osd_position {
+            tracing::info!("OSD position changed to {:?}", position);
+            crate::osd::set_position(position, cx);
+        }
+        if let Some(percent) = max_volume {
+            tracing::info!("Volume limit changed to {}%", percent);
+            AppState::audio(cx).set_max_volume(percent);
+        }
+        if enable_ddc {
+            tracing::info!("DDC/CI brightness enabled");
+            AppState::brightness(cx).enable_ddc();
+        }
+        cx.refresh_windows();
+    }
+
     /// Replace and persist the global config.
     pub fn set(config: Config, cx: &mut App) {
-        Self::replace(config, cx);
+        Self::apply(config, cx);
         if let Err(err) = persistence::save(cx.global::<Config>()) {
             tracing::warn!("Failed to persist config: {}", err);
         }
     }
 
-    /// Reload config from disk and replace the global config.
+    /// Reload config from disk, applying the sections that changed.
+    ///
+    /// On failure the last good config stays active and the error is shown
+    /// as a notification.
     pub fn reload(cx: &mut App) {
         match persistence::load() {
-            Ok(config) => Self::replace(config, cx),
-            Err(err) => tracing::warn!("Failed to reload config from disk: {}", err),
+            Ok(Some(config)) => Self::apply(config, cx),
+            Ok(None) => tracing::warn!("Config file is missing; keeping the current config"),
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to reload config, keeping the current one: {:#}",
+                    err
+                );
+                notification::notify("Config reload failed", format!("{err:#}"), cx);
+            }
         }
     }
 
     /// Reload theme from disk and replace the global theme.
-    fn reload_theme(cx: &mut App) {
+    pub fn reload_theme(cx: &mut App) {
         match theme::persistence::load_theme() {
             Ok(theme) => Theme::set(theme, cx),
             Err(err) => tracing::warn!("Failed to reload theme from disk: {}", err),
@@ -145,15 +216,24 @@ impl Config {
                 }
             };
 
-            let mut rx = FileWatcher::watch(config_path);
+            // Watch the includes and host overlay too. The include list can
+            // change with any reload, so the watch is re-armed when it does.
+            let mut files = sources::files(&config_path);
+            let mut rx = FileWatcher::watch_all(files.clone());
 
             cx.spawn(async move |cx| {
                 while rx.recv().await.is_some() {
                     cx.update(|cx| {
                         tracing::info!("Config file changed, reloading");
                         Config::reload(cx);
-                        cx.refresh_windows();
                     });
+
+                    let current = sources::files(&config_path);
+                    if current != files {
+                        tracing::debug!("Config files changed, now watching {:?}", current);
+                        files = current;
+                        rx = FileWatcher::watch_all(files.clone());
+                    }
                 }
             })
             .detach();
@@ -185,6 +265,12 @@ impl Config {
     }
 }
 
+/// Whether a config section differs, compared through its serialized form
+/// so config types don't all need `PartialEq`.
+fn section_changed<T: Serialize>(old: &T, new: &T) -> bool {
+    toml::Value::try_from(old).ok() != toml::Value::try_from(new).ok()
+}
+
 /// Trait for accessing active app configuration from `App`.
 pub trait ActiveConfig {
     fn config(&self) -> &Config;
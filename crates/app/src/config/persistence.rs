@@ -1,9 +1,15 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use anyhow::{Context, anyhow};
 
 use super::Config;
+use super::document;
+use super::sources::{self, SourceError, Sources};
+
+/// Config file given on the command line, overriding the default location.
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 fn default_config_path() -> anyhow::Result<PathBuf> {
     if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
@@ -22,27 +28,74 @@ fn default_config_path() -> anyhow::Result<PathBuf> {
     ))
 }
 
+/// Use `path` instead of the default config location.
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_PATH_OVERRIDE.set(path);
+}
+
 pub fn config_path() -> anyhow::Result<PathBuf> {
-    default_config_path()
+    match CONFIG_PATH_OVERRIDE.get() {
+        Some(path) => Ok(path.clone()),
+        None => default_config_path(),
+    }
 }
 
-pub fn load() -> anyhow::Result<Config> {
-    let path = default_config_path()?;
+/// Load the config file, or `None` if it does not exist yet.
+pub fn load() -> anyhow::Result<Option<Config>> {
+    let path = config_path()?;
     if !path.exists() {
-        let config = Config::default();
-        save(&config)?;
-        return Ok(config);
+        return Ok(None);
     }
 
-    let raw = fs::read_to_string(&path)
-        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-    let parsed = toml::from_str::<Config>(&raw)
-        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
-    Ok(parsed)
+    load_from(&path).map(Some)
+}
+
+/// Parse the config file at `path` together with its includes and host
+/// overlay.
+///
+/// Errors are reported as `path:line:column: message`, pointing into the
+/// file that caused them.
+pub fn load_from(path: &Path) -> anyhow::Result<Config> {
+    parse(&sources::read(path)?, path)
+}
+
+fn parse(sources: &Sources, path: &Path) -> anyhow::Result<Config> {
+    toml::Value::Table(sources.merged.clone())
+        .try_into::<Config>()
+        .map_err(|err| locate_type_error(&sources.files, path, err).into())
+}
+
+/// Find which source file a type error comes from by parsing each file on
+/// its own, starting with the one of highest precedence.
+pub(super) fn locate_type_error(
+    files: &[(PathBuf, String)],
+    path: &Path,
+    err: toml::de::Error,
+) -> SourceError {
+    files
+        .iter()
+        .rev()
+        .find_map(|(file, raw)| {
+            toml::from_str::<Config>(raw)
+                .err()
+                .map(|err| SourceError::from_toml(file, raw, &err))
+        })
+        .unwrap_or_else(|| SourceError {
+            path: path.to_path_buf(),
+            location: None,
+            message: err.message().to_string(),
+        })
 }
 
+/// Persist `config`.
+///
+/// An existing file is edited in place: only values that differ from what
+/// the config currently loads as are written, keeping comments, key order,
+/// unknown keys and `${VAR}` references. Changes to values the host
+/// overlay sets are not written, since the overlay would override them
+/// again; a file that does not load is not touched at all.
 pub fn save(config: &Config) -> anyhow::Result<()> {
-    let path = default_config_path()?;
+    let path = config_path()?;
     let parent = path.parent().ok_or_else(|| {
         anyhow!(
             "Invalid config path has no parent directory: {}",
@@ -52,8 +105,44 @@ pub fn save(config: &Config) -> anyhow::Result<()> {
     fs::create_dir_all(parent)
         .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
 
-    let encoded = toml::to_string_pretty(config).context("Failed to encode config as TOML")?;
-    fs::write(&path, encoded)
-        .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+    if !path.exists() {
+        let encoded = toml::to_string_pretty(config).context("Failed to encode config as TOML")?;
+        return fs::write(&path, encoded)
+            .with_context(|| format!("Failed to write config file: {}", path.display()));
+    }
+
+    let sources = sources::read(&path).context("Not saving over a config that does not load")?;
+    let current = parse(&sources, &path).context("Not saving over a config that does not load")?;
+
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let own: toml::Table = toml::from_str(&raw)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+    let (rebased, skipped) = document::rebase(
+        &own,
+        &to_table(&current)?,
+        &to_table(config)?,
+        &sources.overlay,
+    );
+    if !skipped.is_empty() {
+        tracing::warn!(
+            "Not saving {}: set by the host overlay, which would override them",
+            skipped.join(", ")
+        );
+    }
+
+    let encoded = document::update(&raw, &own, &rebased)
+        .with_context(|| format!("Failed to update config file: {}", path.display()))?;
+    if encoded != raw {
+        fs::write(&path, encoded)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+    }
     Ok(())
 }
+
+fn to_table(config: &Config) -> anyhow::Result<toml::Table> {
+    match toml::Value::try_from(config).context("Failed to encode config as TOML")? {
+        toml::Value::Table(table) => Ok(table),
+        _ => Err(anyhow!("Config did not encode as a TOML table")),
+    }
+}
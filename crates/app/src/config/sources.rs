@@ -0,0 +1,366 @@
+//! Assembling the config from several files.
+//!
+//! A config file may pull in other files with `include = ["..."]`; the
+//! including file wins over what it includes. After the main file, a
+//! host-specific overlay `config.<hostname>.toml` next to it (if present)
+//! is merged on top. Tables are merged key by key; any other value,
+//! including arrays, replaces the one below it. Finally `${VAR}` (or
+//! `${VAR:-default}`) in string values is replaced from the environment.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use toml::{Table, Value};
+
+/// Key listing files to include.
+pub(super) const INCLUDE_KEY: &str = "include";
+
+/// A problem in one of the files making up the config.
+#[derive(Debug)]
+pub struct SourceError {
+    pub path: PathBuf,
+    /// 1-based line and column, when known.
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl SourceError {
+    /// Build an error from a TOML parse error in `raw`.
+    pub(super) fn from_toml(path: &Path, raw: &str, err: &toml::de::Error) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            location: err.span().map(|span| line_column(raw, span.start)),
+            message: err.message().to_string(),
+        }
+    }
+
+    fn new(path: &Path, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            location: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(
+                f,
+                "{}:{}:{}: {}",
+                self.path.display(),
+                line,
+                column,
+                self.message
+            ),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+/// The files a config was assembled from and the merged result.
+pub(super) struct Sources {
+    /// Files in merge order (lowest precedence first) with their contents.
+    pub files: Vec<(PathBuf, String)>,
+    /// Merged table with environment variables expanded.
+    pub merged: Table,
+    /// What the host overlay and its includes set, unexpanded.
+    pub overlay: Table,
+}
+
+/// Read `path`, its includes and its host overlay.
+pub(super) fn read(path: &Path) -> anyhow::Result<Sources> {
+    let mut sources = Sources {
+        files: Vec::new(),
+        merged: Table::new(),
+        overlay: Table::new(),
+    };
+    read_with_includes(path, &mut sources, &mut Vec::new())?;
+
+    if let Some(overlay) = host_overlay_path(path)
+        && overlay.exists()
+    {
+        let mut overlay_sources = Sources {
+            files: Vec::new(),
+            merged: Table::new(),
+            overlay: Table::new(),
+        };
+        read_with_includes(&overlay, &mut overlay_sources, &mut Vec::new())?;
+        merge(&mut sources.merged, overlay_sources.merged.clone());
+        sources.files.extend(overlay_sources.files);
+        sources.overlay = overlay_sources.merged;
+    }
+
+    expand_table(&mut sources.merged);
+    Ok(sources)
+}
+
+/// Every file the config at `path` is assembled from: the file itself,
+/// its includes, and the host overlay with its includes.
+///
+/// Meant for watching, so it is best effort: files that are missing or do
+/// not parse are still listed, only their includes are not followed. The
+/// host overlay is listed even when it does not exist yet.
+pub(super) fn files(path: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_files(path, &mut files);
+    if let Some(overlay) = host_overlay_path(path) {
+        collect_files(&overlay, &mut files);
+    }
+    files
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if files.iter().any(|file| file == path) {
+        return;
+    }
+    files.push(path.to_path_buf());
+
+    let Some(table) = fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| toml::from_str::<Table>(&raw).ok())
+    else {
+        return;
+    };
+    if let Some(Value::Array(items)) = table.get(INCLUDE_KEY) {
+        for item in items {
+            if let Value::String(include) = item {
+                collect_files(&resolve_include(path, include), files);
+            }
+        }
+    }
+}
+
+/// Path of the host overlay for `path`, e.g. `config.laptop.toml`.
+pub(super) fn host_overlay_path(path: &Path) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_str()?;
+    let host = hostname()?;
+    Some(path.with_file_name(format!("{stem}.{host}.toml")))
+}
+
+fn hostname() -> Option<String> {
+    ["/proc/sys/kernel/hostname", "/etc/hostname"]
+        .iter()
+        .find_map(|file| fs::read_to_string(file).ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+}
+
+fn read_with_includes(
+    path: &Path,
+    sources: &mut Sources,
+    stack: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&canonical) {
+        return Err(SourceError::new(path, "include cycle").into());
+    }
+
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let mut table: Table =
+        toml::from_str(&raw).map_err(|err| SourceError::from_toml(path, &raw, &err))?;
+
+    let includes = match table.remove(INCLUDE_KEY) {
+        None => Vec::new(),
+        Some(Value::Array(items)) => items
+            .into_iter()
+            .map(|item| match item {
+                Value::String(include) => Ok(resolve_include(path, &include)),
+                _ => Err(SourceError::new(path, "`include` entries must be strings")),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(_) => {
+            return Err(SourceError::new(path, "`include` must be an array of paths").into());
+        }
+    };
+
+    stack.push(canonical);
+    for include in includes {
+        read_with_includes(&include, sources, stack)?;
+    }
+    stack.pop();
+
+    merge(&mut sources.merged, table);
+    sources.files.push((path.to_path_buf(), raw));
+    Ok(())
+}
+
+/// Resolve an include relative to the including file, expanding `~` and
+/// environment variables.
+fn resolve_include(from: &Path, include: &str) -> PathBuf {
+    let include = expand_env(include);
+    let path = match include.strip_prefix("~/") {
+        Some(rest) => match std::env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(rest),
+            None => PathBuf::from(&include),
+        },
+        None => PathBuf::from(&include),
+    };
+
+    match from.parent() {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path,
+    }
+}
+
+/// Merge `overlay` into `base`: tables recursively, other values replaced.
+pub(super) fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn expand_table(table: &mut Table) {
+    for (_, value) in table.iter_mut() {
+        expand_value(value);
+    }
+}
+
+fn expand_value(value: &mut Value) {
+    match value {
+        Value::String(s) if s.contains("${") => *s = expand_env(s),
+        Value::Array(items) => items.iter_mut().for_each(expand_value),
+        Value::Table(table) => expand_table(table),
+        _ => {}
+    }
+}
+
+/// Replace `${VAR}` and `${VAR:-default}` with values from the environment.
+/// Unset variables without a default are left as written.
+fn expand_env(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let expr = &rest[start + 2..start + 2 + len];
+        let (name, default) = match expr.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (expr, None),
+        };
+        match (std::env::var(name), default) {
+            (Ok(value), _) => out.push_str(&value),
+            (Err(_), Some(default)) => out.push_str(default),
+            (Err(_), None) => {
+                tracing::warn!("Config references unset environment variable {}", name);
+                out.push_str(&rest[start..start + 3 + len]);
+            }
+        }
+        rest = &rest[start + 3 + len..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Convert a byte offset into a 1-based line and column.
+pub(super) fn line_column(raw: &str, offset: usize) -> (usize, usize) {
+    let before = &raw[..offset.min(raw.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(raw: &str) -> Table {
+        toml::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn test_merge_tables_recursively() {
+        let mut base = table(
+            r#"
+            watch_config = true
+            [bar]
+            size = 32
+            end = ["Clock", "Battery"]
+            "#,
+        );
+        merge(
+            &mut base,
+            table(
+                r#"
+                [bar]
+                end = ["Clock"]
+                [osd]
+                position = "top"
+                "#,
+            ),
+        );
+
+        let expected = table(
+            r#"
+            watch_config = true
+            [bar]
+            size = 32
+            end = ["Clock"]
+            [osd]
+            position = "top"
+            "#,
+        );
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn test_merge_replaces_mismatched_values() {
+        let mut base = table("[bar]\nsize = 32");
+        merge(&mut base, table("bar = false"));
+        assert_eq!(base, table("bar = false"));
+    }
+
+    #[test]
+    fn test_expand_env_defaults() {
+        assert_eq!(expand_env("plain text"), "plain text");
+        assert_eq!(expand_env("${GPUISHELL_TEST_UNSET:-fallback}"), "fallback");
+        assert_eq!(
+            expand_env("a${GPUISHELL_TEST_UNSET:-b}c${GPUISHELL_TEST_UNSET:-}d"),
+            "abcd"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_keeps_unresolved() {
+        assert_eq!(
+            expand_env("${GPUISHELL_TEST_UNSET}/x"),
+            "${GPUISHELL_TEST_UNSET}/x"
+        );
+        assert_eq!(expand_env("${unterminated"), "${unterminated");
+    }
+
+    #[test]
+    fn test_expand_env_set_variable() {
+        let Ok(home) = std::env::var("HOME") else {
+            return;
+        };
+        assert_eq!(expand_env("${HOME}/wall.png"), format!("{home}/wall.png"));
+        assert_eq!(expand_env("${HOME:-/tmp}"), home);
+    }
+
+    #[test]
+    fn test_line_column() {
+        let raw = "a = 1\nbb = 2\n";
+        assert_eq!(line_column(raw, 0), (1, 1));
+        assert_eq!(line_column(raw, 9), (2, 4));
+        assert_eq!(line_column(raw, 100), (3, 1));
+    }
+}
@@ -5,6 +5,7 @@ use anyhow::{Context, anyhow};
 use ui::Theme;
 
 use super::config::StoredTheme;
+use crate::config::document;
 
 pub fn theme_path() -> anyhow::Result<PathBuf> {
     if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
@@ -61,8 +62,21 @@ pub fn save_theme(theme: &Theme) -> anyhow::Result<()> {
     fs::create_dir_all(parent)
         .with_context(|| format!("Failed to create theme directory: {}", parent.display()))?;
 
-    let encoded = toml::to_string_pretty(&StoredTheme::from_theme(theme))
-        .context("Failed to encode theme")?;
+    let stored = StoredTheme::from_theme(theme);
+    let encoded = match fs::read_to_string(&path) {
+        // Edit an existing file in place so hand-written comments survive.
+        Ok(raw) => {
+            let current = toml::from_str(&raw).unwrap_or_default();
+            let toml::Value::Table(new) =
+                toml::Value::try_from(&stored).context("Failed to encode theme")?
+            else {
+                return Err(anyhow!("Theme did not encode as a TOML table"));
+            };
+            document::update(&raw, &current, &new)
+                .with_context(|| format!("Failed to update theme file: {}", path.display()))?
+        }
+        Err(_) => toml::to_string_pretty(&stored).context("Failed to encode theme")?,
+    };
     fs::write(&path, encoded)
         .with_context(|| format!("Failed to write theme file: {}", path.display()))?;
     Ok(())
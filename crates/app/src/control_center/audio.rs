@@ -0,0 +1,193 @@
+//! Audio device section for the Control Center.
+//!
+//! Lists output and input devices and switches the default device.
+
+use gpui::{App, ElementId, MouseButton, SharedString, div, prelude::*, px};
+use services::{AudioCommand, AudioDevice};
+use ui::{ActiveTheme, icon_size, radius, spacing};
+
+use crate::config::ActiveConfig;
+use crate::state::AppState;
+
+use super::icons;
+
+/// Which kind of device a list shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeviceKind {
+    Output,
+    Input,
+}
+
+/// Render the audio section (device lists)
+pub fn render_audio_section(cx: &App) -> impl IntoElement {
+    let audio = AppState::audio(cx).get();
+
+    div()
+        .w_full()
+        .flex()
+        .flex_col()
+        .gap(px(spacing::SM))
+        .child(render_device_list(DeviceKind::Output, audio.sinks, cx))
+        .child(render_device_list(DeviceKind::Input, audio.sources, cx))
+}
+
+/// Render the header and device rows for one kind of device
+fn render_device_list(kind: DeviceKind, devices: Vec<AudioDevice>, cx: &App) -> impl IntoElement {
+    let theme = cx.theme();
+    let (icon, title, empty) = match kind {
+        DeviceKind::Output => (icons::SPEAKER, "Output", "No output devices"),
+        DeviceKind::Input => (icons::MICROPHONE, "Input", "No input devices"),
+    };
+
+    div()
+        .w_full()
+        .flex()
+        .flex_col()
+        .gap(px(spacing::XS))
+        .child(
+            // Section header
+            div()
+                .flex()
+                .items_center()
+                .gap(px(spacing::SM))
+                .child(
+                    div()
+                        .text_size(px(icon_size::SM))
+                        .text_color(theme.text.muted)
+                        .child(icon),
+                )
+                .child(
+                    div()
+                        .text_size(theme.font_sizes.sm)
+                        .text_color(theme.text.secondary)
+                        .font_weight(gpui::FontWeight::MEDIUM)
+                        .child(title),
+                ),
+        )
+        .when(devices.is_empty(), |el| {
+            el.child(
+                div()
+                    .py(px(spacing::SM))
+                    .text_size(theme.font_sizes.sm)
+                    .text_color(theme.text.muted)
+                    .text_center()
+                    .child(empty),
+            )
+        })
+        .when(!devices.is_empty(), |el| {
+            el.child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(px(2.))
+                    .bg(theme.bg.primary)
+                    .border_1()
+                    .border_color(theme.border.subtle)
+                    .rounded(px(radius::SM))
+                    .py(px(spacing::XS))
+                    .children(
+                        devices
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, device)| render_device_item(kind, index, device, cx)),
+                    ),
+            )
+        })
+}
+
+/// Render a single device row; clicking it makes the device the default
+fn render_device_item(
+    kind: DeviceKind,
+    index: usize,
+    device: AudioDevice,
+    cx: &App,
+) -> impl IntoElement {
+    let theme = cx.theme();
+    let label = device.label();
+    let icon = device_icon(kind, &device);
+    let is_default = device.is_default;
+
+    // Pre-compute colors for use in closures
+    let accent_selection = theme.accent.selection;
+    let accent_primary = theme.accent.primary;
+    let interactive_hover = theme.interactive.hover;
+    let text_muted = theme.text.muted;
+    let text_primary = theme.text.primary;
+
+    let services = AppState::audio(cx).clone();
+    let move_streams = cx.config().control_center.move_audio_streams;
+    let name = device.name.clone();
+    let id_prefix = match kind {
+        DeviceKind::Output => "audio-sink",
+        DeviceKind::Input => "audio-source",
+    };
+
+    div()
+        .id(ElementId::Name(SharedString::from(format!(
+            "{}-{}",
+            id_prefix, index
+        ))))
+        .flex()
+        .items_center()
+        .gap(px(spacing::SM))
+        .w_full()
+        .px(px(spacing::SM))
+        .py(px(spacing::XS))
+        .rounded(px(radius::SM))
+        .when(is_default, |el| el.bg(accent_selection))
+        .when(!is_default, |el| {
+            el.cursor_pointer()
+                .hover(move |s| s.bg(interactive_hover))
+                .on_mouse_down(MouseButton::Left, move |_, _, _| {
+                    let name = name.clone();
+                    services.dispatch(match kind {
+                        DeviceKind::Output => AudioCommand::SetDefaultSink { name, move_streams },
+                        DeviceKind::Input => AudioCommand::SetDefaultSource { name, move_streams },
+                    });
+                })
+        })
+        // Device icon
+        .child(
+            div()
+                .text_size(px(icon_size::SM))
+                .text_color(if is_default {
+                    accent_primary
+                } else {
+                    text_muted
+                })
+                .child(icon),
+        )
+        // Device label
+        .child(
+            div()
+                .flex_1()
+                .text_size(theme.font_sizes.sm)
+                .text_color(text_primary)
+                .overflow_hidden()
+                .child(label),
+        )
+        .when(is_default, |el| {
+            el.child(
+                div()
+                    .text_size(px(icon_size::SM))
+                    .text_color(accent_primary)
+                    .child(icons::CHECK),
+            )
+        })
+}
+
+/// Pick an icon from the device's port and name
+fn device_icon(kind: DeviceKind, device: &AudioDevice) -> &'static str {
+    let port = device.port.as_deref().unwrap_or_default().to_lowercase();
+    let name = device.name.to_lowercase();
+
+    if port.contains("headphone") || port.contains("headset") || name.contains("bluez") {
+        icons::HEADPHONES
+    } else if kind == DeviceKind::Input {
+        icons::MICROPHONE
+    } else if name.contains("hdmi") || port.contains("hdmi") || port.contains("displayport") {
+        icons::MONITOR
+    } else {
+        icons::SPEAKER
+    }
+}
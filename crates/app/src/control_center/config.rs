@@ -26,12 +26,16 @@ impl Default for PowerActionsConfig {
 #[serde(default)]
 pub struct ControlCenterConfig {
     pub power_actions: PowerActionsConfig,
+    /// Move playing and recording streams when switching the default
+    /// audio device.
+    pub move_audio_streams: bool,
 }
 
 impl Default for ControlCenterConfig {
     fn default() -> Self {
         Self {
             power_actions: PowerActionsConfig::default(),
+            move_audio_streams: true,
         }
     }
 }
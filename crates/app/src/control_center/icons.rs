@@ -2,6 +2,8 @@
 //!
 //! Uses Nerd Font glyphs for consistent iconography.
 
+use services::BrightnessKind;
+
 // Audio
 pub const VOLUME_HIGH: &str = "󰕾";
 pub const VOLUME_MED: &str = "󰖀";
@@ -9,11 +11,16 @@ pub const VOLUME_LOW: &str = "󰕿";
 pub const VOLUME_MUTE: &str = "󰝟";
 pub const MICROPHONE: &str = "󰍬";
 pub const MICROPHONE_MUTE: &str = "󰍭";
+pub const SPEAKER: &str = "󰓃";
+pub const HEADPHONES: &str = "󰋋";
+pub const MONITOR: &str = "󰍹";
+pub const MIXER: &str = "󰝚";
 
 // Brightness
 pub const BRIGHTNESS: &str = "󰃟";
 pub const BRIGHTNESS_LOW: &str = "󰃞";
 pub const BRIGHTNESS_HIGH: &str = "󰃠";
+pub const KEYBOARD_BACKLIGHT: &str = "󰌌";
 
 // Connectivity
 pub const BLUETOOTH: &str = "󰂯";
@@ -64,8 +71,8 @@ pub fn wifi_signal_icon(strength: u8) -> &'static str {
     }
 }
 
-/// Get volume icon based on level (0-100) and mute state
-pub fn volume_icon(level: u8, muted: bool) -> &'static str {
+/// Get volume icon based on level (percentage) and mute state
+pub fn volume_icon(level: u16, muted: bool) -> &'static str {
     if muted || level == 0 {
         VOLUME_MUTE
     } else if level >= 66 {
@@ -77,6 +84,17 @@ pub fn volume_icon(level: u8, muted: bool) -> &'static str {
     }
 }
 
+/// Get brightness icon based on device kind and level (0-100)
+pub fn brightness_icon(kind: BrightnessKind, level: u8) -> &'static str {
+    match kind {
+        BrightnessKind::Keyboard => KEYBOARD_BACKLIGHT,
+        BrightnessKind::External => MONITOR,
+        BrightnessKind::Backlight if level < 33 => BRIGHTNESS_LOW,
+        BrightnessKind::Backlight if level < 66 => BRIGHTNESS,
+        BrightnessKind::Backlight => BRIGHTNESS_HIGH,
+    }
+}
+
 /// Get battery icon based on percentage and charging state
 pub fn battery_icon(percentage: u8, charging: bool) -> &'static str {
     if charging {
@@ -0,0 +1,219 @@
+//! Per-application volume mixer for the Control Center.
+//!
+//! Shows one slider per playback and recording stream, with mute and a
+//! button that moves the stream to the next device.
+
+use std::collections::HashMap;
+
+use gpui::{App, ElementId, Entity, MouseButton, SharedString, div, img, prelude::*, px};
+use services::{AudioCommand, AudioDevice, AudioStream, StreamKind};
+use ui::{ActiveTheme, Slider, icon_size, radius, spacing};
+
+use crate::state::AppState;
+
+use super::{icons, sliders, tooltip::control_center_tooltip};
+
+/// Identifies a stream's slider
+pub type StreamKey = (StreamKind, u32);
+
+/// Render the mixer section (returns empty if no application streams)
+pub fn render_mixer_section(
+    sliders: &HashMap<StreamKey, Entity<Slider>>,
+    cx: &App,
+) -> impl IntoElement {
+    let theme = cx.theme();
+    let audio = AppState::audio(cx).get();
+
+    if audio.playback_streams.is_empty() && audio.recording_streams.is_empty() {
+        return div().into_any_element();
+    }
+
+    let streams = audio
+        .playback_streams
+        .iter()
+        .chain(&audio.recording_streams)
+        .filter_map(|stream| {
+            let slider = sliders.get(&(stream.kind, stream.index))?;
+            let devices = match stream.kind {
+                StreamKind::Playback => &audio.sinks,
+                StreamKind::Recording => &audio.sources,
+            };
+            Some(render_stream_item(stream, slider, devices, cx))
+        })
+        .collect::<Vec<_>>();
+
+    div()
+        .w_full()
+        .flex()
+        .flex_col()
+        .gap(px(spacing::XS))
+        .child(
+            // Section header
+            div()
+                .flex()
+                .items_center()
+                .gap(px(spacing::SM))
+                .child(
+                    div()
+                        .text_size(px(icon_size::SM))
+                        .text_color(theme.text.muted)
+                        .child(icons::MIXER),
+                )
+                .child(
+                    div()
+                        .text_size(theme.font_sizes.sm)
+                        .text_color(theme.text.secondary)
+                        .font_weight(gpui::FontWeight::MEDIUM)
+                        .child("Applications"),
+                ),
+        )
+        .child(
+            div()
+                .id("mixer-streams")
+                .flex()
+                .flex_col()
+                .gap(px(spacing::XS))
+                .max_h(px(240.))
+                .overflow_y_scroll()
+                .bg(theme.bg.primary)
+                .border_1()
+                .border_color(theme.border.subtle)
+                .rounded(px(radius::SM))
+                .p(px(spacing::XS))
+                .children(streams),
+        )
+        .into_any_element()
+}
+
+/// Render one stream: application, routed device, mute and volume slider
+fn render_stream_item(
+    stream: &AudioStream,
+    slider: &Entity<Slider>,
+    devices: &[AudioDevice],
+    cx: &App,
+) -> impl IntoElement {
+    let theme = cx.theme();
+    let (kind, index) = (stream.kind, stream.index);
+    let id_prefix = match kind {
+        StreamKind::Playback => "mixer-playback",
+        StreamKind::Recording => "mixer-recording",
+    };
+    let icon_path = stream
+        .icon
+        .as_deref()
+        .and_then(|icon| AppState::applications(cx).icon_for_class(icon));
+    let fallback_icon = match kind {
+        StreamKind::Playback => icons::VOLUME_HIGH,
+        StreamKind::Recording => icons::MICROPHONE,
+    };
+    let mute_icon = match (kind, stream.muted) {
+        (StreamKind::Playback, muted) => icons::volume_icon(stream.volume, muted),
+        (StreamKind::Recording, true) => icons::MICROPHONE_MUTE,
+        (StreamKind::Recording, false) => icons::MICROPHONE,
+    };
+    let title = match &stream.media_name {
+        Some(media) if !media.is_empty() && *media != stream.app_name => {
+            format!("{} · {}", stream.app_name, media)
+        }
+        _ => stream.app_name.clone(),
+    };
+
+    // The device the stream is on, and the one a click moves it to
+    let current = devices.iter().position(|d| d.index == stream.device);
+    let device_label = current
+        .map(|i| devices[i].label())
+        .unwrap_or_else(|| "Unknown device".to_string());
+    let next_device = (devices.len() > 1)
+        .then(|| &devices[current.map_or(0, |i| (i + 1) % devices.len())])
+        .map(|d| d.name.clone());
+
+    let services_mute = AppState::audio(cx).clone();
+    let services_move = AppState::audio(cx).clone();
+
+    // Pre-compute colors for closures
+    let interactive_hover = theme.interactive.hover;
+    let interactive_default = theme.interactive.default;
+    let text_primary = theme.text.primary;
+    let text_muted = theme.text.muted;
+
+    div()
+        .w_full()
+        .flex()
+        .flex_col()
+        .gap(px(2.))
+        .px(px(spacing::XS))
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap(px(spacing::SM))
+                .child(
+                    div()
+                        .w(px(16.))
+                        .h(px(16.))
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .when_some(icon_path.clone(), |el, path| {
+                            el.child(img(path).size_full())
+                        })
+                        .when(icon_path.is_none(), |el| {
+                            el.text_size(px(icon_size::SM))
+                                .text_color(text_muted)
+                                .child(fallback_icon)
+                        }),
+                )
+                .child(
+                    div()
+                        .flex_1()
+                        .overflow_hidden()
+                        .text_size(theme.font_sizes.sm)
+                        .text_color(text_primary)
+                        .child(title),
+                )
+                .child(
+                    div()
+                        .id(ElementId::Name(SharedString::from(format!(
+                            "{}-device-{}",
+                            id_prefix, index
+                        ))))
+                        .max_w(px(120.))
+                        .overflow_hidden()
+                        .px(px(spacing::XS))
+                        .rounded(px(radius::SM))
+                        .bg(interactive_default)
+                        .text_size(theme.font_sizes.xs)
+                        .text_color(text_muted)
+                        .child(device_label)
+                        .when_some(next_device, |el, device| {
+                            el.cursor_pointer()
+                                .hover(move |s| s.bg(interactive_hover))
+                                .on_mouse_down(MouseButton::Left, move |_, _, _| {
+                                    services_move.dispatch(AudioCommand::MoveStream {
+                                        kind,
+                                        index,
+                                        device: device.clone(),
+                                    });
+                                })
+                                .tooltip(control_center_tooltip("Move to next device"))
+                        }),
+                ),
+        )
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap(px(spacing::SM))
+                .child(sliders::render_slider_icon(
+                    ElementId::Name(SharedString::from(format!("{}-mute-{}", id_prefix, index))),
+                    mute_icon,
+                    stream.muted,
+                    cx,
+                    move |_cx| {
+                        services_mute.dispatch(AudioCommand::ToggleStreamMute { kind, index });
+                    },
+                ))
+                .child(div().flex_1().child(slider.clone()))
+                .child(sliders::render_percentage_label(stream.volume, cx)),
+        )
+}
@@ -3,24 +3,28 @@
 //! This module provides a panel for controlling system settings like:
 //! - WiFi networks and connections
 //! - Bluetooth devices
-//! - Volume and brightness
+//! - Volume, and brightness of every display and keyboard backlight
 //! - Power profiles and battery status
 //!
 //! The module is split into submodules for better organization:
 //! - `icons` - Icon constants (Nerd Font glyphs)
 //! - `quick_toggles` - Quick toggle buttons for WiFi, Bluetooth, Mic
 //! - `sliders` - Volume and brightness slider controls
+//! - `audio` - Audio output and input device picker
+//! - `mixer` - Per-application volume sliders
 //! - `wifi` - WiFi network list and password handling
 //! - `bluetooth` - Bluetooth device list and connections
 //! - `power` - Battery status and power profiles
 
+mod audio;
 mod bluetooth;
 pub mod config;
 pub mod icons;
+mod mixer;
 mod power;
 mod quick_toggles;
 mod sliders;
-mod tooltip;
+pub(crate) mod tooltip;
 mod wifi;
 
 pub use config::{ControlCenterConfig, PowerActionsConfig};
@@ -29,14 +33,20 @@ use gpui::{
     App, AvailableSpace, Context, Entity, FocusHandle, Focusable, MouseButton, Size, Window, div,
     prelude::*, px,
 };
-use services::{AudioCommand, BrightnessCommand, NetworkCommand, UPowerCommand};
+use services::{
+    AudioCommand, AudioData, BrightnessCommand, BrightnessData, NetworkCommand, ServiceStatus,
+    UPowerCommand,
+};
+use std::collections::HashMap;
 use std::rc::Rc;
 use ui::{ActiveTheme, Slider, SliderEvent, icon_size, radius, spacing};
 
+use crate::config::Config;
 use crate::keybinds::{
     Backspace, Cancel, Confirm, CursorLeft, CursorRight, DeleteWordBack, SelectAll, SelectLeft,
     SelectRight, SelectWordLeft, SelectWordRight, WordLeft, WordRight,
 };
+use crate::panel::{PanelConfig, panel_placement_default, toggle_panel};
 use crate::state::{AppState, watch};
 
 pub use quick_toggles::ExpandedSection;
@@ -45,6 +55,25 @@ pub use wifi::WifiPasswordState;
 pub const CONTROL_CENTER_PANEL_WIDTH: f32 = 340.0;
 pub const CONTROL_CENTER_PANEL_HEIGHT_COLLAPSED: f32 = 288.0;
 
+/// Toggle the control center panel next to the bar.
+pub fn toggle(cx: &mut App) {
+    let (anchor, margin) = panel_placement_default(Config::global(cx).bar.position);
+    toggle_at(anchor, margin, cx);
+}
+
+/// Toggle the control center panel at the given placement.
+pub fn toggle_at(anchor: gpui::layer_shell::Anchor, margin: (f32, f32, f32, f32), cx: &mut App) {
+    let config = PanelConfig {
+        width: CONTROL_CENTER_PANEL_WIDTH,
+        height: CONTROL_CENTER_PANEL_HEIGHT_COLLAPSED,
+        anchor,
+        margin,
+        namespace: "control-center".to_string(),
+    };
+
+    toggle_panel("control-center", config, cx, ControlCenter::new);
+}
+
 /// Control Center panel component.
 ///
 /// Provides a unified interface for system settings and quick actions.
@@ -55,8 +84,10 @@ pub struct ControlCenter {
     focus_handle: FocusHandle,
     /// Volume slider entity
     volume_slider: Entity<Slider>,
-    /// Brightness slider entity
-    brightness_slider: Entity<Slider>,
+    /// Brightness slider entities by device id
+    brightness_sliders: HashMap<String, Entity<Slider>>,
+    /// Volume slider entities of application streams
+    stream_sliders: HashMap<mixer::StreamKey, Entity<Slider>>,
     /// WiFi password input state
     wifi_password: WifiPasswordState,
 }
@@ -68,60 +99,123 @@ impl ControlCenter {
 
         // Create volume slider
         let audio = AppState::audio(cx).get();
+        let volume_max = sliders::volume_slider_max(audio.sink_volume, cx);
         let volume_slider = cx.new(|_| {
             Slider::new()
                 .min(0.0)
-                .max(100.0)
+                .max(volume_max)
                 .step(1.0)
                 .default_value(audio.sink_volume as f32)
         });
 
-        // Create brightness slider
-        let brightness = AppState::brightness(cx).get();
-        let brightness_slider = cx.new(|_| {
-            Slider::new()
-                .min(0.0)
-                .max(100.0)
-                .step(1.0)
-                .default_value(brightness.percentage() as f32)
-        });
-
         // Subscribe to slider events
         let audio_services = AppState::audio(cx).clone();
         cx.subscribe(
             &volume_slider,
             move |_this, _slider, event: &SliderEvent, _cx| {
                 let SliderEvent::Change(value) = event;
-                let target = *value as u8;
+                let target = *value as u16;
                 audio_services.dispatch(AudioCommand::SetSinkVolume(target));
             },
         )
         .detach();
 
-        let brightness_services = AppState::brightness(cx).clone();
-        cx.subscribe(
-            &brightness_slider,
-            move |_this, _slider, event: &SliderEvent, cx| {
-                let SliderEvent::Change(value) = event;
-                let target = *value as u8;
-                let s = brightness_services.clone();
-                cx.spawn(async move |_, _| {
-                    let _ = s.dispatch(BrightnessCommand::SetPercent(target)).await;
-                })
-                .detach();
-            },
-        )
-        .detach();
-
         // Subscribe to service updates
         Self::subscribe_to_services(cx);
 
-        ControlCenter {
+        let mut control_center = ControlCenter {
             expanded: ExpandedSection::None,
             focus_handle,
             volume_slider,
-            brightness_slider,
+            brightness_sliders: HashMap::new(),
+            stream_sliders: HashMap::new(),
             wifi_password: WifiPasswordState::default(),
+        };
+        control_center.sync_stream_sliders(&audio, cx);
+        control_center.sync_brightness_sliders(&AppState::brightness(cx).get(), cx);
+        control_center
+    }
+
+    /// Keep one brightness slider per device
+    fn sync_brightness_sliders(&mut self, brightness: &BrightnessData, cx: &mut Context<Self>) {
+        self.brightness_sliders
+            .retain(|id, _| brightness.device(id).is_some());
+
+        for device in &brightness.devices {
+            let percent = device.percentage() as f32;
+            if let Some(slider) = self.brightness_sliders.get(&device.id) {
+                slider.update(cx, |slider, cx| {
+                    slider.set_value(percent, cx);
+                });
+                continue;
+            }
+
+            let slider = cx.new(|_| {
+                Slider::new()
+                    .min(0.0)
+                    .max(100.0)
+                    .step(1.0)
+                    .default_value(percent)
+            });
+            let brightness_services = AppState::brightness(cx).clone();
+            let id = device.id.clone();
+            cx.subscribe(&slider, move |_this, _slider, event: &SliderEvent, cx| {
+                let SliderEvent::Change(value) = event;
+                let command = BrightnessCommand::SetPercent {
+                    device: id.clone(),
+                    percent: *value as u8,
+                };
+                let s = brightness_services.clone();
+                cx.spawn(async move |_, _| {
+                    let _ = s.dispatch(command).await;
+                })
+                .detach();
+            })
+            .detach();
+            self.brightness_sliders.insert(device.id.clone(), slider);
+        }
+    }
+
+    /// Keep one volume slider per application stream
+    fn sync_stream_sliders(&mut self, audio: &AudioData, cx: &mut Context<Self>) {
+        let streams: Vec<_> = audio
+            .playback_streams
+            .iter()
+            .chain(&audio.recording_streams)
+            .collect();
+        self.stream_sliders
+            .retain(|key, _| streams.iter().any(|s| (s.kind, s.index) == *key));
+
+        for stream in streams {
+            let volume = stream.volume as f32;
+            let max = sliders::volume_slider_max(stream.volume, cx);
+            if let Some(slider) = self.stream_sliders.get(&(stream.kind, stream.index)) {
+                slider.update(cx, |slider, cx| {
+                    slider.set_max(max, cx);
+                    slider.set_value(volume, cx);
+                });
+                continue;
+            }
+
+            let slider = cx.new(|_| {
+                Slider::new()
+                    .min(0.0)
+                    .max(max)
+                    .step(1.0)
+                    .default_value(volume)
+            });
+            let audio_services = AppState::audio(cx).clone();
+            let (kind, index) = (stream.kind, stream.index);
+            cx.subscribe(&slider, move |_this, _slider, event: &SliderEvent, _cx| {
+                let SliderEvent::Change(value) = event;
+                audio_services.dispatch(AudioCommand::SetStreamVolume {
+                    kind,
+                    index,
+                    volume: *value as u16,
+                });
+            })
+            .detach();
+            self.stream_sliders.insert((kind, index), slider);
         }
     }
 
@@ -133,27 +227,30 @@ impl ControlCenter {
             AppState::audio(cx).subscribe(),
             |control_center, data, cx| {
                 let volume = data.sink_volume as f32;
+                let max = sliders::volume_slider_max(data.sink_volume, cx);
                 control_center.volume_slider.update(cx, |slider, cx| {
+                    slider.set_max(max, cx);
                     slider.set_value(volume, cx);
                 });
+                control_center.sync_stream_sliders(&data, cx);
                 cx.notify();
             },
         );
+        watch(cx, AppState::audio(cx).subscribe_status(), |_, _, cx| {
+            cx.notify();
+        });
 
         // Bluetooth
         watch(cx, AppState::bluetooth(cx).subscribe(), |_, _, cx| {
             cx.notify();
         });
 
-        // Brightness - sync brightness slider
+        // Brightness - sync brightness sliders
         watch(
             cx,
             AppState::brightness(cx).subscribe(),
             |control_center, data, cx| {
-                let percent = data.percentage() as f32;
-                control_center.brightness_slider.update(cx, |slider, cx| {
-                    slider.set_value(percent, cx);
-                });
+                control_center.sync_brightness_sliders(&data, cx);
                 cx.notify();
             },
         );
@@ -321,7 +418,8 @@ impl Render for ControlCenter {
             let expanded = self.expanded;
             let upower = AppState::upower(cx).get();
             let brightness_state = AppState::brightness(cx).get();
-            let show_brightness = brightness_state.max != 0;
+            let show_brightness = !brightness_state.devices.is_empty();
+            let show_audio = AppState::audio(cx).status() != ServiceStatus::Unavailable;
             let bg_secondary = theme.bg.secondary;
             let border_subtle = theme.border.subtle;
             let interactive_default = theme.interactive.default;
@@ -376,6 +474,12 @@ impl Render for ControlCenter {
                     (on_toggle_section)(ExpandedSection::Power, cx);
                 }
             };
+            let on_toggle_section_audio = {
+                let on_toggle_section = on_toggle_section.clone();
+                move |cx: &mut App| {
+                    (on_toggle_section)(ExpandedSection::Audio, cx);
+                }
+            };
             let on_cycle_power_profile = {
                 let on_cycle_power_profile = on_cycle_power_profile.clone();
                 move |cx: &mut App| {
@@ -745,31 +849,41 @@ impl Render for ControlCenter {
                                 ),
                         ),
                 )
-                .child(
-                    div()
-                        .id("control-center-volume")
-                        .p(px(spacing::SM))
-                        .bg(bg_secondary)
-                        .border_1()
-                        .border_color(border_subtle)
-                        .rounded(px(radius::MD))
-                        .child(sliders::render_volume_slider(&self.volume_slider, cx)),
-                )
-                .when(show_brightness, |el| {
+                .when(show_audio, |el| {
                     el.child(
                         div()
-                            .id("control-center-brightness")
+                            .id("control-center-volume")
                             .p(px(spacing::SM))
                             .bg(bg_secondary)
                             .border_1()
                             .border_color(border_subtle)
                             .rounded(px(radius::MD))
-                            .child(sliders::render_brightness_slider(
-                                &self.brightness_slider,
+                            .child(sliders::render_volume_slider(
+                                &self.volume_slider,
+                                expanded == ExpandedSection::Audio,
+                                on_toggle_section_audio,
                                 cx,
                             )),
                     )
                 })
+                .when(show_brightness, |el| {
+                    el.child(
+                        div()
+                            .id("control-center-brightness")
+                            .p(px(spacing::SM))
+                            .bg(bg_secondary)
+                            .border_1()
+                            .border_color(border_subtle)
+                            .rounded(px(radius::MD))
+                            .flex()
+                            .flex_col()
+                            .gap(px(spacing::SM))
+                            .children(brightness_state.devices.iter().filter_map(|device| {
+                                let slider = self.brightness_sliders.get(&device.id)?;
+                                Some(sliders::render_brightness_slider(device, slider, cx))
+                            })),
+                    )
+                })
                 .child(quick_toggles::render_quick_toggles(
                     expanded,
                     on_toggle_section_cb,
@@ -802,6 +916,10 @@ impl Render for ControlCenter {
                             })
                             .when(expanded == ExpandedSection::Power, |el| {
                                 el.child(power::render_power_section(cx))
+                            })
+                            .when(show_audio && expanded == ExpandedSection::Audio, |el| {
+                                el.child(audio::render_audio_section(cx))
+                                    .child(mixer::render_mixer_section(&self.stream_sliders, cx))
                             }),
                     )
                 })
@@ -3,7 +3,7 @@
 //! Provides compact modules for WiFi, Bluetooth, Microphone, and Camera status.
 
 use gpui::{App, MouseButton, div, prelude::*, px};
-use services::{AudioCommand, BluetoothCommand, BluetoothState, NetworkCommand};
+use services::{AudioCommand, BluetoothCommand, BluetoothState, NetworkCommand, ServiceStatus};
 use ui::{ActiveTheme, icon_size, radius, spacing};
 
 use crate::state::AppState;
@@ -18,6 +18,7 @@ pub enum ExpandedSection {
     WiFi,
     Bluetooth,
     Power,
+    Audio,
 }
 
 /// Render the quick toggles row
@@ -47,6 +48,7 @@ pub fn render_quick_toggles(
     let bt_active = bluetooth.state == BluetoothState::Active;
     let bt_connected = bluetooth.devices.iter().filter(|d| d.connected).count();
 
+    let mic_available = AppState::audio(cx).status() != ServiceStatus::Unavailable;
     let mic_muted = audio.source_muted;
     let cam_active = privacy.webcam_access();
 
@@ -89,21 +91,23 @@ pub fn render_quick_toggles(
                 .items_center()
                 .gap(px(spacing::SM))
                 .w_full()
-                .child(render_simple_module(
-                    "mic-toggle",
-                    if mic_muted {
-                        icons::MICROPHONE_MUTE
-                    } else {
-                        icons::MICROPHONE
-                    },
-                    "Mic",
-                    mic_status,
-                    !mic_muted,
-                    cx,
-                    move |_cx| {
-                        services_mic.dispatch(AudioCommand::ToggleSourceMute);
-                    },
-                ))
+                .when(mic_available, |el| {
+                    el.child(render_simple_module(
+                        "mic-toggle",
+                        if mic_muted {
+                            icons::MICROPHONE_MUTE
+                        } else {
+                            icons::MICROPHONE
+                        },
+                        "Mic",
+                        mic_status,
+                        !mic_muted,
+                        cx,
+                        move |_cx| {
+                            services_mic.dispatch(AudioCommand::ToggleSourceMute);
+                        },
+                    ))
+                })
                 .child(render_status_module(
                     "cam-status",
                     icons::CAMERA,
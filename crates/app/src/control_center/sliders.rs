@@ -1,30 +1,48 @@
 //! Volume and brightness slider components for the Control Center.
 
-use gpui::{App, Entity, MouseButton, div, prelude::*, px};
-use services::{AudioCommand, BrightnessCommand};
+use gpui::{App, Entity, MouseButton, ScrollWheelEvent, div, prelude::*, px};
+use services::{AudioCommand, BrightnessCommand, BrightnessDevice};
 use ui::{ActiveTheme, Slider, icon_size, radius, spacing};
 
+use crate::config::ActiveConfig;
 use crate::state::AppState;
 
-use super::icons;
+use super::{icons, tooltip::control_center_tooltip};
 
 /// Render the volume slider row
-pub fn render_volume_slider(volume_slider: &Entity<Slider>, cx: &App) -> impl IntoElement {
+pub fn render_volume_slider(
+    volume_slider: &Entity<Slider>,
+    devices_expanded: bool,
+    on_toggle_devices: impl Fn(&mut App) + 'static,
+    cx: &App,
+) -> impl IntoElement {
     let audio = AppState::audio(cx).get();
     let volume = audio.sink_volume;
     let muted = audio.sink_muted;
 
     let icon = icons::volume_icon(volume, muted);
 
+    let step = cx.config().audio.step_delta();
+
     let services_toggle = AppState::audio(cx).clone();
     let services_dec = AppState::audio(cx).clone();
     let services_inc = AppState::audio(cx).clone();
+    let services_scroll = AppState::audio(cx).clone();
 
     div()
+        .id("volume-row")
         .flex()
         .items_center()
         .gap(px(spacing::SM))
         .w_full()
+        .on_scroll_wheel(move |event: &ScrollWheelEvent, _, _| {
+            let delta = event.delta.pixel_delta(px(1.0));
+            if delta.y > px(0.5) {
+                services_scroll.dispatch(AudioCommand::AdjustSinkVolume(step));
+            } else if delta.y < px(-0.5) {
+                services_scroll.dispatch(AudioCommand::AdjustSinkVolume(-step));
+            }
+        })
         // Icon (click to toggle mute)
         .child(render_slider_icon(
             "volume-icon",
@@ -44,35 +62,75 @@ pub fn render_volume_slider(volume_slider: &Entity<Slider>, cx: &App) -> impl In
             "volume",
             cx,
             move |_cx| {
-                services_dec.dispatch(AudioCommand::AdjustSinkVolume(-5));
+                services_dec.dispatch(AudioCommand::AdjustSinkVolume(-step));
             },
             move |_cx| {
-                services_inc.dispatch(AudioCommand::AdjustSinkVolume(5));
+                services_inc.dispatch(AudioCommand::AdjustSinkVolume(step));
             },
         ))
+        // Device picker
+        .child(render_devices_button(
+            devices_expanded,
+            cx,
+            on_toggle_devices,
+        ))
 }
 
-/// Render the brightness slider row (returns empty if no brightness control available)
-pub fn render_brightness_slider(brightness_slider: &Entity<Slider>, cx: &App) -> impl IntoElement {
+/// Render the button that opens the audio device picker
+fn render_devices_button(
+    expanded: bool,
+    cx: &App,
+    on_click: impl Fn(&mut App) + 'static,
+) -> impl IntoElement {
     let theme = cx.theme();
-    let brightness = AppState::brightness(cx).get();
 
-    if brightness.max == 0 {
-        return div().into_any_element();
-    }
+    // Pre-compute colors for closures
+    let interactive_default = theme.interactive.default;
+    let interactive_hover = theme.interactive.hover;
+    let accent_primary = theme.accent.primary;
+    let text_muted = theme.text.muted;
 
-    let percent = brightness.percentage();
+    div()
+        .id("volume-devices")
+        .w(px(20.))
+        .h(px(20.))
+        .rounded(px(radius::SM))
+        .flex()
+        .items_center()
+        .justify_center()
+        .cursor_pointer()
+        .bg(interactive_default)
+        .hover(move |s| s.bg(interactive_hover))
+        .on_mouse_down(MouseButton::Left, move |_, _, cx| {
+            on_click(cx);
+        })
+        .child(
+            div()
+                .text_size(px(icon_size::SM))
+                .text_color(if expanded { accent_primary } else { text_muted })
+                .child(if expanded {
+                    icons::CHEVRON_UP
+                } else {
+                    icons::CHEVRON_DOWN
+                }),
+        )
+        .tooltip(control_center_tooltip("Audio devices"))
+}
 
-    let icon = if percent < 33 {
-        icons::BRIGHTNESS_LOW
-    } else if percent < 66 {
-        icons::BRIGHTNESS
-    } else {
-        icons::BRIGHTNESS_HIGH
-    };
+/// Render the brightness slider row of a device
+pub fn render_brightness_slider(
+    device: &BrightnessDevice,
+    brightness_slider: &Entity<Slider>,
+    cx: &App,
+) -> impl IntoElement {
+    let theme = cx.theme();
+    let percent = device.percentage();
+    let icon = icons::brightness_icon(device.kind, percent);
 
     let services_dec = AppState::brightness(cx).clone();
     let services_inc = AppState::brightness(cx).clone();
+    let device_dec = device.id.clone();
+    let device_inc = device.id.clone();
 
     // Pre-compute colors
     let interactive_default = theme.interactive.default;
@@ -86,7 +144,7 @@ pub fn render_brightness_slider(brightness_slider: &Entity<Slider>, cx: &App) ->
         // Icon
         .child(
             div()
-                .id("brightness-icon")
+                .id(format!("brightness-icon-{}", device.id))
                 .w(px(28.))
                 .h(px(28.))
                 .rounded(px(radius::SM))
@@ -99,37 +157,45 @@ pub fn render_brightness_slider(brightness_slider: &Entity<Slider>, cx: &App) ->
                         .text_size(px(icon_size::SM))
                         .text_color(text_primary)
                         .child(icon),
-                ),
+                )
+                .tooltip(control_center_tooltip(device.name.clone())),
         )
         // Slider
         .child(div().flex_1().child(brightness_slider.clone()))
         // Percent
-        .child(render_percentage_label(percent, cx))
+        .child(render_percentage_label(percent.into(), cx))
         // +/- buttons
         .child(render_adjustment_buttons(
-            "brightness",
+            &format!("brightness-{}", device.id),
             cx,
             move |cx| {
                 let s = services_dec.clone();
+                let command = BrightnessCommand::Decrease {
+                    device: device_dec.clone(),
+                    percent: 5,
+                };
                 cx.spawn(async move |_| {
-                    let _ = s.dispatch(BrightnessCommand::Decrease(5)).await;
+                    let _ = s.dispatch(command).await;
                 })
                 .detach();
             },
             move |cx| {
                 let s = services_inc.clone();
+                let command = BrightnessCommand::Increase {
+                    device: device_inc.clone(),
+                    percent: 5,
+                };
                 cx.spawn(async move |_| {
-                    let _ = s.dispatch(BrightnessCommand::Increase(5)).await;
+                    let _ = s.dispatch(command).await;
                 })
                 .detach();
             },
         ))
-        .into_any_element()
 }
 
 /// Render a clickable slider icon
-fn render_slider_icon(
-    id: &'static str,
+pub(super) fn render_slider_icon(
+    id: impl Into<gpui::ElementId>,
     icon: &'static str,
     is_muted: bool,
     cx: &App,
@@ -146,7 +212,7 @@ fn render_slider_icon(
     let icon_color = if is_muted { status_error } else { text_primary };
 
     div()
-        .id(id)
+        .id(id.into())
         .w(px(28.))
         .h(px(28.))
         .rounded(px(radius::SM))
@@ -167,21 +233,31 @@ fn render_slider_icon(
         )
 }
 
-/// Render the percentage label
-fn render_percentage_label(percent: u8, cx: &App) -> impl IntoElement {
+/// Upper bound of a volume slider: the configured limit, or the current
+/// volume if another application set it higher.
+pub(super) fn volume_slider_max(volume: u16, cx: &App) -> f32 {
+    cx.config().audio.max_volume.max(volume) as f32
+}
+
+/// Render the percentage label, highlighting boosted volumes above 100%
+pub(super) fn render_percentage_label(percent: u16, cx: &App) -> impl IntoElement {
     let theme = cx.theme();
 
     div()
         .w(px(32.))
         .text_size(theme.font_sizes.xs)
-        .text_color(theme.text.muted)
+        .text_color(if percent > 100 {
+            theme.status.warning
+        } else {
+            theme.text.muted
+        })
         .text_right()
         .child(format!("{}%", percent))
 }
 
 /// Render +/- adjustment buttons
 fn render_adjustment_buttons(
-    id_prefix: &'static str,
+    id_prefix: &str,
     cx: &App,
     on_decrease: impl Fn(&mut App) + 'static,
     on_increase: impl Fn(&mut App) + 'static,
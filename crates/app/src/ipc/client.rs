@@ -0,0 +1,121 @@
+//! Client side of the IPC socket used by `gpuishell msg`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+
+use super::messages::{IpcCommand, IpcResponse, MSG_USAGE, encode_request};
+use super::service::socket_path;
+
+/// How long to wait for the running shell to answer.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Send a single command to the running shell and wait for its response.
+pub fn send(command: &IpcCommand) -> anyhow::Result<IpcResponse> {
+    let mut reader = connect(command)?;
+    read_response(&mut reader)
+}
+
+/// Send a `subscribe` command and print every event line until the shell
+/// closes the connection.
+fn stream(command: &IpcCommand) -> anyhow::Result<IpcResponse> {
+    let mut reader = connect(command)?;
+    let response = read_response(&mut reader)?;
+    if !response.ok {
+        return Ok(response);
+    }
+
+    // Events may be arbitrarily far apart.
+    reader.get_ref().set_read_timeout(None)?;
+    let stdout = std::io::stdout();
+    for line in reader.lines() {
+        let line = line.context("Failed to read event")?;
+        let mut out = stdout.lock();
+        writeln!(out, "{line}")?;
+        out.flush()?;
+    }
+
+    Ok(response)
+}
+
+fn connect(command: &IpcCommand) -> anyhow::Result<BufReader<UnixStream>> {
+    let path = socket_path();
+    let mut stream = UnixStream::connect(&path)
+        .with_context(|| format!("gpuishell is not running (no socket at {})", path.display()))?;
+    stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+
+    stream
+        .write_all(encode_request(command).as_bytes())
+        .context("Failed to send request")?;
+    stream.flush()?;
+    let _ = stream.shutdown(std::net::Shutdown::Write);
+
+    Ok(BufReader::new(stream))
+}
+
+fn read_response(reader: &mut BufReader<UnixStream>) -> anyhow::Result<IpcResponse> {
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .context("Failed to read response")?;
+    if line.trim().is_empty() {
+        return Err(anyhow!("Connection closed without a response"));
+    }
+
+    serde_json::from_str(&line).context("Failed to parse response")
+}
+
+/// Run `gpuishell msg <command> [args]`, print the JSON reply and return
+/// the process exit code.
+pub fn run_msg(args: &[String]) -> i32 {
+    let command = match IpcCommand::from_args(args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("Error: {err}\n\n{MSG_USAGE}");
+            return 2;
+        }
+    };
+
+    // A subscription prints its events instead of the acknowledgement.
+    if matches!(command, IpcCommand::Subscribe { .. }) {
+        return match stream(&command) {
+            Ok(response) if response.ok => 0,
+            Ok(response) => {
+                eprintln!("Error: {}", response.error.unwrap_or_default());
+                1
+            }
+            Err(err) => {
+                eprintln!("Error: {err:#}");
+                1
+            }
+        };
+    }
+
+    match send(&command) {
+        Ok(response) => {
+            println!("{}", serde_json::to_string(&response).unwrap_or_default());
+            if response.ok { 0 } else { 1 }
+        }
+        Err(err) => {
+            eprintln!("Error: {err:#}");
+            1
+        }
+    }
+}
+
+/// Run `gpuishell launcher`: toggle the launcher of the running shell.
+pub fn run_launcher(input: Option<String>) -> i32 {
+    match send(&IpcCommand::LauncherToggle { input }) {
+        Ok(response) if response.ok => 0,
+        Ok(response) => {
+            eprintln!("Error: {}", response.error.unwrap_or_default());
+            1
+        }
+        Err(err) => {
+            eprintln!("Error: {err:#}");
+            1
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Desktop events streamed to `subscribe` connections.
+//!
+//! Service signals are watched on the tokio runtime and turned into
+//! [`IpcEvent`]s, which are fanned out to every subscriber through a
+//! broadcast channel. The latest state event of each kind is kept so new
+//! subscribers start from a full snapshot.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use futures_signals::signal::SignalExt;
+use serde::{Deserialize, Serialize};
+use services::{
+    AudioData, AudioSubscriber, BatteryState, CompositorState, CompositorSubscriber,
+    NotificationData, NotificationSubscriber, UPowerData, UPowerSubscriber,
+};
+use tokio::sync::broadcast;
+
+/// How many events a slow subscriber may fall behind before skipping.
+const EVENT_BUFFER: usize = 64;
+
+/// Kinds of events a client can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventKind {
+    Workspaces,
+    Volume,
+    Notification,
+    Battery,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 4] = [
+        EventKind::Workspaces,
+        EventKind::Volume,
+        EventKind::Notification,
+        EventKind::Battery,
+    ];
+
+    /// Parse an event kind from a `gpuishell msg subscribe` argument.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "workspaces" => Some(Self::Workspaces),
+            "volume" => Some(Self::Volume),
+            "notification" | "notifications" => Some(Self::Notification),
+            "battery" => Some(Self::Battery),
+            _ => None,
+        }
+    }
+}
+
+/// A workspace as reported in [`IpcEvent::Workspaces`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkspaceInfo {
+    pub id: i32,
+    pub name: String,
+    pub monitor: String,
+    pub windows: u16,
+    pub special: bool,
+}
+
+/// An event sent to subscribers, one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum IpcEvent {
+    /// Workspace list or focus changed.
+    Workspaces {
+        active: Option<i32>,
+        workspaces: Vec<WorkspaceInfo>,
+    },
+    /// Output or input volume/mute changed.
+    Volume {
+        sink_volume: u16,
+        sink_muted: bool,
+        source_volume: u16,
+        source_muted: bool,
+    },
+    /// A new notification arrived.
+    Notification {
+        id: u32,
+        app_name: String,
+        summary: String,
+        body: String,
+        urgency: u8,
+    },
+    /// Battery level or charging state changed.
+    Battery {
+        percentage: u8,
+        state: BatteryState,
+        on_battery: bool,
+    },
+}
+
+impl IpcEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            IpcEvent::Workspaces { .. } => EventKind::Workspaces,
+            IpcEvent::Volume { .. } => EventKind::Volume,
+            IpcEvent::Notification { .. } => EventKind::Notification,
+            IpcEvent::Battery { .. } => EventKind::Battery,
+        }
+    }
+
+    fn workspaces(state: &CompositorState) -> Self {
+        IpcEvent::Workspaces {
+            active: state.active_workspace_id,
+            workspaces: state
+                .workspaces
+                .iter()
+                .map(|ws| WorkspaceInfo {
+                    id: ws.id,
+                    name: ws.name.clone(),
+                    monitor: ws.monitor.clone(),
+                    windows: ws.windows,
+                    special: ws.is_special,
+                })
+                .collect(),
+        }
+    }
+
+    fn volume(data: &AudioData) -> Self {
+        IpcEvent::Volume {
+            sink_volume: data.sink_volume,
+            sink_muted: data.sink_muted,
+            source_volume: data.source_volume,
+            source_muted: data.source_muted,
+        }
+    }
+
+    fn battery(data: &UPowerData) -> Option<Self> {
+        let battery = data.battery.as_ref()?;
+        Some(IpcEvent::Battery {
+            percentage: battery.percentage,
+            state: battery.state,
+            on_battery: data.on_battery,
+        })
+    }
+}
+
+/// Fan-out of desktop events to socket subscribers.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<IpcEvent>,
+    latest: Arc<Mutex<HashMap<EventKind, IpcEvent>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            sender,
+            latest: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Subscribe to future events, returning the current state snapshot too.
+    pub fn subscribe(&self) -> (Vec<IpcEvent>, broadcast::Receiver<IpcEvent>) {
+        let latest = self.latest.lock().unwrap();
+        let receiver = self.sender.subscribe();
+        let snapshot = EventKind::ALL
+            .iter()
+            .filter_map(|kind| latest.get(kind).cloned())
+            .collect();
+        (snapshot, receiver)
+    }
+
+    /// Publish an event, skipping state events that did not change.
+    ///
+    /// The snapshot lock is held while sending, so a concurrent
+    /// [`subscribe`](Self::subscribe) sees each event either in its
+    /// snapshot or on its receiver, never both or neither.
+    fn publish(&self, event: IpcEvent) {
+        let kind = event.kind();
+        let mut latest = self.latest.lock().unwrap();
+        if kind != EventKind::Notification {
+            if latest.get(&kind) == Some(&event) {
+                return;
+            }
+            latest.insert(kind, event.clone());
+        }
+        // No receivers is not an error: nobody is subscribed yet.
+        let _ = self.sender.send(event);
+    }
+
+    /// Start watching service signals. Must be called within a tokio runtime.
+    pub fn watch_services(
+        &self,
+        compositor: CompositorSubscriber,
+        audio: AudioSubscriber,
+        notification: NotificationSubscriber,
+        upower: UPowerSubscriber,
+    ) {
+        let bus = self.clone();
+        tokio::spawn(compositor.subscribe().for_each(move |state| {
+            bus.publish(IpcEvent::workspaces(&state));
+            async {}
+        }));
+
+        let bus = self.clone();
+        tokio::spawn(audio.subscribe().for_each(move |data| {
+            bus.publish(IpcEvent::volume(&data));
+            async {}
+        }));
+
+        let bus = self.clone();
+        tokio::spawn(upower.subscribe().for_each(move |data| {
+            if let Some(event) = IpcEvent::battery(&data) {
+                bus.publish(event);
+            }
+            async {}
+        }));
+
+        // Seed with existing notifications so only new ones are reported.
+        let bus = self.clone();
+        let mut seen: HashSet<u32> = notification
+            .get()
+            .notifications
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        tokio::spawn(notification.subscribe().for_each(move |data| {
+            publish_new_notifications(&bus, &data, &mut seen);
+            async {}
+        }));
+    }
+}
+
+fn publish_new_notifications(bus: &EventBus, data: &NotificationData, seen: &mut HashSet<u32>) {
+    // Oldest first so subscribers see them in arrival order.
+    for notification in data.notifications.iter().rev() {
+        if seen.insert(notification.id) {
+            bus.publish(IpcEvent::Notification {
+                id: notification.id,
+                app_name: notification.app_name.clone(),
+                summary: notification.summary.clone(),
+                body: notification.body.clone(),
+                urgency: notification.urgency,
+            });
+        }
+    }
+    seen.retain(|id| data.notifications.iter().any(|n| n.id == *id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume(level: u16) -> IpcEvent {
+        IpcEvent::Volume {
+            sink_volume: level,
+            sink_muted: false,
+            source_volume: 0,
+            source_muted: false,
+        }
+    }
+
+    #[test]
+    fn test_unchanged_state_is_skipped() {
+        let bus = EventBus::new();
+        let (_, mut receiver) = bus.subscribe();
+        bus.publish(volume(50));
+        bus.publish(volume(50));
+        bus.publish(volume(60));
+
+        assert_eq!(receiver.try_recv().unwrap(), volume(50));
+        assert_eq!(receiver.try_recv().unwrap(), volume(60));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_snapshot_and_stream_do_not_overlap() {
+        let bus = EventBus::new();
+        bus.publish(volume(50));
+        let (snapshot, mut receiver) = bus.subscribe();
+        bus.publish(volume(60));
+
+        assert_eq!(snapshot, vec![volume(50)]);
+        assert_eq!(receiver.try_recv().unwrap(), volume(60));
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Execution of IPC commands on the GPUI thread.
+
+use gpui::App;
+use serde_json::json;
+use services::{AudioCommand, BrightnessCommand, NotificationCommand};
+use ui::Theme;
+
+use crate::config::{ActiveConfig, Config};
+use crate::launcher::modules::theme::find_scheme;
+use crate::state::AppState;
+use crate::{control_center, launcher, notification};
+
+use super::messages::{IpcCommand, IpcMessage, IpcResponse, StateTarget};
+
+pub(super) fn handle_message(message: IpcMessage, cx: &mut App) {
+    let IpcMessage { id, command, reply } = message;
+    tracing::info!("Processing IPC request: id={}, command={:?}", id, command);
+
+    let response = match command {
+        IpcCommand::Ping => {
+            IpcResponse::with_data(id, json!({ "version": env!("CARGO_PKG_VERSION") }))
+        }
+        IpcCommand::LauncherToggle { input } => {
+            launcher::toggle(input, cx);
+            IpcResponse::ok(id)
+        }
+        IpcCommand::ControlCenterToggle => {
+            control_center::toggle(cx);
+            IpcResponse::ok(id)
+        }
+        IpcCommand::NotificationCenterToggle => {
+            notification::toggle_center(cx);
+            IpcResponse::ok(id)
+        }
+        IpcCommand::Dnd { enabled } => {
+            let subscriber = AppState::notification(cx).clone();
+            let enabled = enabled.unwrap_or(!subscriber.get().dnd);
+            notification::dispatch_notification_command(
+                subscriber,
+                NotificationCommand::SetDnd(enabled),
+            );
+            IpcResponse::with_data(id, json!({ "dnd": enabled }))
+        }
+        IpcCommand::VolumeSet { percent } | IpcCommand::MicSet { percent }
+            if percent > cx.config().audio.max_volume =>
+        {
+            IpcResponse::error(
+                id,
+                format!(
+                    "{percent}% is above the volume limit of {}% (audio.max_volume)",
+                    cx.config().audio.max_volume
+                ),
+            )
+        }
+        IpcCommand::VolumeSet { percent } => audio(id, AudioCommand::SetSinkVolume(percent), cx),
+        IpcCommand::VolumeAdjust { delta } => audio(id, AudioCommand::AdjustSinkVolume(delta), cx),
+        IpcCommand::VolumeStep { steps } => {
+            let delta = steps.saturating_mul(cx.config().audio.step_delta());
+            audio(id, AudioCommand::AdjustSinkVolume(delta), cx)
+        }
+        IpcCommand::VolumeMuteToggle => audio(id, AudioCommand::ToggleSinkMute, cx),
+        IpcCommand::MicSet { percent } => audio(id, AudioCommand::SetSourceVolume(percent), cx),
+        IpcCommand::MicAdjust { delta } => audio(id, AudioCommand::AdjustSourceVolume(delta), cx),
+        IpcCommand::MicStep { steps } => {
+            let delta = steps.saturating_mul(cx.config().audio.step_delta());
+            audio(id, AudioCommand::AdjustSourceVolume(delta), cx)
+        }
+        IpcCommand::MicMuteToggle => audio(id, AudioCommand::ToggleSourceMute, cx),
+        IpcCommand::BrightnessSet { percent } => {
+            let command = |device| BrightnessCommand::SetPercent { device, percent };
+            return brightness(id, command, reply, cx);
+        }
+        IpcCommand::BrightnessAdjust { delta } => {
+            let percent = delta.unsigned_abs();
+            let command = move |device| {
+                if delta >= 0 {
+                    BrightnessCommand::Increase { device, percent }
+                } else {
+                    BrightnessCommand::Decrease { device, percent }
+                }
+            };
+            return brightness(id, command, reply, cx);
+        }
+        IpcCommand::ThemeSet { name } => match find_scheme(&name) {
+            Some(scheme) => {
+                Theme::set(scheme.theme, cx);
+                if let Err(err) = Config::save_theme(cx) {
+                    tracing::warn!("Failed to persist selected theme: {}", err);
+                }
+                cx.refresh_windows();
+                IpcResponse::with_data(id, json!({ "theme": scheme.name }))
+            }
+            None => IpcResponse::error(id, format!("unknown theme: {name}")),
+        },
+        IpcCommand::ConfigReload => {
+            Config::reload(cx);
+            Config::reload_theme(cx);
+            cx.refresh_windows();
+            IpcResponse::ok(id)
+        }
+        IpcCommand::Get { target } => state(id, target, cx),
+        // Subscriptions are served directly by the socket listener.
+        IpcCommand::Subscribe { .. } => {
+            IpcResponse::error(id, "subscribe is not a one-shot command")
+        }
+    };
+
+    let _ = reply.send(response);
+}
+
+fn state(id: u64, target: StateTarget, cx: &App) -> IpcResponse {
+    let value = match target {
+        StateTarget::Audio => serde_json::to_value(AppState::audio(cx).get()),
+        StateTarget::Network => serde_json::to_value(AppState::network(cx).get()),
+        StateTarget::Battery => serde_json::to_value(AppState::upower(cx).get()),
+        StateTarget::Workspaces => serde_json::to_value(AppState::compositor(cx).get()),
+        StateTarget::Notifications => serde_json::to_value(AppState::notification(cx).get()),
+        StateTarget::Mpris => serde_json::to_value(AppState::mpris(cx).get()),
+    };
+
+    match value {
+        Ok(data) => IpcResponse::with_data(id, data),
+        Err(err) => IpcResponse::error(id, format!("failed to serialize state: {err}")),
+    }
+}
+
+fn audio(id: u64, command: AudioCommand, cx: &App) -> IpcResponse {
+    let audio = AppState::audio(cx);
+    if !audio.status().is_operational() {
+        return IpcResponse::error(id, "audio service unavailable");
+    }
+    audio.dispatch(command);
+    IpcResponse::ok(id)
+}
+
+/// Apply a brightness command to the primary display.
+fn brightness(
+    id: u64,
+    command: impl FnOnce(String) -> BrightnessCommand,
+    reply: tokio::sync::oneshot::Sender<IpcResponse>,
+    cx: &mut App,
+) {
+    let brightness = AppState::brightness(cx).clone();
+    let Some(device) = brightness.get().primary().map(|device| device.id.clone()) else {
+        let _ = reply.send(IpcResponse::error(
+            id,
+            "no display brightness device available",
+        ));
+        return;
+    };
+    let command = command(device);
+
+    cx.spawn(async move |_| {
+        let response = match brightness.dispatch(command).await {
+            Ok(()) => IpcResponse::ok(id),
+            Err(err) => IpcResponse::error(id, err.to_string()),
+        };
+        let _ = reply.send(response);
+    })
+    .detach();
+}
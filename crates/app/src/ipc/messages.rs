@@ -1,64 +1,500 @@
-use gpui::App;
+//! Wire protocol for the IPC socket.
+//!
+//! Every connection carries one JSON request line and receives one JSON
+//! response line. Commands are tagged by a kebab-case `command` field:
+//!
+//! ```json
+//! {"version":1,"command":"volume-set","percent":40}
+//! {"version":1,"id":7,"ok":true}
+//! ```
+//!
+//! A `subscribe` request keeps the connection open: after the response
+//! line, the shell writes one [`IpcEvent`](super::events::IpcEvent) per line
+//! until the client disconnects.
 
-use crate::args::Args;
-use crate::launcher;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::oneshot;
 
-const LAUNCHER_PREFIX: &str = "ipc:launcher:";
+use super::events::{EventKind, IpcEvent};
 
-#[derive(Debug, Clone)]
+/// Current protocol version. Bump when requests or responses change shape.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Step (in percent) for `brightness up/down` without an explicit step.
+const BRIGHTNESS_STEP: i8 = 5;
+
+/// A command received over the socket, paired with its reply channel.
+#[derive(Debug)]
 pub struct IpcMessage {
     pub id: u64,
     pub command: IpcCommand,
+    pub reply: oneshot::Sender<IpcResponse>,
 }
 
-#[derive(Debug, Clone)]
+/// Commands understood by the running shell.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
 pub enum IpcCommand {
-    LauncherToggle { input: Option<String> },
+    /// Check that the shell is running.
+    Ping,
+    /// Toggle the launcher, optionally prefilled with input.
+    LauncherToggle {
+        #[serde(default)]
+        input: Option<String>,
+    },
+    /// Toggle the control center panel.
+    ControlCenterToggle,
+    /// Toggle the notification center panel.
+    NotificationCenterToggle,
+    /// Set do-not-disturb, or toggle it when `enabled` is omitted.
+    Dnd {
+        #[serde(default)]
+        enabled: Option<bool>,
+    },
+    /// Set output volume as a percentage, up to the configured limit.
+    VolumeSet { percent: u16 },
+    /// Adjust output volume by a signed percentage.
+    VolumeAdjust { delta: i8 },
+    /// Adjust output volume by a signed number of `audio.step`s.
+    VolumeStep { steps: i8 },
+    /// Toggle output mute.
+    VolumeMuteToggle,
+    /// Set input volume as a percentage, up to the configured limit.
+    MicSet { percent: u16 },
+    /// Adjust input volume by a signed percentage.
+    MicAdjust { delta: i8 },
+    /// Adjust input volume by a signed number of `audio.step`s.
+    MicStep { steps: i8 },
+    /// Toggle input mute.
+    MicMuteToggle,
+    /// Set display brightness as a percentage.
+    BrightnessSet { percent: u8 },
+    /// Adjust display brightness by a signed percentage.
+    BrightnessAdjust { delta: i8 },
+    /// Apply a theme scheme by name.
+    ThemeSet { name: String },
+    /// Reload `config.toml` and `theme.toml` from disk.
+    ConfigReload,
+    /// Return a snapshot of a service's current state.
+    Get { target: StateTarget },
+    /// Stream events of the given kinds (all kinds when empty).
+    Subscribe {
+        #[serde(default)]
+        events: Vec<EventKind>,
+    },
 }
 
-pub fn command_for_secondary(args: &Args) -> IpcCommand {
-    IpcCommand::LauncherToggle {
-        input: args.input.clone(),
+/// Services whose state can be queried with [`IpcCommand::Get`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StateTarget {
+    Audio,
+    Network,
+    Battery,
+    Workspaces,
+    Notifications,
+    Mpris,
+}
+
+impl StateTarget {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "audio" => Some(Self::Audio),
+            "network" => Some(Self::Network),
+            "battery" => Some(Self::Battery),
+            "workspaces" => Some(Self::Workspaces),
+            "notifications" => Some(Self::Notifications),
+            "mpris" => Some(Self::Mpris),
+            _ => None,
+        }
     }
 }
 
-pub fn encode_command(command: &IpcCommand) -> String {
-    match command {
-        IpcCommand::LauncherToggle { input } => {
-            format!("{}{}", LAUNCHER_PREFIX, input.as_deref().unwrap_or(""))
+/// A request as sent over the socket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcRequest {
+    pub version: u32,
+    #[serde(flatten)]
+    pub command: IpcCommand,
+}
+
+/// A response as sent back over the socket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcResponse {
+    pub version: u32,
+    pub id: u64,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl IpcResponse {
+    /// Successful response without a payload.
+    pub fn ok(id: u64) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            id,
+            ok: true,
+            data: None,
+            error: None,
+        }
+    }
+
+    /// Successful response carrying a JSON payload.
+    pub fn with_data(id: u64, data: Value) -> Self {
+        Self {
+            data: Some(data),
+            ..Self::ok(id)
+        }
+    }
+
+    /// Failed response with an error message.
+    pub fn error(id: u64, error: impl Into<String>) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            id,
+            ok: false,
+            data: None,
+            error: Some(error.into()),
         }
     }
 }
 
-pub fn decode_command(payload: &str) -> IpcCommand {
-    if let Some(rest) = payload.strip_prefix(LAUNCHER_PREFIX) {
-        return IpcCommand::LauncherToggle {
-            input: if rest.is_empty() {
-                None
-            } else {
-                Some(rest.to_string())
+impl IpcCommand {
+    /// Parse a command from `gpuishell msg` arguments.
+    ///
+    /// Examples: `volume set 40`, `volume up`, `brightness down 10`,
+    /// `dnd on`, `theme Gruvbox Dark`, `config reload`.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let words: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        match words.as_slice() {
+            [] => Err("missing command".to_string()),
+            ["ping"] => Ok(Self::Ping),
+            ["launcher"] => Ok(Self::LauncherToggle { input: None }),
+            ["launcher", rest @ ..] => Ok(Self::LauncherToggle {
+                input: Some(rest.join(" ")),
+            }),
+            ["control-center"] | ["control-center", "toggle"] => Ok(Self::ControlCenterToggle),
+            ["notifications"] | ["notifications", "toggle"] => Ok(Self::NotificationCenterToggle),
+            ["dnd"] | ["dnd", "toggle"] => Ok(Self::Dnd { enabled: None }),
+            ["dnd", "on"] => Ok(Self::Dnd {
+                enabled: Some(true),
+            }),
+            ["dnd", "off"] => Ok(Self::Dnd {
+                enabled: Some(false),
+            }),
+            ["volume", rest @ ..] => match parse_level(rest, u16::MAX)? {
+                Level::Set(percent) => Ok(Self::VolumeSet { percent }),
+                Level::Adjust(delta) => Ok(Self::VolumeAdjust { delta }),
+                Level::Step(steps) => Ok(Self::VolumeStep { steps }),
+                Level::MuteToggle => Ok(Self::VolumeMuteToggle),
+            },
+            ["mic", rest @ ..] => match parse_level(rest, u16::MAX)? {
+                Level::Set(percent) => Ok(Self::MicSet { percent }),
+                Level::Adjust(delta) => Ok(Self::MicAdjust { delta }),
+                Level::Step(steps) => Ok(Self::MicStep { steps }),
+                Level::MuteToggle => Ok(Self::MicMuteToggle),
+            },
+            ["brightness", rest @ ..] => match parse_level(rest, 100)? {
+                Level::Set(percent) => Ok(Self::BrightnessSet {
+                    percent: percent as u8,
+                }),
+                Level::Adjust(delta) => Ok(Self::BrightnessAdjust { delta }),
+                Level::Step(steps) => Ok(Self::BrightnessAdjust {
+                    delta: steps * BRIGHTNESS_STEP,
+                }),
+                Level::MuteToggle => Err("brightness cannot be muted".to_string()),
             },
-        };
+            ["theme", name @ ..] if !name.is_empty() => Ok(Self::ThemeSet {
+                name: name.join(" "),
+            }),
+            ["config", "reload"] => Ok(Self::ConfigReload),
+            ["get", target] => StateTarget::parse(target)
+                .map(|target| Self::Get { target })
+                .ok_or_else(|| format!("unknown state: {target}")),
+            ["subscribe", kinds @ ..] => {
+                let events = kinds
+                    .iter()
+                    .map(|kind| {
+                        EventKind::parse(kind).ok_or_else(|| format!("unknown event: {kind}"))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Self::Subscribe { events })
+            }
+            _ => Err(format!("unknown command: {}", words.join(" "))),
+        }
     }
+}
+
+/// Usage text for `gpuishell msg`.
+pub const MSG_USAGE: &str = "\
+Usage: gpuishell msg <command> [args]
+
+Commands:
+  ping
+  launcher [input]
+  control-center [toggle]
+  notifications [toggle]
+  dnd [on|off|toggle]
+  volume set <percent> | up [step] | down [step] | mute
+  mic set <percent> | up [step] | down [step] | mute
+  brightness set <percent> | up [step] | down [step]
+  theme <name>
+  config reload
+  get audio|network|battery|workspaces|notifications|mpris
+  subscribe [workspaces|volume|notifications|battery ...]";
+
+#[derive(Debug, PartialEq)]
+enum Level {
+    Set(u16),
+    Adjust(i8),
+    /// `up` or `down` without a step, resolved by the shell.
+    Step(i8),
+    MuteToggle,
+}
 
-    IpcCommand::LauncherToggle {
-        input: if payload.is_empty() {
-            None
-        } else {
-            Some(payload.to_string())
-        },
+/// Parse `set/up/down/mute` arguments; `set` accepts up to `max_percent`.
+fn parse_level(words: &[&str], max_percent: u16) -> Result<Level, String> {
+    match words {
+        ["set", value] => parse_percent(value, max_percent).map(Level::Set),
+        ["up"] => Ok(Level::Step(1)),
+        ["down"] => Ok(Level::Step(-1)),
+        ["up", step] => parse_step(step).map(Level::Adjust),
+        ["down", step] => parse_step(step).map(|step| Level::Adjust(-step)),
+        ["mute"] => Ok(Level::MuteToggle),
+        _ => Err(format!(
+            "expected set/up/down/mute, got: {}",
+            words.join(" ")
+        )),
     }
 }
 
-pub(super) fn handle_message(message: IpcMessage, cx: &mut App) {
-    match message.command {
-        IpcCommand::LauncherToggle { input } => {
-            tracing::info!(
-                "Processing launcher request: id={}, input={:?}",
-                message.id,
-                input
-            );
-            launcher::toggle(input, cx);
-        }
+fn parse_percent(value: &str, max: u16) -> Result<u16, String> {
+    let value = value.trim_end_matches('%');
+    match value.parse::<u16>() {
+        Ok(percent) if percent <= max => Ok(percent),
+        _ => Err(format!("invalid percentage: {value}")),
+    }
+}
+
+fn parse_step(value: &str) -> Result<i8, String> {
+    let value = value.trim_end_matches('%');
+    match value.parse::<i8>() {
+        Ok(step) if step > 0 => Ok(step),
+        _ => Err(format!("invalid step: {value}")),
+    }
+}
+
+/// Encode a command as a single request line.
+pub fn encode_request(command: &IpcCommand) -> String {
+    let request = IpcRequest {
+        version: PROTOCOL_VERSION,
+        command: command.clone(),
+    };
+    let mut line = serde_json::to_string(&request).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+/// Decode a request line into a command.
+pub fn decode_request(payload: &str) -> Result<IpcCommand, String> {
+    let payload = payload.trim();
+    if payload.is_empty() {
+        return Err("empty request".to_string());
+    }
+
+    let request: IpcRequest =
+        serde_json::from_str(payload).map_err(|e| format!("invalid request: {e}"))?;
+    if request.version != PROTOCOL_VERSION {
+        return Err(format!(
+            "unsupported protocol version {} (expected {})",
+            request.version, PROTOCOL_VERSION
+        ));
+    }
+
+    Ok(request.command)
+}
+
+/// Encode a response as a single line.
+pub fn encode_response(response: &IpcResponse) -> String {
+    let mut line = serde_json::to_string(response).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+/// Encode an event as a single line.
+pub fn encode_event(event: &IpcEvent) -> String {
+    let mut line = serde_json::to_string(event).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<IpcCommand, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        IpcCommand::from_args(&args)
+    }
+
+    #[test]
+    fn test_simple_commands() {
+        assert_eq!(parse("ping"), Ok(IpcCommand::Ping));
+        assert_eq!(parse("control-center"), Ok(IpcCommand::ControlCenterToggle));
+        assert_eq!(
+            parse("notifications toggle"),
+            Ok(IpcCommand::NotificationCenterToggle)
+        );
+        assert_eq!(
+            parse("dnd on"),
+            Ok(IpcCommand::Dnd {
+                enabled: Some(true)
+            })
+        );
+        assert_eq!(parse("dnd"), Ok(IpcCommand::Dnd { enabled: None }));
+        assert_eq!(parse("config reload"), Ok(IpcCommand::ConfigReload));
+    }
+
+    #[test]
+    fn test_joined_arguments() {
+        assert_eq!(
+            parse("launcher ;win fire"),
+            Ok(IpcCommand::LauncherToggle {
+                input: Some(";win fire".to_string())
+            })
+        );
+        assert_eq!(
+            parse("theme Gruvbox Dark"),
+            Ok(IpcCommand::ThemeSet {
+                name: "Gruvbox Dark".to_string()
+            })
+        );
+        assert!(parse("theme").is_err());
+    }
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(
+            parse("volume set 40%"),
+            Ok(IpcCommand::VolumeSet { percent: 40 })
+        );
+        assert_eq!(
+            parse("volume set 150"),
+            Ok(IpcCommand::VolumeSet { percent: 150 })
+        );
+        assert_eq!(parse("volume up"), Ok(IpcCommand::VolumeStep { steps: 1 }));
+        assert_eq!(parse("mic down"), Ok(IpcCommand::MicStep { steps: -1 }));
+        assert_eq!(
+            parse("mic down 10"),
+            Ok(IpcCommand::MicAdjust { delta: -10 })
+        );
+        assert_eq!(parse("mic mute"), Ok(IpcCommand::MicMuteToggle));
+        assert_eq!(
+            parse("brightness set 30"),
+            Ok(IpcCommand::BrightnessSet { percent: 30 })
+        );
+        assert_eq!(
+            parse("brightness down"),
+            Ok(IpcCommand::BrightnessAdjust {
+                delta: -BRIGHTNESS_STEP
+            })
+        );
+        assert!(parse("brightness set 101").is_err());
+        assert!(parse("brightness mute").is_err());
+    }
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level(&["set", "100"], 100), Ok(Level::Set(100)));
+        assert_eq!(parse_level(&["up"], 100), Ok(Level::Step(1)));
+        assert_eq!(parse_level(&["down", "3%"], 100), Ok(Level::Adjust(-3)));
+        assert_eq!(parse_level(&["mute"], 100), Ok(Level::MuteToggle));
+        assert!(parse_level(&["set", "-1"], 100).is_err());
+        assert!(parse_level(&["up", "0"], 100).is_err());
+        assert!(parse_level(&["up", "200"], 100).is_err());
+        assert!(parse_level(&["louder"], 100).is_err());
+        assert!(parse_level(&[], 100).is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(
+            parse("get audio"),
+            Ok(IpcCommand::Get {
+                target: StateTarget::Audio
+            })
+        );
+        assert_eq!(
+            parse("get weather"),
+            Err("unknown state: weather".to_string())
+        );
+        assert!(parse("get").is_err());
+    }
+
+    #[test]
+    fn test_subscribe() {
+        assert_eq!(
+            parse("subscribe volume notifications"),
+            Ok(IpcCommand::Subscribe {
+                events: vec![EventKind::Volume, EventKind::Notification]
+            })
+        );
+        assert_eq!(
+            parse("subscribe"),
+            Ok(IpcCommand::Subscribe { events: vec![] })
+        );
+        assert!(parse("subscribe weather").is_err());
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        assert_eq!(parse(""), Err("missing command".to_string()));
+        assert_eq!(
+            parse("reboot now"),
+            Err("unknown command: reboot now".to_string())
+        );
+    }
+
+    #[test]
+    fn test_request_roundtrip() {
+        let command = IpcCommand::VolumeSet { percent: 40 };
+        assert_eq!(decode_request(&encode_request(&command)), Ok(command));
+        assert_eq!(
+            decode_request(r#"{"version":1,"command":"dnd","enabled":false}"#),
+            Ok(IpcCommand::Dnd {
+                enabled: Some(false)
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_request_errors() {
+        assert_eq!(decode_request(" \n"), Err("empty request".to_string()));
+        assert_eq!(
+            decode_request(r#"{"version":2,"command":"ping"}"#),
+            Err("unsupported protocol version 2 (expected 1)".to_string())
+        );
+        assert!(decode_request(r#"{"version":1,"command":"reboot"}"#).is_err());
+        assert!(decode_request("ping").is_err());
+    }
+
+    #[test]
+    fn test_response_roundtrip() {
+        assert_eq!(
+            encode_response(&IpcResponse::ok(3)),
+            "{\"version\":1,\"id\":3,\"ok\":true}\n"
+        );
+
+        let line = encode_response(&IpcResponse::error(7, "no audio"));
+        let response: IpcResponse = serde_json::from_str(&line).unwrap();
+        assert_eq!(response.id, 7);
+        assert!(!response.ok);
+        assert_eq!(response.data, None);
+        assert_eq!(response.error.as_deref(), Some("no audio"));
     }
 }
@@ -1,13 +1,27 @@
+mod client;
+mod events;
+mod handler;
 mod messages;
 mod service;
 
 use gpui::App;
 
+use crate::state::AppState;
+use events::EventBus;
+
+pub use client::{run_launcher, run_msg};
 pub use service::IpcSubscriber;
 
 impl IpcSubscriber {
     pub fn start(mut self, cx: &mut App) {
-        let mut receiver = self.start_listener();
+        let events = EventBus::new();
+        events.watch_services(
+            AppState::compositor(cx).clone(),
+            AppState::audio(cx).clone(),
+            AppState::notification(cx).clone(),
+            AppState::upower(cx).clone(),
+        );
+        let mut receiver = self.start_listener(events);
 
         cx.spawn(async move |cx| {
             // Keep the subscriber alive so the socket file isn't removed.
@@ -17,7 +31,7 @@ impl IpcSubscriber {
 
             while let Some(message) = receiver.recv().await {
                 cx.update(move |cx| {
-                    messages::handle_message(message, cx);
+                    handler::handle_message(message, cx);
                 });
             }
 
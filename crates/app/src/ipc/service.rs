@@ -1,29 +1,63 @@
+use std::fs::{File, TryLockError};
 use std::io::Write;
 use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use tokio::net::UnixListener as TokioUnixListener;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
-use crate::args::Args;
-
-use super::messages::{IpcMessage, command_for_secondary, decode_command, encode_command};
+use super::events::{EventBus, EventKind};
+use super::messages::{
+    IpcCommand, IpcMessage, IpcResponse, decode_request, encode_event, encode_request,
+    encode_response,
+};
 
 pub type IpcReceiver = mpsc::UnboundedReceiver<IpcMessage>;
 
+/// How long a connection may take to send its request line.
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long to wait for the GPUI thread to answer a request.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often to try reaching an instance that holds the lock but may not
+/// be listening yet, and how long to wait between attempts.
+const CONNECT_ATTEMPTS: u32 = 10;
+const CONNECT_RETRY_DELAY: Duration = Duration::from_millis(50);
+
 enum AcquireResult {
     Primary(IpcSubscriber),
     Secondary,
     Error(String),
 }
 
+/// Why [`IpcSubscriber::bind`] failed.
+#[derive(Debug)]
+pub enum BindError {
+    /// Another instance holds the single-instance lock.
+    AlreadyRunning,
+    /// The lock or socket could not be set up.
+    Failed(String),
+}
+
+impl std::fmt::Display for BindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BindError::AlreadyRunning => write!(f, "gpuishell is already running"),
+            BindError::Failed(err) => write!(f, "{}", err),
+        }
+    }
+}
+
 /// Subscriber for IPC messages from other instances.
 pub struct IpcSubscriber {
     /// The bound Unix listener (not yet accepting connections)
     listener: Option<TokioUnixListener>,
     /// Socket path for cleanup
     socket_path: PathBuf,
+    /// Exclusive lock held while this process is the primary instance
+    _lock: File,
 }
 
 impl std::fmt::Debug for IpcSubscriber {
@@ -42,16 +76,15 @@ impl IpcSubscriber {
     ///
     /// This performs one retry without initial input when the first acquire
     /// attempt fails with an error.
-    pub fn init(args: &Args) -> Option<IpcSubscriber> {
-        match Self::acquire(args) {
+    pub fn init(input: Option<String>) -> Option<IpcSubscriber> {
+        match Self::acquire(input) {
             AcquireResult::Primary(subscriber) => Some(subscriber),
             AcquireResult::Secondary => None,
             AcquireResult::Error(err) => {
                 error!("IPC service error: {}", err);
                 warn!("Retrying IPC acquire without initial input");
 
-                let retry_args = Args { input: None };
-                match Self::acquire(&retry_args) {
+                match Self::acquire(None) {
                     AcquireResult::Primary(subscriber) => Some(subscriber),
                     AcquireResult::Secondary => None,
                     AcquireResult::Error(retry_err) => {
@@ -63,43 +96,35 @@ impl IpcSubscriber {
         }
     }
 
-    /// Try to become the primary instance or signal an existing one.
-    ///
-    /// This function uses synchronous I/O for the secondary path to minimize
-    /// startup latency - no async runtime needed to signal an existing instance.
+    /// Become the primary instance: take the single-instance lock, then
+    /// bind the socket.
     ///
-    /// When becoming the primary instance, a tokio runtime must be active.
+    /// The lock is an exclusive `flock` on a file next to the socket, held
+    /// for the lifetime of the subscriber. Taking it is atomic, so two
+    /// processes starting at once can never both become primary, and a
+    /// leftover socket can only be stale once the lock is ours.
     ///
-    /// If no other instance is running, prepares a Unix socket listener
-    /// and returns `AcquireResult::Primary` with the subscriber.
-    ///
-    /// If another instance is already running, sends a command
-    /// to it synchronously and returns `AcquireResult::Secondary`.
-    fn acquire(args: &Args) -> AcquireResult {
+    /// A tokio runtime must be active.
+    pub fn bind() -> Result<IpcSubscriber, BindError> {
         let path = socket_path();
-        let command = command_for_secondary(args);
-
-        // Try to connect to existing instance (fast, synchronous path)
-        if let Ok(mut stream) = UnixStream::connect(&path) {
-            // Set a short timeout for the write
-            let _ = stream.set_write_timeout(Some(std::time::Duration::from_millis(100)));
-
-            let payload = encode_command(&command);
-            if let Err(e) = stream.write_all(payload.as_bytes()) {
-                error!("Failed to send message to existing instance: {}", e);
-                return AcquireResult::Error(format!("Failed to signal existing instance: {}", e));
+        let lock_path = path.with_extension("lock");
+
+        let lock = File::create(&lock_path).map_err(|e| {
+            BindError::Failed(format!("Failed to open {}: {}", lock_path.display(), e))
+        })?;
+        match lock.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Err(BindError::AlreadyRunning),
+            Err(TryLockError::Error(e)) => {
+                return Err(BindError::Failed(format!(
+                    "Failed to lock {}: {}",
+                    lock_path.display(),
+                    e
+                )));
             }
-
-            // Flush and shutdown to signal end of message
-            let _ = stream.flush();
-            let _ = stream.shutdown(std::net::Shutdown::Write);
-
-            info!("Successfully signaled existing instance");
-            return AcquireResult::Secondary;
         }
 
-        // No existing instance, become the primary
-        // Remove stale socket if it exists
+        // Holding the lock, any existing socket belongs to a dead instance
         if path.exists()
             && let Err(e) = std::fs::remove_file(&path)
         {
@@ -107,50 +132,91 @@ impl IpcSubscriber {
         }
 
         // Create the Unix listener
-        let listener = match UnixListener::bind(&path) {
-            Ok(l) => l,
-            Err(e) => {
-                error!("Failed to bind Unix socket: {}", e);
-                return AcquireResult::Error(format!("Failed to create socket: {}", e));
-            }
-        };
+        let listener = UnixListener::bind(&path)
+            .map_err(|e| BindError::Failed(format!("Failed to create socket: {}", e)))?;
 
         // Set non-blocking for tokio compatibility
-        if let Err(e) = listener.set_nonblocking(true) {
-            error!("Failed to set socket non-blocking: {}", e);
-            return AcquireResult::Error(format!("Failed to configure socket: {}", e));
-        }
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| BindError::Failed(format!("Failed to configure socket: {}", e)))?;
 
         // Convert to tokio listener (doesn't require runtime yet)
-        let tokio_listener = match TokioUnixListener::from_std(listener) {
-            Ok(l) => l,
-            Err(e) => {
-                error!("Failed to create tokio listener: {}", e);
-                return AcquireResult::Error(format!("Failed to create async listener: {}", e));
-            }
-        };
+        let tokio_listener = TokioUnixListener::from_std(listener)
+            .map_err(|e| BindError::Failed(format!("Failed to create async listener: {}", e)))?;
 
         info!("Prepared as primary instance, socket at {:?}", path);
-        let subscriber = IpcSubscriber {
+        Ok(IpcSubscriber {
             listener: Some(tokio_listener),
             socket_path: path,
+            _lock: lock,
+        })
+    }
+
+    /// Try to become the primary instance or signal an existing one.
+    ///
+    /// If no other instance holds the lock, binds the socket and returns
+    /// `AcquireResult::Primary` with the subscriber.
+    ///
+    /// If another instance is already running, sends a command to it
+    /// synchronously and returns `AcquireResult::Secondary`. The other
+    /// instance may still be starting up, so connecting is retried briefly.
+    fn acquire(input: Option<String>) -> AcquireResult {
+        match Self::bind() {
+            Ok(subscriber) => return AcquireResult::Primary(subscriber),
+            Err(BindError::AlreadyRunning) => {}
+            Err(BindError::Failed(err)) => return AcquireResult::Error(err),
+        }
+
+        let path = socket_path();
+        let command = IpcCommand::LauncherToggle { input };
+
+        let mut attempt = 1;
+        let mut stream = loop {
+            match UnixStream::connect(&path) {
+                Ok(stream) => break stream,
+                Err(_) if attempt < CONNECT_ATTEMPTS => {
+                    attempt += 1;
+                    std::thread::sleep(CONNECT_RETRY_DELAY);
+                }
+                Err(e) => {
+                    return AcquireResult::Error(format!(
+                        "Failed to connect to existing instance: {}",
+                        e
+                    ));
+                }
+            }
         };
 
-        AcquireResult::Primary(subscriber)
+        // Set a short timeout for the write
+        let _ = stream.set_write_timeout(Some(Duration::from_millis(100)));
+
+        let payload = encode_request(&command);
+        if let Err(e) = stream.write_all(payload.as_bytes()) {
+            error!("Failed to send message to existing instance: {}", e);
+            return AcquireResult::Error(format!("Failed to signal existing instance: {}", e));
+        }
+
+        // Flush and shutdown to signal end of message
+        let _ = stream.flush();
+        let _ = stream.shutdown(std::net::Shutdown::Write);
+
+        info!("Successfully signaled existing instance");
+        AcquireResult::Secondary
     }
 
     /// Start the listener and return a receiver for IPC messages.
     ///
     /// This must be called from within a tokio runtime context.
-    /// Returns a receiver that yields `IpcMessage` items.
-    pub fn start_listener(&mut self) -> IpcReceiver {
+    /// Returns a receiver that yields `IpcMessage` items; `subscribe`
+    /// connections are served from `events` instead.
+    pub fn start_listener(&mut self, events: EventBus) -> IpcReceiver {
         let (sender, receiver) = mpsc::unbounded_channel();
 
         // Take the listener and spawn the accept loop
         if let Some(listener) = self.listener.take() {
             let path_clone = self.socket_path.clone();
             tokio::spawn(async move {
-                accept_loop(listener, sender, path_clone).await;
+                accept_loop(listener, sender, events, path_clone).await;
             });
         }
 
@@ -162,6 +228,7 @@ impl IpcSubscriber {
 async fn accept_loop(
     listener: TokioUnixListener,
     sender: mpsc::UnboundedSender<IpcMessage>,
+    events: EventBus,
     socket_path: PathBuf,
 ) {
     use std::sync::atomic::AtomicU64;
@@ -173,58 +240,132 @@ async fn accept_loop(
         match listener.accept().await {
             Ok((stream, _)) => {
                 let sender = sender.clone();
+                let events = events.clone();
                 tokio::spawn(async move {
-                    handle_connection(stream, sender, &REQUEST_COUNTER).await;
+                    handle_connection(stream, sender, events, &REQUEST_COUNTER).await;
                 });
             }
             Err(e) => {
                 error!("Failed to accept connection: {}", e);
                 // Small delay to prevent tight loop on persistent errors
-                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                tokio::time::sleep(Duration::from_millis(10)).await;
             }
         }
     }
 }
 
-/// Handle a single connection from a secondary instance.
+/// Handle a single connection: read one request line, answer with one response line
+/// (or with an event stream for `subscribe`).
 async fn handle_connection(
     stream: tokio::net::UnixStream,
     sender: mpsc::UnboundedSender<IpcMessage>,
+    events: EventBus,
     counter: &std::sync::atomic::AtomicU64,
 ) {
-    use tokio::io::AsyncReadExt;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
     let mut stream = stream;
-    let mut buffer = Vec::with_capacity(256);
+    let (reader, mut writer) = stream.split();
+    let mut reader = BufReader::new(reader);
+    let mut payload = String::new();
 
     // Read with timeout
-    let read_result = tokio::time::timeout(
-        std::time::Duration::from_millis(100),
-        stream.read_to_end(&mut buffer),
-    )
-    .await;
-
-    let payload = match read_result {
-        Ok(Ok(_)) => String::from_utf8_lossy(&buffer).to_string(),
+    let read_result = tokio::time::timeout(READ_TIMEOUT, reader.read_line(&mut payload)).await;
+    match read_result {
+        Ok(Ok(_)) => {}
         Ok(Err(e)) => {
             debug!("Error reading from socket: {}", e);
-            String::new()
+            payload.clear();
         }
         Err(_) => {
             debug!("Timeout reading from socket");
-            String::new()
+            payload.clear();
         }
-    };
+    }
 
     let request_id = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    let command = decode_command(&payload);
-    let message = IpcMessage {
-        id: request_id,
-        command,
+    let response = match decode_request(&payload) {
+        Ok(IpcCommand::Subscribe { events: kinds }) => {
+            info!("IPC request {} subscribed to events", request_id);
+            stream_events(request_id, &mut writer, &events, &kinds).await;
+            return;
+        }
+        Ok(command) => dispatch(request_id, command, &sender).await,
+        Err(err) => {
+            warn!("Rejected IPC request {}: {}", request_id, err);
+            IpcResponse::error(request_id, err)
+        }
     };
 
+    let line = encode_response(&response);
+    if let Err(e) = writer.write_all(line.as_bytes()).await {
+        debug!("Failed to write IPC response: {}", e);
+    }
+    let _ = writer.shutdown().await;
+}
+
+/// Acknowledge a subscription, then write matching events until the
+/// client disconnects.
+async fn stream_events<W>(id: u64, writer: &mut W, events: &EventBus, kinds: &[EventKind])
+where
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncWriteExt;
+    use tokio::sync::broadcast::error::RecvError;
+
+    let wanted = |kind: EventKind| kinds.is_empty() || kinds.contains(&kind);
+    let (snapshot, mut receiver) = events.subscribe();
+
+    let mut lines = vec![encode_response(&IpcResponse::ok(id))];
+    lines.extend(
+        snapshot
+            .iter()
+            .filter(|event| wanted(event.kind()))
+            .map(encode_event),
+    );
+    for line in lines {
+        if writer.write_all(line.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+
+    loop {
+        let event = match receiver.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("IPC subscriber {} lagged, skipped {} events", id, skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+        if !wanted(event.kind()) {
+            continue;
+        }
+        if let Err(e) = writer.write_all(encode_event(&event).as_bytes()).await {
+            debug!("IPC subscriber {} disconnected: {}", id, e);
+            break;
+        }
+    }
+}
+
+/// Forward a command to the GPUI thread and wait for its response.
+async fn dispatch(
+    id: u64,
+    command: IpcCommand,
+    sender: &mpsc::UnboundedSender<IpcMessage>,
+) -> IpcResponse {
+    let (reply, response) = oneshot::channel();
+    let message = IpcMessage { id, command, reply };
+
     if let Err(e) = sender.send(message) {
         error!("Failed to send request to channel: {}", e);
+        return IpcResponse::error(id, "shell is shutting down");
+    }
+
+    match tokio::time::timeout(REPLY_TIMEOUT, response).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(_)) => IpcResponse::error(id, "request dropped without a response"),
+        Err(_) => IpcResponse::error(id, "timed out waiting for response"),
     }
 }
 
@@ -243,7 +384,7 @@ impl Drop for IpcSubscriber {
 }
 
 /// Get the socket path for IPC.
-fn socket_path() -> PathBuf {
+pub(super) fn socket_path() -> PathBuf {
     // Prefer XDG_RUNTIME_DIR for security (user-only access, tmpfs)
     if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
         PathBuf::from(runtime_dir).join("gpuishell.sock")
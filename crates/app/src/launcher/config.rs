@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::modules::{
     apps::config::AppsConfig, help::config::HelpConfig, services::config::ServicesConfig,
     shell::config::ShellConfig, theme::config::ThemesConfig, wallpaper::config::WallpaperConfig,
-    web::config::WebConfig, workspaces::config::WorkspacesConfig,
+    web::config::WebConfig, windows::config::WindowsConfig, workspaces::config::WorkspacesConfig,
 };
 
 /// Launcher window configuration.
@@ -41,6 +41,7 @@ pub struct ModulesConfig {
     pub shell: ShellConfig,
     pub web: WebConfig,
     pub workspaces: WorkspacesConfig,
+    pub windows: WindowsConfig,
     pub wallpaper: WallpaperConfig,
     pub themes: ThemesConfig,
     pub services: ServicesConfig,
@@ -54,6 +55,7 @@ impl Default for ModulesConfig {
             shell: ShellConfig::default(),
             web: WebConfig::default(),
             workspaces: WorkspacesConfig::default(),
+            windows: WindowsConfig::default(),
             wallpaper: WallpaperConfig::default(),
             themes: ThemesConfig::default(),
             services: ServicesConfig::default(),
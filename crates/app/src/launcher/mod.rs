@@ -5,6 +5,7 @@
 //! - Running shell commands ($ prefix)
 //! - Web search with multiple providers (! prefix with shebangs)
 //! - Switching workspaces (;ws prefix)
+//! - Switching to open windows (;win prefix)
 //! - Viewing help and available commands (? prefix)
 
 pub mod config;
@@ -77,6 +78,14 @@ impl Launcher {
         }
     }
 
+    /// Recreate views from the current launcher config.
+    fn reload_views(&mut self, cx: &App) {
+        let launcher_config = &Config::global(cx).launcher;
+        self.views = all_views(launcher_config);
+        self.help_view = HelpView::new(&launcher_config.modules.help, &self.views);
+        self.selected_index = 0;
+    }
+
     /// Set the search query (used for IPC input).
     pub fn set_input(&mut self, input: String) {
         self.input.set_text(input);
@@ -586,6 +595,18 @@ static LAUNCHER_WINDOW: std::sync::Mutex<Option<WindowHandle<Launcher>>> =
 
 pub fn init(_cx: &mut App) {}
 
+/// Recreate the views of the open launcher, if any, after a config change.
+/// A closed launcher picks up the new config the next time it opens.
+pub fn reload_views(cx: &mut App) {
+    let handle = *LAUNCHER_WINDOW.lock().unwrap();
+    if let Some(handle) = handle {
+        let _ = handle.update(cx, |launcher, _, cx| {
+            launcher.reload_views(cx);
+            cx.notify();
+        });
+    }
+}
+
 /// Toggle the launcher window with optional prefilled input.
 ///
 /// Behavior:
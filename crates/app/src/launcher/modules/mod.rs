@@ -1,7 +1,7 @@
 //! Launcher modules.
 //!
 //! This module contains all the different views available in the launcher,
-//! such as applications search, workspaces, shell commands, web search, and help.
+//! such as applications search, workspaces, windows, shell commands, web search, and help.
 //! Each view is in its own folder for better organization.
 
 pub mod apps;
@@ -11,6 +11,7 @@ pub mod shell;
 pub mod theme;
 pub mod wallpaper;
 pub mod web;
+pub mod windows;
 pub mod workspaces;
 
 pub use apps::AppsView;
@@ -20,6 +21,7 @@ pub use shell::ShellView;
 pub use theme::ThemeView;
 pub use wallpaper::WallpaperView;
 pub use web::WebSearchView;
+pub use windows::WindowsView;
 pub use workspaces::WorkspacesView;
 
 use super::config::LauncherConfig;
@@ -31,6 +33,7 @@ pub fn all_views(config: &LauncherConfig) -> Vec<Box<dyn LauncherView>> {
         Box::new(ShellView::new(&config.modules.shell)),
         Box::new(WebSearchView::new(&config.modules.web)),
         Box::new(WorkspacesView::new(&config.modules.workspaces)),
+        Box::new(WindowsView::new(&config.modules.windows)),
         Box::new(WallpaperView::new(&config.modules.wallpaper)),
         Box::new(ThemeView::new(&config.modules.themes)),
         Box::new(ServicesView::new(&config.modules.services)),
@@ -43,6 +43,11 @@ impl ServicesView {
                 icon: "󰕾",
                 status: AppState::audio(cx).status(),
             },
+            ServiceInfo {
+                name: "Compositor",
+                icon: "󰍹",
+                status: AppState::compositor(cx).status(),
+            },
             ServiceInfo {
                 name: "Network",
                 icon: "󰖟",
@@ -189,6 +189,13 @@ fn all_schemes(query: &str) -> Vec<ThemeScheme> {
         .collect()
 }
 
+/// Find a theme scheme by name (case-insensitive).
+pub fn find_scheme(name: &str) -> Option<ThemeScheme> {
+    all_schemes("")
+        .into_iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+}
+
 fn colors_match(a: gpui::Hsla, b: gpui::Hsla) -> bool {
     (a.h - b.h).abs() < 0.01 && (a.s - b.s).abs() < 0.01 && (a.l - b.l).abs() < 0.01
 }
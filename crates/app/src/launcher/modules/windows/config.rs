@@ -0,0 +1,18 @@
+//! Windows view configuration.
+
+use serde::{Deserialize, Serialize};
+
+/// Windows view configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowsConfig {
+    pub prefix: String,
+}
+
+impl Default for WindowsConfig {
+    fn default() -> Self {
+        Self {
+            prefix: ";win".into(),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Windows view for switching to any open window.
+
+pub mod config;
+
+use gpui::{AnyElement, App, div, img, prelude::*, px};
+use services::{CompositorCommand, CompositorState, Window};
+use ui::{ActiveTheme, Color, Label, LabelCommon, LabelSize, ListItem, ListItemSpacing};
+
+use self::config::WindowsConfig;
+use crate::launcher::view::{LauncherView, ViewContext, render_footer_hints};
+use crate::state::AppState;
+
+/// Windows view - lists open windows across all workspaces.
+pub struct WindowsView {
+    prefix: String,
+}
+
+impl WindowsView {
+    pub fn new(config: &WindowsConfig) -> Self {
+        Self {
+            prefix: config.prefix.clone(),
+        }
+    }
+
+    /// Open windows matching the query, best match first.
+    fn filtered_windows(&self, vx: &ViewContext, cx: &App) -> (CompositorState, Vec<Window>) {
+        let state = AppState::compositor(cx).get();
+        let query = vx.query.trim().to_lowercase();
+
+        let mut scored: Vec<_> = state
+            .windows
+            .iter()
+            .filter_map(|window| {
+                if query.is_empty() {
+                    return Some((0, window.clone()));
+                }
+                let title = fuzzy_score(&window.title.to_lowercase(), &query);
+                let class = fuzzy_score(&window.class.to_lowercase(), &query);
+                title.max(class).map(|score| (score, window.clone()))
+            })
+            .collect();
+        // Stable sort keeps the compositor's order among equal scores.
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let windows = scored.into_iter().map(|(_, window)| window).collect();
+        (state, windows)
+    }
+}
+
+/// Score `needle` as a subsequence of `haystack` (both lowercase).
+///
+/// Returns `None` when not every character of the needle is found in
+/// order. Consecutive matches and matches at word starts score higher.
+fn fuzzy_score(haystack: &str, needle: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut needle_chars = needle.chars().peekable();
+    let mut previous: Option<char> = None;
+    let mut streak = 0;
+
+    for c in haystack.chars() {
+        let Some(&wanted) = needle_chars.peek() else {
+            break;
+        };
+        if c == wanted {
+            needle_chars.next();
+            streak += 1;
+            score += streak;
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 5;
+            }
+        } else {
+            streak = 0;
+        }
+        previous = Some(c);
+    }
+
+    if needle_chars.peek().is_some() {
+        return None;
+    }
+    if haystack.contains(needle) {
+        score += 10;
+    }
+    Some(score)
+}
+
+impl LauncherView for WindowsView {
+    fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn name(&self) -> &'static str {
+        "Windows"
+    }
+
+    fn icon(&self) -> &'static str {
+        "󰖯"
+    }
+
+    fn description(&self) -> &'static str {
+        "Switch to an open window"
+    }
+
+    fn match_count(&self, vx: &ViewContext, cx: &App) -> usize {
+        self.filtered_windows(vx, cx).1.len()
+    }
+
+    fn render_item(&self, index: usize, selected: bool, vx: &ViewContext, cx: &App) -> AnyElement {
+        let (state, filtered) = self.filtered_windows(vx, cx);
+        let Some(window) = filtered.get(index) else {
+            return div().into_any_element();
+        };
+
+        let theme = cx.theme();
+        let title = if window.title.is_empty() {
+            window.class.clone()
+        } else {
+            window.title.clone()
+        };
+        let workspace = window
+            .workspace_id
+            .and_then(|id| state.workspaces.iter().find(|ws| ws.id == id));
+        let subtitle = match workspace {
+            Some(ws) if ws.name.is_empty() => format!("{} on workspace {}", window.class, ws.id),
+            Some(ws) => format!("{} on workspace {}", window.class, ws.name),
+            None => window.class.clone(),
+        };
+        let icon_path = AppState::applications(cx).icon_for_class(&window.class);
+        let window_id = window.id.clone();
+        let compositor_clone = AppState::compositor(cx).clone();
+        let interactive_default = theme.interactive.default;
+        let fallback_icon = self.icon();
+
+        ListItem::new(format!("win-{}", window.id))
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected)
+            .start_slot(
+                div()
+                    .w(px(28.))
+                    .h(px(28.))
+                    .rounded(px(6.))
+                    .overflow_hidden()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .when_some(icon_path.clone(), |el, path| {
+                        el.child(img(path).size_full())
+                    })
+                    .when(icon_path.is_none(), move |el| {
+                        el.bg(interactive_default)
+                            .text_size(px(14.))
+                            .child(fallback_icon)
+                    }),
+            )
+            .on_click(move |_, _, _cx| {
+                let _ =
+                    compositor_clone.dispatch(CompositorCommand::FocusWindow(window_id.clone()));
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(px(1.))
+                    .child(Label::new(title).size(LabelSize::Default))
+                    .child(
+                        Label::new(subtitle)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .into_any_element()
+    }
+
+    fn on_select(&self, index: usize, vx: &ViewContext, cx: &mut App) -> bool {
+        let (_, filtered) = self.filtered_windows(vx, cx);
+        if let Some(window) = filtered.get(index) {
+            let _ = AppState::compositor(cx)
+                .dispatch(CompositorCommand::FocusWindow(window.id.clone()));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn render_footer_bar(&self, _vx: &ViewContext, cx: &App) -> AnyElement {
+        render_footer_hints(vec![("Focus", "Enter"), ("Close", "Esc")], cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_requires_subsequence() {
+        assert!(fuzzy_score("firefox", "ffx").is_some());
+        assert_eq!(fuzzy_score("firefox", "xf"), None);
+        assert_eq!(fuzzy_score("firefox", "firefoxes"), None);
+        assert!(fuzzy_score("firefox", "").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_score_ranking() {
+        // Substrings beat scattered matches.
+        assert!(fuzzy_score("firefox", "fire") > fuzzy_score("foo irene", "fire"));
+        // Matches at word starts beat matches inside words.
+        assert!(fuzzy_score("visual studio code", "code") > fuzzy_score("decode", "code"));
+        assert!(fuzzy_score("gnome terminal", "gt") > fuzzy_score("magnet", "gt"));
+    }
+}
@@ -6,7 +6,13 @@
 //! Usage:
 //!   gpuishell              - Start the shell or open launcher if already running
 //!   gpuishell --input "x"  - Open launcher with prefilled input
+//!   gpuishell daemon       - Start the shell, failing if already running
+//!   gpuishell msg <cmd>    - Send a command to the running instance
+//!   gpuishell config check - Validate the config file
+//!
+//! See `gpuishell --help` for all commands and options.
 
+use crate::args::{Args, Command, USAGE};
 use crate::ipc::IpcSubscriber;
 use assets::Assets;
 use gpui_platform::application;
@@ -35,18 +41,43 @@ async fn main() {
         .init();
 
     // Parse command-line arguments
-    let args = args::Args::parse();
+    let args = Args::parse();
+    if let Some(path) = args.config {
+        config::set_config_path(path);
+    }
 
-    // Try to acquire single-instance lock or signal existing instance.
+    // Acquire the single-instance lock, or signal the existing instance.
     // Secondary path exits immediately after signaling the primary instance.
-    let Some(ipc) = IpcSubscriber::init(&args) else {
-        return;
+    let ipc = match args.command {
+        Command::Default { input } => {
+            let Some(ipc) = IpcSubscriber::init(input) else {
+                return;
+            };
+            ipc
+        }
+        Command::Daemon => match IpcSubscriber::bind() {
+            Ok(ipc) => ipc,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        },
+        Command::Launcher { input } => std::process::exit(ipc::run_launcher(input)),
+        Command::Msg(msg) => std::process::exit(ipc::run_msg(&msg)),
+        Command::ConfigCheck { path } => std::process::exit(config::cli::check(path)),
+        Command::ConfigDumpDefault => std::process::exit(config::cli::dump_default()),
+        Command::Help => {
+            println!("{USAGE}");
+            return;
+        }
+        Command::Version => {
+            println!("gpuishell {}", env!("CARGO_PKG_VERSION"));
+            return;
+        }
     };
 
     // Initialize services (requires async)
-    let services = state::init_services()
-        .await
-        .expect("Failed to initialize services");
+    let services = state::init_services().await;
 
     // Create and run the GPUI application
     let app = application().with_assets(Assets {});
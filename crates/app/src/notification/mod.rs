@@ -10,9 +10,59 @@ pub use config::{NotificationConfig, NotificationPopupPosition};
 pub use popup::init;
 pub use widget::NotificationWidget;
 
+use gpui::App;
+use gpui::layer_shell::Anchor;
 use services::{NotificationCommand, NotificationSubscriber};
 
-fn dispatch_notification_command(subscriber: NotificationSubscriber, command: NotificationCommand) {
+use crate::config::Config;
+use crate::panel::{PanelConfig, panel_placement_default, toggle_panel};
+use crate::state::AppState;
+use pannel::NotificationCenter;
+
+/// Toggle the notification center next to the bar.
+pub fn toggle_center(cx: &mut App) {
+    let (anchor, margin) = panel_placement_default(Config::global(cx).bar.position);
+    toggle_center_at(anchor, margin, cx);
+}
+
+/// Toggle the notification center at the given placement, marking all
+/// notifications as read.
+pub fn toggle_center_at(anchor: Anchor, margin: (f32, f32, f32, f32), cx: &mut App) {
+    let notification_config = &Config::global(cx).notification;
+    let panel_config = PanelConfig {
+        width: notification_config.center_width,
+        height: notification_config.center_height,
+        anchor,
+        margin,
+        namespace: "notification-center".to_string(),
+    };
+
+    let subscriber = AppState::notification(cx).clone();
+    dispatch_notification_command(subscriber.clone(), NotificationCommand::MarkAllRead);
+
+    toggle_panel("notification-center", panel_config, cx, move |cx| {
+        NotificationCenter::new(subscriber, cx)
+    });
+}
+
+/// Show a notification from the shell itself, e.g. to report config errors.
+///
+/// Deferred so it can be called before the app state is initialized.
+pub fn notify(summary: impl Into<String>, body: impl Into<String>, cx: &mut App) {
+    let command = NotificationCommand::Notify {
+        summary: summary.into(),
+        body: body.into(),
+        urgency: 2,
+    };
+    cx.defer(move |cx| {
+        dispatch_notification_command(AppState::notification(cx).clone(), command);
+    });
+}
+
+pub(crate) fn dispatch_notification_command(
+    subscriber: NotificationSubscriber,
+    command: NotificationCommand,
+) {
     std::thread::spawn(move || {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
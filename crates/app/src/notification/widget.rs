@@ -1,22 +1,21 @@
 use gpui::{App, Context, MouseButton, Render, Size, Window, div, prelude::*, px};
-use services::{NotificationCommand, NotificationData, NotificationSubscriber};
+use services::NotificationData;
 use ui::{ActiveTheme, icon_size, radius, spacing};
 
+use crate::bar;
+use crate::bar::modules::style;
 use crate::config::{ActiveConfig, Config};
-use crate::panel::{PanelConfig, panel_placement_from_event, toggle_panel};
+use crate::panel::panel_placement_from_event;
 use crate::state::{AppState, watch};
 
-use super::dispatch_notification_command;
-use super::pannel::NotificationCenter;
-
 /// Notification widget for the bar.
 pub struct NotificationWidget {
-    subscriber: NotificationSubscriber,
     data: NotificationData,
+    grouped: bool,
 }
 
 impl NotificationWidget {
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(grouped: bool, cx: &mut Context<Self>) -> Self {
         let subscriber = AppState::notification(cx).clone();
         let data = subscriber.get();
 
@@ -25,7 +24,7 @@ impl NotificationWidget {
             cx.notify();
         });
 
-        Self { subscriber, data }
+        Self { data, grouped }
     }
 
     fn toggle_center(&self, event: &gpui::MouseDownEvent, window: &Window, cx: &mut App) {
@@ -36,29 +35,17 @@ impl NotificationWidget {
             px(notification_config.center_height),
         );
         let (anchor, margin) =
-            panel_placement_from_event(config.bar.position, event, window, cx, panel_size);
-        let subscriber = self.subscriber.clone();
-        dispatch_notification_command(subscriber.clone(), NotificationCommand::MarkAllRead);
-
-        let panel_config = PanelConfig {
-            width: notification_config.center_width,
-            height: notification_config.center_height,
-            anchor,
-            margin,
-            namespace: "notification-center".to_string(),
-        };
-
-        toggle_panel("notification-center", panel_config, cx, move |cx| {
-            NotificationCenter::new(subscriber, cx)
-        });
+            panel_placement_from_event(bar::position(window, cx), event, window, cx, panel_size);
+        super::toggle_center_at(anchor, margin, cx);
     }
 }
 
 impl Render for NotificationWidget {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl gpui::IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl gpui::IntoElement {
         let theme = cx.theme();
         let config = &cx.config().notification;
-        let is_vertical = cx.config().bar.is_vertical();
+        let is_vertical = bar::position(window, cx).is_vertical();
+        let grouped = self.grouped;
         let unread = self.data.unread_count;
 
         let interactive_default = theme.interactive.default;
@@ -74,11 +61,13 @@ impl Render for NotificationWidget {
             .when(is_vertical, |this| this.flex_col())
             .items_center()
             .gap(px(spacing::XS))
-            .px(px(spacing::XS))
-            .py(px(3.0))
-            .rounded(px(radius::SM))
+            .when(!grouped, |el| {
+                el.px(px(spacing::XS))
+                    .py(px(3.0))
+                    .rounded(px(radius::SM))
+                    .bg(interactive_default)
+            })
             .cursor_pointer()
-            .bg(interactive_default)
             .hover(move |el| el.bg(interactive_hover))
             .active(move |el| el.bg(interactive_active))
             .on_mouse_down(
@@ -2,6 +2,7 @@
 //!
 //! Shows a brief overlay with icon, progress bar, and percentage
 //! when volume or brightness changes. Auto-dismisses after 2 seconds.
+//! Volumes boosted above 100% are drawn in the warning color.
 //!
 //! Supports four positions: Top, Bottom, Left, Right.
 //! Left/Right use a vertical layout; Top/Bottom use a horizontal layout.
@@ -10,6 +11,7 @@ mod config;
 
 pub use config::{OsdConfig, OsdPosition};
 
+use std::collections::HashMap;
 use std::sync::Mutex;
 use std::time::Duration;
 
@@ -19,9 +21,10 @@ use gpui::{
     WindowBackgroundAppearance, WindowBounds, WindowKind, WindowOptions, div, layer_shell::*,
     prelude::*, px,
 };
+use services::{BrightnessData, BrightnessKind};
 use ui::{ActiveTheme, icon_size, radius, spacing};
 
-use crate::config::Config;
+use crate::config::{ActiveConfig, Config};
 use crate::control_center::icons;
 use crate::state::AppState;
 
@@ -65,8 +68,8 @@ impl OsdPosition {
 /// What the OSD is currently displaying.
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum OsdKind {
-    Volume { level: u8, muted: bool },
-    Brightness { level: u8 },
+    Volume { level: u16, muted: bool },
+    Brightness { level: u8, device: BrightnessKind },
 }
 
 /// The OSD view rendered inside the layer-shell window.
@@ -80,22 +83,25 @@ impl OsdView {
         Self { kind, position }
     }
 
-    fn icon_and_level(&self) -> (&'static str, u8, bool) {
+    fn icon_and_level(&self) -> (&'static str, u16, bool) {
         match self.kind {
             OsdKind::Volume { level, muted } => (icons::volume_icon(level, muted), level, muted),
-            OsdKind::Brightness { level } => {
-                let icon = if level < 33 {
-                    icons::BRIGHTNESS_LOW
-                } else if level < 66 {
-                    icons::BRIGHTNESS
-                } else {
-                    icons::BRIGHTNESS_HIGH
-                };
-                (icon, level, false)
+            OsdKind::Brightness { level, device } => {
+                (icons::brightness_icon(device, level), level.into(), false)
             }
         }
     }
 
+    /// Filled part of the progress bar. With a volume limit above 100%,
+    /// the bar spans up to the limit so boosted levels remain visible.
+    fn fill_fraction(&self, level: u16, cx: &App) -> f32 {
+        let full = match self.kind {
+            OsdKind::Volume { .. } => cx.config().audio.max_volume.max(100),
+            OsdKind::Brightness { .. } => 100,
+        };
+        (level as f32 / full as f32).min(1.0)
+    }
+
     fn render_horizontal(&self, cx: &Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
         let (icon, level, muted) = self.icon_and_level();
@@ -108,7 +114,7 @@ impl OsdView {
             theme.accent.primary
         };
 
-        let bar_fill_pct = (level as f32 / 100.0).min(1.0);
+        let bar_fill_pct = self.fill_fraction(level, cx);
 
         let icon_color = if muted {
             theme.status.error
@@ -177,7 +183,7 @@ impl OsdView {
             theme.accent.primary
         };
 
-        let bar_fill_pct = (level as f32 / 100.0).min(1.0);
+        let bar_fill_pct = self.fill_fraction(level, cx);
 
         let icon_color = if muted {
             theme.status.error
@@ -335,6 +341,13 @@ fn close_osd(cx: &mut App) {
     }
 }
 
+/// Move the OSD to another screen edge. A visible OSD is closed and the
+/// next one opens at the new position.
+pub fn set_position(position: OsdPosition, cx: &mut App) {
+    *OSD_POSITION.lock().unwrap() = position;
+    close_osd(cx);
+}
+
 /// Generation counter for dismiss scheduling.
 static DISMISS_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
 
@@ -355,6 +368,14 @@ fn schedule_dismiss(cx: &mut App) {
     .detach();
 }
 
+/// Brightness percentage of every device, by device id.
+fn brightness_percents(data: &BrightnessData) -> HashMap<String, u8> {
+    data.devices
+        .iter()
+        .map(|device| (device.id.clone(), device.percentage()))
+        .collect()
+}
+
 /// Initialize OSD listeners for audio and brightness changes.
 ///
 /// Should be called once during app initialization.
@@ -395,11 +416,12 @@ pub fn init(cx: &mut App) {
     })
     .detach();
 
-    // Brightness listener
+    // Brightness listener, for every device. Devices that appear later
+    // (e.g. monitors found over DDC/CI) do not trigger the OSD.
     cx.spawn({
         let mut signal = brightness_service.subscribe().to_stream();
         let brightness = brightness_service.clone();
-        let mut prev_percent = initial_brightness.percentage();
+        let mut prev_percents = brightness_percents(&initial_brightness);
 
         async move |cx| {
             use futures_util::StreamExt;
@@ -407,12 +429,20 @@ pub fn init(cx: &mut App) {
 
             while signal.next().await.is_some() {
                 let data = brightness.get();
-                let percent = data.percentage();
-                if percent != prev_percent {
-                    prev_percent = percent;
-                    let kind = OsdKind::Brightness { level: percent };
+                let percents = brightness_percents(&data);
+                let changed = data.devices.iter().find(|device| {
+                    prev_percents
+                        .get(&device.id)
+                        .is_some_and(|prev| *prev != device.percentage())
+                });
+                if let Some(device) = changed {
+                    let kind = OsdKind::Brightness {
+                        level: device.percentage(),
+                        device: device.kind,
+                    };
                     cx.update(|cx| show_osd(kind, cx));
                 }
+                prev_percents = percents;
             }
         }
     })
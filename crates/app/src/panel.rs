@@ -62,6 +62,18 @@ pub fn panel_placement_from_event(
     )
 }
 
+/// Resolve panel anchor/margin when there is no click to place it from
+/// (e.g. when opened over IPC): the panel sits in the bar's end corner.
+pub fn panel_placement_default(bar_position: BarPosition) -> (Anchor, (f32, f32, f32, f32)) {
+    let anchor = match bar_position {
+        BarPosition::Left => Anchor::LEFT | Anchor::BOTTOM,
+        BarPosition::Right => Anchor::RIGHT | Anchor::BOTTOM,
+        BarPosition::Top => Anchor::TOP | Anchor::RIGHT,
+        BarPosition::Bottom => Anchor::BOTTOM | Anchor::RIGHT,
+    };
+    (anchor, PanelConfig::default().margin)
+}
+
 /// Resolve panel anchor/margin from a click position.
 pub fn panel_placement_from_click(
     bar_position: BarPosition,
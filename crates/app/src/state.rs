@@ -4,6 +4,8 @@ use futures_signals::signal::{Signal, SignalExt};
 use futures_util::StreamExt;
 use gpui::{App, Context, Global};
 
+use crate::config::ActiveConfig;
+
 /// Shared services container for all system integrations.
 ///
 /// This struct holds instances of all available services and should be
@@ -26,27 +28,55 @@ pub(crate) struct Services {
     pub wallpaper: services::WallpaperSubscriber,
 }
 
-pub(crate) async fn init_services() -> anyhow::Result<Services> {
+/// Start all services.
+///
+/// A service that cannot start (no compositor, no system bus, tray watcher
+/// already taken, ...) is replaced by a disabled instance reporting
+/// `ServiceStatus::Unavailable`, so the rest of the shell still runs.
+pub(crate) async fn init_services() -> Services {
     let applications = services::ApplicationsService::new();
     let audio = services::AudioSubscriber::new();
-    let bluetooth = services::BluetoothSubscriber::new().await?;
-    let brightness = services::BrightnessSubscriber::new().await?;
-    let compositor = services::CompositorSubscriber::new().await?;
-    let mpris = services::MprisSubscriber::new().await?;
-    let network = services::NetworkSubscriber::new().await?;
+    let bluetooth = services::BluetoothSubscriber::new()
+        .await
+        .unwrap_or_else(|err| {
+            unavailable("Bluetooth", err, services::BluetoothSubscriber::disabled)
+        });
+    let brightness = services::BrightnessSubscriber::new()
+        .await
+        .unwrap_or_else(|err| {
+            unavailable("Brightness", err, services::BrightnessSubscriber::disabled)
+        });
+    let compositor = services::CompositorSubscriber::new()
+        .await
+        .unwrap_or_else(|err| {
+            unavailable("Compositor", err, services::CompositorSubscriber::disabled)
+        });
+    let mpris = services::MprisSubscriber::new()
+        .await
+        .unwrap_or_else(|err| unavailable("MPRIS", err, services::MprisSubscriber::disabled));
+    let network = services::NetworkSubscriber::new()
+        .await
+        .unwrap_or_else(|err| unavailable("Network", err, services::NetworkSubscriber::disabled));
     let notification = services::NotificationSubscriber::new()
         .await
         .unwrap_or_else(|err| {
-            tracing::warn!("Notification service unavailable: {}", err);
-            services::NotificationSubscriber::disabled()
+            unavailable(
+                "Notification",
+                err,
+                services::NotificationSubscriber::disabled,
+            )
         });
     let privacy = services::PrivacySubscriber::new();
     let sysinfo = services::SysInfoSubscriber::new();
-    let tray = services::TraySubscriber::new().await?;
-    let upower = services::UPowerSubscriber::new().await?;
+    let tray = services::TraySubscriber::new()
+        .await
+        .unwrap_or_else(|err| unavailable("Tray", err, services::TraySubscriber::disabled));
+    let upower = services::UPowerSubscriber::new()
+        .await
+        .unwrap_or_else(|err| unavailable("UPower", err, services::UPowerSubscriber::disabled));
     let wallpaper = services::WallpaperSubscriber::new();
 
-    Ok(Services {
+    Services {
         applications,
         audio,
         bluetooth,
@@ -60,7 +90,12 @@ pub(crate) async fn init_services() -> anyhow::Result<Services> {
         tray,
         upower,
         wallpaper,
-    })
+    }
+}
+
+fn unavailable<T>(name: &str, err: anyhow::Error, disabled: fn() -> T) -> T {
+    tracing::warn!("{} service unavailable: {:#}", name, err);
+    disabled()
 }
 
 /// Watch a signal and apply updates to component state.
@@ -87,6 +122,32 @@ where
     .detach();
 }
 
+/// Look for DDC/CI monitors again whenever the compositor's monitor list
+/// changes.
+fn redetect_ddc_on_monitor_change(services: &Services, cx: &mut App) {
+    let brightness = services.brightness.clone();
+    let monitors = services
+        .compositor
+        .subscribe()
+        .map(|state| {
+            state
+                .monitors
+                .iter()
+                .map(|monitor| monitor.name.clone())
+                .collect::<Vec<_>>()
+        })
+        .dedupe_cloned();
+
+    cx.spawn(async move |_| {
+        // The first list is the one DDC/CI detection started with.
+        let mut stream = monitors.to_stream().skip(1);
+        while stream.next().await.is_some() {
+            brightness.redetect_ddc();
+        }
+    })
+    .detach();
+}
+
 /// Global runtime state shared across views/widgets.
 #[derive(Clone)]
 pub struct AppState {
@@ -98,6 +159,11 @@ impl Global for AppState {}
 impl AppState {
     /// Initialize the global app state.
     pub(crate) fn init(services: Services, cx: &mut App) {
+        services.audio.set_max_volume(cx.config().audio.max_volume);
+        if cx.config().brightness.ddc {
+            services.brightness.enable_ddc();
+        }
+        redetect_ddc_on_monitor_change(&services, cx);
         cx.set_global(Self { services });
     }
 
@@ -7,7 +7,7 @@ pub mod icons;
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::thread;
 
@@ -123,6 +123,36 @@ impl ApplicationsService {
             .find(|app| app.name.to_lowercase() == name_lower)
     }
 
+    /// Find the icon for a window class (Wayland app id).
+    ///
+    /// Prefers the icon of a desktop entry named after the class (e.g.
+    /// `org.gnome.Nautilus.desktop`), then looks the class up as an icon name.
+    pub fn icon_for_class(&self, class: &str) -> Option<PathBuf> {
+        if class.is_empty() {
+            return None;
+        }
+
+        let matches_class = |path: &Path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem.eq_ignore_ascii_case(class))
+        };
+        let from_desktop_entry = self
+            .apps
+            .iter()
+            .find(|app| matches_class(&app.desktop_file) || app.name.eq_ignore_ascii_case(class))
+            .and_then(|app| app.icon_path.clone());
+        if from_desktop_entry.is_some() {
+            return from_desktop_entry;
+        }
+
+        let lower = class.to_lowercase();
+        let short = lower.rsplit('.').next().unwrap_or(&lower).to_string();
+        [class.to_string(), lower, short]
+            .iter()
+            .find_map(|name| icons::lookup_icon(name))
+    }
+
     /// Rescan for applications.
     pub fn refresh(&mut self) {
         self.apps = scan_applications();
@@ -0,0 +1,287 @@
+//! Applying audio commands through the listener's PulseAudio context.
+
+use libpulse_binding::{
+    context::{Context, introspect::Introspector},
+    volume::{ChannelVolumes, Volume},
+};
+use tracing::error;
+
+use super::{AudioCommand, AudioData, AudioStream, StreamKind};
+
+const DEFAULT_SINK: &str = "@DEFAULT_SINK@";
+const DEFAULT_SOURCE: &str = "@DEFAULT_SOURCE@";
+
+/// Merge adjacent commands that change the same volume, so a burst of
+/// scroll ticks results in a single volume change.
+pub(super) fn coalesce(
+    commands: impl IntoIterator<Item = AudioCommand>,
+    max_volume: u16,
+) -> Vec<AudioCommand> {
+    let mut merged: Vec<AudioCommand> = Vec::new();
+    for command in commands {
+        if let Some(combined) = merged
+            .last()
+            .and_then(|last| combine(last, &command, max_volume))
+        {
+            *merged.last_mut().unwrap() = combined;
+        } else {
+            merged.push(command);
+        }
+    }
+    merged
+}
+
+fn combine(previous: &AudioCommand, next: &AudioCommand, max_volume: u16) -> Option<AudioCommand> {
+    use AudioCommand::*;
+
+    match (previous, next) {
+        (SetSinkVolume(_) | AdjustSinkVolume(_), SetSinkVolume(volume)) => {
+            Some(SetSinkVolume(*volume))
+        }
+        (SetSinkVolume(volume), AdjustSinkVolume(delta)) => {
+            let volume = adjusted((*volume).min(max_volume), *delta, max_volume);
+            Some(SetSinkVolume(volume))
+        }
+        (AdjustSinkVolume(a), AdjustSinkVolume(b)) => Some(AdjustSinkVolume(a.saturating_add(*b))),
+        (SetSourceVolume(_) | AdjustSourceVolume(_), SetSourceVolume(volume)) => {
+            Some(SetSourceVolume(*volume))
+        }
+        (SetSourceVolume(volume), AdjustSourceVolume(delta)) => {
+            let volume = adjusted((*volume).min(max_volume), *delta, max_volume);
+            Some(SetSourceVolume(volume))
+        }
+        (AdjustSourceVolume(a), AdjustSourceVolume(b)) => {
+            Some(AdjustSourceVolume(a.saturating_add(*b)))
+        }
+        (
+            SetStreamVolume { kind, index, .. },
+            SetStreamVolume {
+                kind: next_kind,
+                index: next_index,
+                ..
+            },
+        ) if kind == next_kind && index == next_index => Some(next.clone()),
+        _ => None,
+    }
+}
+
+/// Apply a command, limiting volumes to `max_volume` percent.
+///
+/// `data` is updated right away, so follow-up adjustments and the UI see
+/// the new values before PulseAudio reports them back.
+pub(super) fn apply(
+    context: &mut Context,
+    introspector: &mut Introspector,
+    data: &mut AudioData,
+    max_volume: u16,
+    command: AudioCommand,
+) {
+    match command {
+        AudioCommand::SetSinkVolume(volume) => {
+            set_sink_volume(introspector, data, volume.min(max_volume));
+        }
+        AudioCommand::SetSourceVolume(volume) => {
+            set_source_volume(introspector, data, volume.min(max_volume));
+        }
+        AudioCommand::AdjustSinkVolume(delta) => {
+            let volume = adjusted(data.sink_volume, delta, max_volume);
+            set_sink_volume(introspector, data, volume);
+        }
+        AudioCommand::AdjustSourceVolume(delta) => {
+            let volume = adjusted(data.source_volume, delta, max_volume);
+            set_source_volume(introspector, data, volume);
+        }
+        AudioCommand::ToggleSinkMute => {
+            data.sink_muted = !data.sink_muted;
+            introspector.set_sink_mute_by_name(DEFAULT_SINK, data.sink_muted, None);
+        }
+        AudioCommand::ToggleSourceMute => {
+            data.source_muted = !data.source_muted;
+            introspector.set_source_mute_by_name(DEFAULT_SOURCE, data.source_muted, None);
+        }
+        AudioCommand::SetDefaultSink { name, move_streams } => {
+            context.set_default_sink(&name, log_failure("set default sink"));
+            if move_streams {
+                for stream in &data.playback_streams {
+                    introspector.move_sink_input_by_name(stream.index, &name, None);
+                }
+            }
+            data.default_sink = name;
+        }
+        AudioCommand::SetDefaultSource { name, move_streams } => {
+            context.set_default_source(&name, log_failure("set default source"));
+            if move_streams {
+                for stream in &data.recording_streams {
+                    introspector.move_source_output_by_name(stream.index, &name, None);
+                }
+            }
+            data.default_source = name;
+        }
+        AudioCommand::SetStreamVolume {
+            kind,
+            index,
+            volume,
+        } => {
+            let volume = volume.min(max_volume);
+            let volumes = channel_volumes(volume);
+            match kind {
+                StreamKind::Playback => introspector.set_sink_input_volume(index, &volumes, None),
+                StreamKind::Recording => {
+                    introspector.set_source_output_volume(index, &volumes, None)
+                }
+            };
+            if let Some(stream) = stream_mut(data, kind, index) {
+                stream.volume = volume;
+            }
+        }
+        AudioCommand::ToggleStreamMute { kind, index } => {
+            let Some(stream) = stream_mut(data, kind, index) else {
+                return;
+            };
+            stream.muted = !stream.muted;
+            match kind {
+                StreamKind::Playback => introspector.set_sink_input_mute(index, stream.muted, None),
+                StreamKind::Recording => {
+                    introspector.set_source_output_mute(index, stream.muted, None)
+                }
+            };
+        }
+        AudioCommand::MoveStream {
+            kind,
+            index,
+            device,
+        } => {
+            match kind {
+                StreamKind::Playback => introspector.move_sink_input_by_name(index, &device, None),
+                StreamKind::Recording => {
+                    introspector.move_source_output_by_name(index, &device, None)
+                }
+            };
+        }
+    }
+}
+
+fn set_sink_volume(introspector: &mut Introspector, data: &mut AudioData, volume: u16) {
+    data.sink_volume = volume;
+    introspector.set_sink_volume_by_name(DEFAULT_SINK, &channel_volumes(volume), None);
+}
+
+fn set_source_volume(introspector: &mut Introspector, data: &mut AudioData, volume: u16) {
+    data.source_volume = volume;
+    introspector.set_source_volume_by_name(DEFAULT_SOURCE, &channel_volumes(volume), None);
+}
+
+fn stream_mut(data: &mut AudioData, kind: StreamKind, index: u32) -> Option<&mut AudioStream> {
+    let streams = match kind {
+        StreamKind::Playback => &mut data.playback_streams,
+        StreamKind::Recording => &mut data.recording_streams,
+    };
+    streams.iter_mut().find(|stream| stream.index == index)
+}
+
+fn log_failure(action: &'static str) -> impl FnMut(bool) + 'static {
+    move |success| {
+        if !success {
+            error!("Failed to {}", action);
+        }
+    }
+}
+
+/// Apply a percentage delta, clamped to `max_volume`.
+///
+/// A volume already above the limit is not raised further, but is not
+/// pulled down to the limit either.
+fn adjusted(volume: u16, delta: i8, max_volume: u16) -> u16 {
+    let limit = max_volume.max(volume) as i32;
+    (volume as i32 + delta as i32).clamp(0, limit) as u16
+}
+
+/// Volume for all channels from a percentage, 100 being the normal volume.
+///
+/// A single channel sets every channel of the device or stream.
+fn channel_volumes(percent: u16) -> ChannelVolumes {
+    let ratio = percent as f64 / 100.0;
+    let mut volumes = ChannelVolumes::default();
+    volumes.set(1, Volume((ratio * Volume::NORMAL.0 as f64).round() as u32));
+    volumes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AudioCommand::*;
+
+    #[test]
+    fn test_adjusted() {
+        assert_eq!(adjusted(50, 5, 100), 55);
+        assert_eq!(adjusted(98, 5, 100), 100);
+        assert_eq!(adjusted(3, -5, 100), 0);
+        assert_eq!(adjusted(100, 10, 150), 110);
+    }
+
+    #[test]
+    fn test_adjusted_above_limit() {
+        // Set elsewhere above the limit: not raised, not pulled down.
+        assert_eq!(adjusted(120, 5, 100), 120);
+        assert_eq!(adjusted(120, -5, 100), 115);
+    }
+
+    #[test]
+    fn test_coalesce_adjustments() {
+        assert_eq!(
+            coalesce(
+                [
+                    AdjustSinkVolume(5),
+                    AdjustSinkVolume(5),
+                    AdjustSinkVolume(-3)
+                ],
+                100
+            ),
+            vec![AdjustSinkVolume(7)]
+        );
+        assert_eq!(
+            coalesce([AdjustSourceVolume(100), AdjustSourceVolume(100)], 100),
+            vec![AdjustSourceVolume(i8::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_set_volume() {
+        assert_eq!(
+            coalesce([AdjustSinkVolume(5), SetSinkVolume(30)], 100),
+            vec![SetSinkVolume(30)]
+        );
+        assert_eq!(
+            coalesce([SetSinkVolume(50), AdjustSinkVolume(10)], 100),
+            vec![SetSinkVolume(60)]
+        );
+        assert_eq!(
+            coalesce([SetSourceVolume(150), AdjustSourceVolume(5)], 100),
+            vec![SetSourceVolume(100)]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_keeps_other_commands() {
+        let commands = vec![
+            AdjustSinkVolume(5),
+            ToggleSinkMute,
+            AdjustSinkVolume(5),
+            AdjustSourceVolume(5),
+        ];
+        assert_eq!(coalesce(commands.clone(), 100), commands);
+    }
+
+    #[test]
+    fn test_coalesce_stream_volumes() {
+        let volume = |index, volume| SetStreamVolume {
+            kind: StreamKind::Playback,
+            index,
+            volume,
+        };
+        assert_eq!(
+            coalesce([volume(1, 20), volume(1, 30), volume(2, 40)], 100),
+            vec![volume(1, 30), volume(2, 40)]
+        );
+    }
+}
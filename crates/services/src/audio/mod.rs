@@ -1,36 +1,131 @@
 //! Audio service for volume control via PulseAudio/PipeWire.
 //!
 //! This module provides a reactive subscriber for monitoring and controlling
-//! audio sink (output) and source (input) volumes using libpulse for monitoring
-//! and wpctl for commands.
+//! audio sink (output) and source (input) volumes, devices and application
+//! streams using libpulse. Commands go through the same connection as the
+//! monitoring, so they work on plain PulseAudio as well as PipeWire.
+
+mod commands;
 
 use std::cell::{Cell, RefCell};
-use std::process::Command;
+use std::io::{self, Read, Write};
+use std::os::fd::AsRawFd;
+use std::os::unix::net::UnixStream;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, mpsc};
 use std::thread;
 
 use futures_signals::signal::{Mutable, MutableSignalCloned};
 use libpulse_binding::{
+    callbacks::ListResult,
     context::{self, Context, FlagSet, subscribe::InterestMaskSet},
-    mainloop::standard::{IterateResult, Mainloop},
-    proplist::{Proplist, properties::APPLICATION_NAME},
+    def::{SinkState, SourceState},
+    mainloop::{
+        api::Mainloop as _,
+        events::io::FlagSet as IoEventFlagSet,
+        standard::{IterateResult, Mainloop},
+    },
+    proplist::{
+        Proplist,
+        properties::{
+            APPLICATION_ICON_NAME, APPLICATION_NAME, APPLICATION_PROCESS_BINARY, MEDIA_NAME,
+        },
+    },
     volume::Volume,
 };
-use tracing::{debug, error};
+use serde::Serialize;
+use tracing::{debug, error, warn};
 
 use crate::ServiceStatus;
 
+/// A sink (output) or source (input) device.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AudioDevice {
+    /// PulseAudio device index.
+    pub index: u32,
+    /// PulseAudio device name, used to address the device.
+    pub name: String,
+    /// Human-readable description (e.g. "Built-in Audio Analog Stereo").
+    pub description: String,
+    /// Description of the active port (e.g. "Headphones"), if any.
+    pub port: Option<String>,
+    /// Whether this is the default device.
+    pub is_default: bool,
+    /// Whether the device is currently playing or recording.
+    pub running: bool,
+}
+
+impl AudioDevice {
+    /// Label for device pickers: the description, plus the port if any.
+    pub fn label(&self) -> String {
+        let description = if self.description.is_empty() {
+            &self.name
+        } else {
+            &self.description
+        };
+        match &self.port {
+            Some(port) if !description.contains(port.as_str()) => {
+                format!("{} ({})", description, port)
+            }
+            _ => description.clone(),
+        }
+    }
+}
+
+/// Direction of an application stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum StreamKind {
+    /// A sink input: an application playing audio.
+    Playback,
+    /// A source output: an application recording audio.
+    Recording,
+}
+
+/// An application's playback or recording stream.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioStream {
+    /// Whether the stream plays or records.
+    pub kind: StreamKind,
+    /// PulseAudio stream index.
+    pub index: u32,
+    /// Application name (e.g. "Firefox").
+    pub app_name: String,
+    /// Icon name or executable of the application, if reported.
+    pub icon: Option<String>,
+    /// What the stream is playing or recording, if reported.
+    pub media_name: Option<String>,
+    /// Stream volume as percentage; above 100 when boosted.
+    pub volume: u16,
+    /// Whether the stream is muted.
+    pub muted: bool,
+    /// Index of the sink or source the stream is routed to.
+    pub device: u32,
+}
+
 /// Audio device data.
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct AudioData {
-    /// Sink (output) volume as percentage (0-100).
-    pub sink_volume: u8,
+    /// Sink (output) volume as percentage; above 100 when boosted.
+    pub sink_volume: u16,
     /// Whether the sink is muted.
     pub sink_muted: bool,
-    /// Source (input) volume as percentage (0-100).
-    pub source_volume: u8,
+    /// Source (input) volume as percentage; above 100 when boosted.
+    pub source_volume: u16,
     /// Whether the source is muted.
     pub source_muted: bool,
+    /// All output devices.
+    pub sinks: Vec<AudioDevice>,
+    /// All input devices, excluding monitors of sinks.
+    pub sources: Vec<AudioDevice>,
+    /// Name of the default sink.
+    pub default_sink: String,
+    /// Name of the default source.
+    pub default_source: String,
+    /// Application playback streams.
+    pub playback_streams: Vec<AudioStream>,
+    /// Application recording streams.
+    pub recording_streams: Vec<AudioStream>,
 }
 
 impl AudioData {
@@ -54,20 +149,46 @@ impl AudioData {
 }
 
 /// Commands for controlling audio.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AudioCommand {
-    /// Set sink (output) volume as percentage (0-100).
-    SetSinkVolume(u8),
-    /// Set source (input) volume as percentage (0-100).
-    SetSourceVolume(u8),
+    /// Set sink (output) volume as percentage, up to the volume limit.
+    SetSinkVolume(u16),
+    /// Set source (input) volume as percentage, up to the volume limit.
+    SetSourceVolume(u16),
     /// Toggle sink mute state.
     ToggleSinkMute,
     /// Toggle source mute state.
     ToggleSourceMute,
     /// Adjust sink volume by delta percentage (+/-).
+    ///
+    /// Raising stops at the volume limit, but never lowers a volume that
+    /// was set above it elsewhere.
     AdjustSinkVolume(i8),
-    /// Adjust source volume by delta percentage (+/-).
+    /// Adjust source volume by delta percentage (+/-), like `AdjustSinkVolume`.
     AdjustSourceVolume(i8),
+    /// Make a sink the default output, by name.
+    ///
+    /// With `move_streams`, playing streams are moved to it as well.
+    SetDefaultSink { name: String, move_streams: bool },
+    /// Make a source the default input, by name.
+    ///
+    /// With `move_streams`, recording streams are moved to it as well.
+    SetDefaultSource { name: String, move_streams: bool },
+    /// Set an application stream's volume as percentage, up to the volume
+    /// limit.
+    SetStreamVolume {
+        kind: StreamKind,
+        index: u32,
+        volume: u16,
+    },
+    /// Toggle an application stream's mute state.
+    ToggleStreamMute { kind: StreamKind, index: u32 },
+    /// Move an application stream to another sink or source, by name.
+    MoveStream {
+        kind: StreamKind,
+        index: u32,
+        device: String,
+    },
 }
 
 /// Event-driven audio subscriber.
@@ -78,6 +199,10 @@ pub enum AudioCommand {
 pub struct AudioSubscriber {
     data: Mutable<AudioData>,
     status: Mutable<ServiceStatus>,
+    commands: mpsc::Sender<AudioCommand>,
+    /// Written to after queueing a command, to wake up the listener.
+    waker: Arc<UnixStream>,
+    max_volume: Arc<AtomicU16>,
 }
 
 impl AudioSubscriber {
@@ -85,8 +210,26 @@ impl AudioSubscriber {
     pub fn new() -> Self {
         let data = Mutable::new(AudioData::default());
         let status = Mutable::new(ServiceStatus::Initializing);
-        start_listener(data.clone(), status.clone());
-        Self { data, status }
+        let (commands, command_rx) = mpsc::channel();
+        let (waker, wake_rx) = UnixStream::pair().expect("Failed to create audio wake-up socket");
+        if let Err(err) = waker.set_nonblocking(true) {
+            error!("Failed to make the audio wake-up socket non-blocking: {err}");
+        }
+        let max_volume = Arc::new(AtomicU16::new(DEFAULT_MAX_VOLUME));
+        start_listener(
+            data.clone(),
+            status.clone(),
+            command_rx,
+            wake_rx,
+            max_volume.clone(),
+        );
+        Self {
+            data,
+            status,
+            commands,
+            waker: Arc::new(waker),
+            max_volume,
+        }
     }
 
     /// Get a signal that emits when audio state changes.
@@ -104,91 +247,36 @@ impl AudioSubscriber {
         self.status.get_cloned()
     }
 
+    /// Get a signal that emits when the service status changes.
+    pub fn subscribe_status(&self) -> MutableSignalCloned<ServiceStatus> {
+        self.status.signal_cloned()
+    }
+
     /// Execute an audio command.
+    ///
+    /// Commands are applied on the listener thread through its PulseAudio
+    /// connection; bursts of volume changes are merged into one.
     pub fn dispatch(&self, command: AudioCommand) {
-        match command {
-            AudioCommand::SetSinkVolume(volume) => {
-                let vol_float = volume.min(100) as f32 / 100.0;
-                let result = Command::new("wpctl")
-                    .args([
-                        "set-volume",
-                        "@DEFAULT_AUDIO_SINK@",
-                        &format!("{:.2}", vol_float),
-                    ])
-                    .output();
-
-                if let Err(e) = result {
-                    error!("Failed to set sink volume: {}", e);
-                }
-            }
-            AudioCommand::SetSourceVolume(volume) => {
-                let vol_float = volume.min(100) as f32 / 100.0;
-                let result = Command::new("wpctl")
-                    .args([
-                        "set-volume",
-                        "@DEFAULT_AUDIO_SOURCE@",
-                        &format!("{:.2}", vol_float),
-                    ])
-                    .output();
-
-                if let Err(e) = result {
-                    error!("Failed to set source volume: {}", e);
-                }
-            }
-            AudioCommand::ToggleSinkMute => {
-                let result = Command::new("wpctl")
-                    .args(["set-mute", "@DEFAULT_AUDIO_SINK@", "toggle"])
-                    .output();
-
-                if let Err(e) = result {
-                    error!("Failed to toggle sink mute: {}", e);
-                }
-            }
-            AudioCommand::ToggleSourceMute => {
-                let result = Command::new("wpctl")
-                    .args(["set-mute", "@DEFAULT_AUDIO_SOURCE@", "toggle"])
-                    .output();
-
-                if let Err(e) = result {
-                    error!("Failed to toggle source mute: {}", e);
-                }
-            }
-            AudioCommand::AdjustSinkVolume(delta) => {
-                let delta_float = (delta as f32).abs() / 100.0;
-                let sign = if delta >= 0 { "+" } else { "-" };
-                let result = Command::new("wpctl")
-                    .args([
-                        "set-volume",
-                        "-l",
-                        "1.0",
-                        "@DEFAULT_AUDIO_SINK@",
-                        &format!("{:.2}{}", delta_float, sign),
-                    ])
-                    .output();
-
-                if let Err(e) = result {
-                    error!("Failed to adjust sink volume: {}", e);
-                }
-            }
-            AudioCommand::AdjustSourceVolume(delta) => {
-                let delta_float = (delta as f32).abs() / 100.0;
-                let sign = if delta >= 0 { "+" } else { "-" };
-                let result = Command::new("wpctl")
-                    .args([
-                        "set-volume",
-                        "-l",
-                        "1.0",
-                        "@DEFAULT_AUDIO_SOURCE@",
-                        &format!("{:.2}{}", delta_float, sign),
-                    ])
-                    .output();
-
-                if let Err(e) = result {
-                    error!("Failed to adjust source volume: {}", e);
-                }
-            }
+        if self.commands.send(command).is_err() {
+            error!("Audio listener is not running, dropping command");
+            return;
+        }
+        // The listener drains the socket whenever it wakes up, so a full
+        // socket already holds a pending wake-up.
+        match (&*self.waker).write(&[1]) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+            Err(err) => error!("Failed to wake up the audio listener: {err}"),
         }
     }
+
+    /// Set the highest volume, as percentage, that commands may set.
+    ///
+    /// Values above 100 allow boosting quiet devices. Volumes set above the
+    /// limit by other applications are left alone.
+    pub fn set_max_volume(&self, percent: u16) {
+        self.max_volume.store(percent.max(1), Ordering::Relaxed);
+    }
 }
 
 impl Default for AudioSubscriber {
@@ -197,40 +285,85 @@ impl Default for AudioSubscriber {
     }
 }
 
-/// Convert PulseAudio volume to percentage (0-100).
-fn volume_to_percent(volume: Volume) -> u8 {
+/// Volume limit until the shell sets one.
+const DEFAULT_MAX_VOLUME: u16 = 100;
+
+/// Convert PulseAudio volume to percentage, keeping boosted levels.
+fn volume_to_percent(volume: Volume) -> u16 {
     let ratio = volume.0 as f64 / Volume::NORMAL.0 as f64;
-    (ratio * 100.0).round().clamp(0.0, 100.0) as u8
+    (ratio * 100.0).round().clamp(0.0, u16::MAX as f64) as u16
+}
+
+/// Application name of a stream, falling back to the stream name.
+fn app_name(proplist: &Proplist, stream_name: Option<&str>) -> String {
+    proplist
+        .get_str(APPLICATION_NAME)
+        .or_else(|| stream_name.map(String::from))
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// Icon name of a stream's application, falling back to its executable.
+fn app_icon(proplist: &Proplist) -> Option<String> {
+    proplist
+        .get_str(APPLICATION_ICON_NAME)
+        .or_else(|| proplist.get_str(APPLICATION_PROCESS_BINARY))
+}
+
+/// Report that the listener could not connect to PulseAudio.
+fn unavailable(status: &Mutable<ServiceStatus>, reason: &str) {
+    warn!("{}, audio service unavailable", reason);
+    *status.lock_mut() = ServiceStatus::Unavailable;
 }
 
 /// Start the PulseAudio event listener thread.
-fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
+///
+/// The thread also applies the commands received on `command_rx`, limited
+/// to `max_volume`. It sleeps until PulseAudio reports something or a byte
+/// arrives on `wake_rx`.
+fn start_listener(
+    data: Mutable<AudioData>,
+    status: Mutable<ServiceStatus>,
+    command_rx: mpsc::Receiver<AudioCommand>,
+    wake_rx: UnixStream,
+    max_volume: Arc<AtomicU16>,
+) {
     thread::spawn(move || {
-        let mut proplist = Proplist::new().expect("Failed to create PulseAudio proplist");
+        let Some(mut proplist) = Proplist::new() else {
+            unavailable(&status, "Failed to create PulseAudio proplist");
+            return;
+        };
         let _ = proplist.set_str(APPLICATION_NAME, "gpuishell");
 
-        let mut mainloop = Mainloop::new().expect("Failed to create PulseAudio mainloop");
+        let Some(mut mainloop) = Mainloop::new() else {
+            unavailable(&status, "Failed to create PulseAudio mainloop");
+            return;
+        };
 
-        let mut context = Context::new_with_proplist(&mainloop, "gpuishell", &proplist)
-            .expect("Failed to create PulseAudio context");
+        let Some(mut context) = Context::new_with_proplist(&mainloop, "gpuishell", &proplist)
+        else {
+            unavailable(&status, "Failed to create PulseAudio context");
+            return;
+        };
 
-        context
-            .connect(None, FlagSet::NOFLAGS, None)
-            .expect("Failed to connect to PulseAudio");
+        if let Err(err) = context.connect(None, FlagSet::NOFLAGS, None) {
+            unavailable(
+                &status,
+                &format!("Failed to connect to PulseAudio: {}", err),
+            );
+            return;
+        }
 
         // Wait for context to be ready
         loop {
             match mainloop.iterate(true) {
                 IterateResult::Quit(_) | IterateResult::Err(_) => {
-                    error!("PulseAudio mainloop error during connect, stopping listener");
-                    *status.lock_mut() = ServiceStatus::Error(None);
+                    unavailable(&status, "PulseAudio mainloop error during connect");
                     return;
                 }
                 IterateResult::Success(_) => match context.get_state() {
                     context::State::Ready => break,
                     context::State::Failed | context::State::Terminated => {
-                        error!("PulseAudio context failed or terminated, stopping listener");
-                        *status.lock_mut() = ServiceStatus::Error(None);
+                        unavailable(&status, "PulseAudio context failed or terminated");
                         return;
                     }
                     _ => {}
@@ -255,15 +388,14 @@ fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
                 let local_data = local_data.clone();
                 let pending = pending.clone();
                 introspector.get_sink_info_by_name("@DEFAULT_SINK@", move |result| match result {
-                    libpulse_binding::callbacks::ListResult::Item(sink) => {
+                    ListResult::Item(sink) => {
                         let volume = volume_to_percent(sink.volume.avg());
                         let muted = sink.mute;
                         let mut data = local_data.borrow_mut();
                         data.sink_volume = volume;
                         data.sink_muted = muted;
                     }
-                    libpulse_binding::callbacks::ListResult::End
-                    | libpulse_binding::callbacks::ListResult::Error => {
+                    ListResult::End | ListResult::Error => {
                         pending.set(pending.get().saturating_sub(1));
                     }
                 });
@@ -280,7 +412,7 @@ fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
                 let pending = pending.clone();
                 introspector.get_source_info_by_name("@DEFAULT_SOURCE@", move |result| {
                     match result {
-                        libpulse_binding::callbacks::ListResult::Item(source) => {
+                        ListResult::Item(source) => {
                             // Skip monitor sources (they mirror sinks)
                             if source.monitor_of_sink.is_none() {
                                 let volume = volume_to_percent(source.volume.avg());
@@ -290,8 +422,7 @@ fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
                                 data.source_muted = muted;
                             }
                         }
-                        libpulse_binding::callbacks::ListResult::End
-                        | libpulse_binding::callbacks::ListResult::Error => {
+                        ListResult::End | ListResult::Error => {
                             pending.set(pending.get().saturating_sub(1));
                         }
                     }
@@ -299,13 +430,197 @@ fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
             }
         };
 
+        // Device lists are collected here and swapped in once complete.
+        let sinks: Rc<RefCell<Vec<AudioDevice>>> = Rc::new(RefCell::new(Vec::new()));
+        let sources: Rc<RefCell<Vec<AudioDevice>>> = Rc::new(RefCell::new(Vec::new()));
+
+        let query_devices = {
+            let introspector = context.introspect();
+            let local_data = local_data.clone();
+            let pending = pending_queries.clone();
+            move || {
+                pending.set(pending.get() + 3);
+
+                {
+                    let local_data = local_data.clone();
+                    let pending = pending.clone();
+                    introspector.get_server_info(move |info| {
+                        let mut data = local_data.borrow_mut();
+                        data.default_sink = info
+                            .default_sink_name
+                            .as_deref()
+                            .unwrap_or_default()
+                            .to_string();
+                        data.default_source = info
+                            .default_source_name
+                            .as_deref()
+                            .unwrap_or_default()
+                            .to_string();
+                        pending.set(pending.get().saturating_sub(1));
+                    });
+                }
+
+                {
+                    let local_data = local_data.clone();
+                    let pending = pending.clone();
+                    let sinks = sinks.clone();
+                    sinks.borrow_mut().clear();
+                    introspector.get_sink_info_list(move |result| match result {
+                        ListResult::Item(sink) => {
+                            sinks.borrow_mut().push(AudioDevice {
+                                index: sink.index,
+                                name: sink.name.as_deref().unwrap_or_default().to_string(),
+                                description: sink
+                                    .description
+                                    .as_deref()
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                port: sink
+                                    .active_port
+                                    .as_ref()
+                                    .and_then(|port| port.description.as_deref())
+                                    .map(String::from),
+                                is_default: false,
+                                running: sink.state == SinkState::Running,
+                            });
+                        }
+                        ListResult::End | ListResult::Error => {
+                            local_data.borrow_mut().sinks = sinks.take();
+                            pending.set(pending.get().saturating_sub(1));
+                        }
+                    });
+                }
+
+                {
+                    let local_data = local_data.clone();
+                    let pending = pending.clone();
+                    let sources = sources.clone();
+                    sources.borrow_mut().clear();
+                    introspector.get_source_info_list(move |result| match result {
+                        // Skip monitor sources (they mirror sinks)
+                        ListResult::Item(source) if source.monitor_of_sink.is_none() => {
+                            sources.borrow_mut().push(AudioDevice {
+                                index: source.index,
+                                name: source.name.as_deref().unwrap_or_default().to_string(),
+                                description: source
+                                    .description
+                                    .as_deref()
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                port: source
+                                    .active_port
+                                    .as_ref()
+                                    .and_then(|port| port.description.as_deref())
+                                    .map(String::from),
+                                is_default: false,
+                                running: source.state == SourceState::Running,
+                            });
+                        }
+                        ListResult::Item(_) => {}
+                        ListResult::End | ListResult::Error => {
+                            local_data.borrow_mut().sources = sources.take();
+                            pending.set(pending.get().saturating_sub(1));
+                        }
+                    });
+                }
+            }
+        };
+
+        // Stream lists, collected like the device lists.
+        let playback: Rc<RefCell<Vec<AudioStream>>> = Rc::new(RefCell::new(Vec::new()));
+        let recording: Rc<RefCell<Vec<AudioStream>>> = Rc::new(RefCell::new(Vec::new()));
+
+        let query_streams = {
+            let introspector = context.introspect();
+            let local_data = local_data.clone();
+            let pending = pending_queries.clone();
+            move || {
+                pending.set(pending.get() + 2);
+
+                {
+                    let local_data = local_data.clone();
+                    let pending = pending.clone();
+                    let playback = playback.clone();
+                    playback.borrow_mut().clear();
+                    introspector.get_sink_input_info_list(move |result| match result {
+                        ListResult::Item(input) => {
+                            playback.borrow_mut().push(AudioStream {
+                                kind: StreamKind::Playback,
+                                index: input.index,
+                                app_name: app_name(&input.proplist, input.name.as_deref()),
+                                icon: app_icon(&input.proplist),
+                                media_name: input.proplist.get_str(MEDIA_NAME),
+                                volume: volume_to_percent(input.volume.avg()),
+                                muted: input.mute,
+                                device: input.sink,
+                            });
+                        }
+                        ListResult::End | ListResult::Error => {
+                            local_data.borrow_mut().playback_streams = playback.take();
+                            pending.set(pending.get().saturating_sub(1));
+                        }
+                    });
+                }
+
+                {
+                    let local_data = local_data.clone();
+                    let pending = pending.clone();
+                    let recording = recording.clone();
+                    recording.borrow_mut().clear();
+                    introspector.get_source_output_info_list(move |result| match result {
+                        // Skip level meters and similar streams without a volume
+                        ListResult::Item(output) if output.has_volume => {
+                            recording.borrow_mut().push(AudioStream {
+                                kind: StreamKind::Recording,
+                                index: output.index,
+                                app_name: app_name(&output.proplist, output.name.as_deref()),
+                                icon: app_icon(&output.proplist),
+                                media_name: output.proplist.get_str(MEDIA_NAME),
+                                volume: volume_to_percent(output.volume.avg()),
+                                muted: output.mute,
+                                device: output.source,
+                            });
+                        }
+                        ListResult::Item(_) => {}
+                        ListResult::End | ListResult::Error => {
+                            local_data.borrow_mut().recording_streams = recording.take();
+                            pending.set(pending.get().saturating_sub(1));
+                        }
+                    });
+                }
+            }
+        };
+
+        // Mark the default devices once all queries have completed.
+        let mark_defaults = {
+            let local_data = local_data.clone();
+            move || {
+                let mut data = local_data.borrow_mut();
+                let AudioData {
+                    sinks,
+                    sources,
+                    default_sink,
+                    default_source,
+                    ..
+                } = &mut *data;
+                for sink in sinks.iter_mut() {
+                    sink.is_default = sink.name == *default_sink;
+                }
+                for source in sources.iter_mut() {
+                    source.is_default = source.name == *default_source;
+                }
+            }
+        };
+
         // Fetch initial audio data
         query_sink();
         query_source();
+        query_devices();
+        query_streams();
 
-        // Process initial queries with non-blocking iterations
+        // Wait for the initial queries to complete
         while pending_queries.get() > 0 {
-            match mainloop.iterate(false) {
+            match mainloop.iterate(true) {
                 IterateResult::Quit(_) | IterateResult::Err(_) => {
                     error!("PulseAudio mainloop error during initial query, stopping listener");
                     *status.lock_mut() = ServiceStatus::Error(None);
@@ -317,6 +632,7 @@ fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
 
         // Update shared state with initial data
         {
+            mark_defaults();
             let current = local_data.borrow().clone();
             debug!(
                 "Initial audio state: sink={}% (muted={}), source={}% (muted={})",
@@ -335,6 +651,8 @@ fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
         context.subscribe(
             InterestMaskSet::SINK
                 .union(InterestMaskSet::SOURCE)
+                .union(InterestMaskSet::SINK_INPUT)
+                .union(InterestMaskSet::SOURCE_OUTPUT)
                 .union(InterestMaskSet::SERVER),
             |success| {
                 if !success {
@@ -349,29 +667,69 @@ fn start_listener(data: Mutable<AudioData>, status: Mutable<ServiceStatus>) {
             needs_refresh_cb.set(true);
         })));
 
-        // Main event loop
+        let mut introspector = context.introspect();
+
+        // Wake up when another thread queues a command. The byte only ends
+        // the wait; the commands are read from `command_rx` below.
+        if let Err(err) = wake_rx.set_nonblocking(true) {
+            error!("Failed to set up audio command wake-ups: {}", err);
+        }
+        let wake_fd = wake_rx.as_raw_fd();
+        let Some(_wake_event) = mainloop.new_io_event(
+            wake_fd,
+            IoEventFlagSet::INPUT,
+            Box::new(move |_, _, _| {
+                let mut buf = [0u8; 64];
+                while matches!((&wake_rx).read(&mut buf), Ok(n) if n > 0) {}
+            }),
+        ) else {
+            error!("Failed to watch for audio commands, stopping listener");
+            *status.lock_mut() = ServiceStatus::Error(None);
+            return;
+        };
+
+        // Main event loop. Replies to pending queries arrive as events too,
+        // so the loop always blocks until there is something to do.
         loop {
-            // Use non-blocking iteration when queries are pending,
-            // otherwise block waiting for events
-            let has_pending = pending_queries.get() > 0 || needs_refresh.get();
-            match mainloop.iterate(!has_pending) {
+            match mainloop.iterate(true) {
                 IterateResult::Quit(_) | IterateResult::Err(_) => {
                     error!("PulseAudio mainloop error in event loop, stopping listener");
                     *status.lock_mut() = ServiceStatus::Error(None);
                     return;
                 }
                 IterateResult::Success(_) => {
-                    // Check if we need to refresh due to a subscription event
-                    if needs_refresh.get() {
+                    // Apply queued commands, merging bursts of volume changes
+                    let queued: Vec<_> = command_rx.try_iter().collect();
+                    if !queued.is_empty() {
+                        let limit = max_volume.load(Ordering::Relaxed);
+                        let mut local = local_data.borrow_mut();
+                        for command in commands::coalesce(queued, limit) {
+                            debug!("Applying audio command: {:?}", command);
+                            commands::apply(
+                                &mut context,
+                                &mut introspector,
+                                &mut local,
+                                limit,
+                                command,
+                            );
+                        }
+                    }
+
+                    // Check if we need to refresh due to a subscription event.
+                    // Wait for running queries so device lists do not mix.
+                    if needs_refresh.get() && pending_queries.get() == 0 {
                         needs_refresh.set(false);
 
                         // Fire off queries
                         query_sink();
                         query_source();
+                        query_devices();
+                        query_streams();
                     }
 
                     // If all pending queries completed, check for changes
                     if pending_queries.get() == 0 {
+                        mark_defaults();
                         let local = local_data.borrow().clone();
                         let current = data.lock_ref().clone();
                         if local != current {
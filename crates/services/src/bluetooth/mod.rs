@@ -28,7 +28,7 @@ use crate::ServiceStatus;
 pub struct BluetoothSubscriber {
     data: Mutable<BluetoothData>,
     status: Mutable<ServiceStatus>,
-    conn: zbus::Connection,
+    conn: Option<zbus::Connection>,
 }
 
 impl BluetoothSubscriber {
@@ -54,7 +54,20 @@ impl BluetoothSubscriber {
         // Start the D-Bus listener
         start_listener(data.clone(), status.clone(), conn.clone());
 
-        Ok(Self { data, status, conn })
+        Ok(Self {
+            data,
+            status,
+            conn: Some(conn),
+        })
+    }
+
+    /// Fallback subscriber when the system bus is unavailable.
+    pub fn disabled() -> Self {
+        Self {
+            data: Mutable::new(BluetoothData::default()),
+            status: Mutable::new(ServiceStatus::Unavailable),
+            conn: None,
+        }
     }
 
     /// Get a signal that emits when Bluetooth state changes.
@@ -72,9 +85,16 @@ impl BluetoothSubscriber {
         self.status.get_cloned()
     }
 
+    /// Connection to the bus, or an error when the service is unavailable.
+    fn conn(&self) -> anyhow::Result<&zbus::Connection> {
+        self.conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Bluetooth service is unavailable"))
+    }
+
     /// Execute a Bluetooth command.
     pub async fn dispatch(&self, command: BluetoothCommand) -> anyhow::Result<()> {
-        let bluetooth = BluetoothDbus::new(&self.conn).await?;
+        let bluetooth = BluetoothDbus::new(self.conn()?).await?;
 
         match command {
             BluetoothCommand::Toggle => {
@@ -98,7 +118,7 @@ impl BluetoothSubscriber {
                 bluetooth.start_discovery().await?;
 
                 // Auto-stop discovery after 15 seconds
-                let conn = self.conn.clone();
+                let conn = self.conn()?.clone();
                 tokio::spawn(async move {
                     tokio::time::sleep(Duration::from_secs(15)).await;
                     if let Ok(bt) = BluetoothDbus::new(&conn).await {
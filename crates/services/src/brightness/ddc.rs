@@ -0,0 +1,267 @@
+//! External monitors controlled over DDC/CI.
+//!
+//! Requires the `ddcutil` binary, which talks to the monitors through
+//! `/dev/i2c-*` (the `i2c-dev` module must be loaded and the devices
+//! writable by the user). Detection and every transfer take a noticeable
+//! time, so all calls run on a worker thread.
+
+use std::collections::BTreeMap;
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
+
+use anyhow::{Context, Result, bail};
+use futures_signals::signal::Mutable;
+use tracing::{debug, info, warn};
+
+use super::{BrightnessData, BrightnessDevice, BrightnessKind};
+use crate::ServiceStatus;
+
+/// VCP feature code of the brightness control.
+const BRIGHTNESS_VCP: &str = "10";
+
+/// Id prefix of DDC/CI devices, followed by the I2C bus number.
+const ID_PREFIX: &str = "ddc/i2c-";
+
+/// A request to the DDC/CI worker.
+#[derive(Debug)]
+pub(super) enum DdcRequest {
+    /// Set the brightness of the monitor on an I2C bus.
+    Set { bus: u32, value: u32 },
+    /// Look for monitors again.
+    Detect,
+}
+
+/// A monitor found by `ddcutil detect`.
+#[derive(Debug)]
+struct Display {
+    bus: u32,
+    model: String,
+}
+
+/// I2C bus number of a DDC/CI device id.
+pub(super) fn bus_of(id: &str) -> Option<u32> {
+    id.strip_prefix(ID_PREFIX)?.parse().ok()
+}
+
+/// Start the worker thread: detect monitors, add them to `data`, then
+/// apply the requests received on the returned channel.
+pub(super) fn start(
+    data: Mutable<BrightnessData>,
+    status: Mutable<ServiceStatus>,
+) -> mpsc::Sender<DdcRequest> {
+    let (requests, request_rx) = mpsc::channel::<DdcRequest>();
+
+    thread::spawn(move || {
+        detect(&data, &status);
+
+        // Of a burst, apply only the last value per monitor and detect once.
+        while let Ok(first) = request_rx.recv() {
+            let mut latest = BTreeMap::new();
+            let mut redetect = false;
+            for request in std::iter::once(first).chain(request_rx.try_iter()) {
+                match request {
+                    DdcRequest::Set { bus, value } => {
+                        latest.insert(bus, value);
+                    }
+                    DdcRequest::Detect => redetect = true,
+                }
+            }
+
+            for (bus, value) in latest {
+                if let Err(e) = set_brightness(bus, value) {
+                    warn!("Failed to set brightness on i2c-{}: {:#}", bus, e);
+                }
+                // Monitors may round or ignore the value; show what they report.
+                match get_brightness(bus) {
+                    Ok((current, max)) => update(&data, bus, current, max),
+                    Err(e) => debug!("Failed to read brightness on i2c-{}: {:#}", bus, e),
+                }
+            }
+
+            if redetect {
+                detect(&data, &status);
+            }
+        }
+    });
+
+    requests
+}
+
+/// Detect monitors and replace the external devices in `data` with them.
+fn detect(data: &Mutable<BrightnessData>, status: &Mutable<ServiceStatus>) {
+    let displays = match ddcutil(&["detect", "--brief"]) {
+        Ok(output) => parse_detect(&output),
+        Err(e) => {
+            warn!("DDC/CI brightness unavailable: {:#}", e);
+            return;
+        }
+    };
+
+    let mut devices = Vec::new();
+    for display in displays {
+        match get_brightness(display.bus) {
+            Ok((current, max)) => devices.push(BrightnessDevice {
+                id: format!("{}{}", ID_PREFIX, display.bus),
+                name: if display.model.is_empty() {
+                    "External display".to_string()
+                } else {
+                    display.model
+                },
+                kind: BrightnessKind::External,
+                current,
+                max,
+            }),
+            Err(e) => debug!("Skipping monitor on i2c-{}: {:#}", display.bus, e),
+        }
+    }
+
+    let external = |device: &&BrightnessDevice| device.kind == BrightnessKind::External;
+    if data
+        .lock_ref()
+        .devices
+        .iter()
+        .filter(external)
+        .eq(devices.iter())
+    {
+        return;
+    }
+
+    info!("Found {} DDC/CI monitor(s)", devices.len());
+    let mut data = data.lock_mut();
+    data.devices
+        .retain(|device| device.kind != BrightnessKind::External);
+    data.devices.extend(devices);
+    *status.lock_mut() = if data.devices.is_empty() {
+        ServiceStatus::Unavailable
+    } else {
+        ServiceStatus::Active
+    };
+}
+
+/// Store the brightness a monitor reports.
+fn update(data: &Mutable<BrightnessData>, bus: u32, current: u32, max: u32) {
+    let id = format!("{}{}", ID_PREFIX, bus);
+    if let Some(device) = data.lock_mut().device_mut(&id) {
+        device.current = current;
+        device.max = max;
+    }
+}
+
+/// Run `ddcutil` and return its standard output.
+fn ddcutil(args: &[&str]) -> Result<String> {
+    let output = Command::new("ddcutil")
+        .args(args)
+        .output()
+        .context("Failed to run ddcutil")?;
+    if !output.status.success() {
+        bail!(
+            "ddcutil {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Current and maximum brightness of the monitor on a bus.
+fn get_brightness(bus: u32) -> Result<(u32, u32)> {
+    let bus = bus.to_string();
+    let output = ddcutil(&["getvcp", BRIGHTNESS_VCP, "--bus", &bus, "--brief"])?;
+    parse_getvcp(&output).context("Unexpected ddcutil getvcp output")
+}
+
+fn set_brightness(bus: u32, value: u32) -> Result<()> {
+    let (bus, value) = (bus.to_string(), value.to_string());
+    ddcutil(&["setvcp", BRIGHTNESS_VCP, &value, "--bus", &bus])?;
+    Ok(())
+}
+
+/// Parse `ddcutil detect --brief`, skipping invalid displays.
+///
+/// Each display is a `Display N` line followed by indented fields such as
+/// `I2C bus:  /dev/i2c-5` and `Monitor:  DEL:DELL U2719D:ABC123`.
+fn parse_detect(output: &str) -> Vec<Display> {
+    let mut displays = Vec::new();
+    let mut valid = false;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            valid = line.starts_with("Display ");
+            continue;
+        }
+        if !valid {
+            continue;
+        }
+
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("I2C bus:") {
+            if let Some(bus) = path.trim().strip_prefix("/dev/i2c-")
+                && let Ok(bus) = bus.parse()
+            {
+                displays.push(Display {
+                    bus,
+                    model: String::new(),
+                });
+            }
+        } else if let Some(monitor) = line.strip_prefix("Monitor:")
+            && let Some(display) = displays.last_mut()
+        {
+            // Manufacturer, model and serial number, separated by colons
+            display.model = monitor.trim().split(':').nth(1).unwrap_or("").to_string();
+        }
+    }
+    displays
+}
+
+/// Parse `ddcutil getvcp --brief` output like `VCP 10 C 50 100`.
+fn parse_getvcp(output: &str) -> Option<(u32, u32)> {
+    let fields: Vec<&str> = output.split_whitespace().collect();
+    match fields.as_slice() {
+        ["VCP", _, "C", current, max, ..] => Some((current.parse().ok()?, max.parse().ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_detect() {
+        let output = "\
+Display 1
+   I2C bus:  /dev/i2c-5
+   DRM connector:           card1-DP-1
+   Monitor:                 DEL:DELL U2719D:ABC123
+
+Invalid display
+   I2C bus:  /dev/i2c-7
+   Monitor:                 XYZ:Broken:0
+
+Display 2
+   I2C bus:  /dev/i2c-6
+";
+        let displays = parse_detect(output);
+        let found: Vec<_> = displays
+            .iter()
+            .map(|display| (display.bus, display.model.as_str()))
+            .collect();
+        assert_eq!(found, vec![(5, "DELL U2719D"), (6, "")]);
+    }
+
+    #[test]
+    fn test_parse_detect_empty() {
+        assert!(parse_detect("").is_empty());
+        assert!(parse_detect("No displays found.\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_getvcp() {
+        assert_eq!(parse_getvcp("VCP 10 C 50 100\n"), Some((50, 100)));
+        assert_eq!(parse_getvcp("VCP 10 C 0 255"), Some((0, 255)));
+        assert_eq!(parse_getvcp("VCP 10 ERR"), None);
+        assert_eq!(parse_getvcp("VCP 10 SNC x01"), None);
+        assert_eq!(parse_getvcp(""), None);
+    }
+}
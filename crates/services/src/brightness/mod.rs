@@ -1,29 +1,51 @@
-//! Brightness service for backlight control.
+//! Brightness service for backlights, keyboard backlights and monitors.
 //!
 //! This module provides a reactive subscriber for monitoring and controlling
-//! display backlight brightness. Uses udev for device discovery and change
-//! monitoring, and D-Bus (systemd-logind) for unprivileged brightness control.
+//! the brightness of every display backlight and keyboard backlight, using
+//! udev for device discovery and change monitoring, and D-Bus
+//! (systemd-logind) for unprivileged brightness control. External monitors
+//! can be added through DDC/CI.
 
-use std::path::{Path, PathBuf};
+mod ddc;
+mod sysfs;
 
-use anyhow::Result;
+use std::sync::{Arc, Mutex, mpsc};
+
+use anyhow::{Context, Result, anyhow, bail};
 use futures_signals::signal::{Mutable, MutableSignalCloned};
-use tokio::io::unix::AsyncFd;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, info, warn};
 use zbus::proxy;
 
 use crate::ServiceStatus;
 
-/// Brightness data state.
-#[derive(Debug, Clone, Default)]
-pub struct BrightnessData {
+/// What a brightness device controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrightnessKind {
+    /// A built-in display backlight.
+    Backlight,
+    /// A keyboard backlight LED.
+    Keyboard,
+    /// An external monitor controlled over DDC/CI.
+    External,
+}
+
+/// A device whose brightness can be changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrightnessDevice {
+    /// Stable id used in commands, e.g. `backlight/intel_backlight`,
+    /// `leds/tpacpi::kbd_backlight` or `ddc/i2c-5`.
+    pub id: String,
+    /// Human-readable name (e.g. "Display", "Keyboard" or a monitor model).
+    pub name: String,
+    /// What the device controls.
+    pub kind: BrightnessKind,
     /// Current brightness value (raw).
     pub current: u32,
     /// Maximum brightness value.
     pub max: u32,
 }
 
-impl BrightnessData {
+impl BrightnessDevice {
     /// Get brightness as a percentage (0-100).
     pub fn percentage(&self) -> u8 {
         if self.max == 0 {
@@ -44,85 +66,134 @@ impl BrightnessData {
     }
 }
 
-/// Commands for controlling brightness.
+/// Brightness data state.
+#[derive(Debug, Clone, Default)]
+pub struct BrightnessData {
+    /// All controllable devices: backlights and keyboard backlights first,
+    /// then external monitors.
+    pub devices: Vec<BrightnessDevice>,
+}
+
+impl BrightnessData {
+    /// Find a device by id.
+    pub fn device(&self, id: &str) -> Option<&BrightnessDevice> {
+        self.devices.iter().find(|device| device.id == id)
+    }
+
+    fn device_mut(&mut self, id: &str) -> Option<&mut BrightnessDevice> {
+        self.devices.iter_mut().find(|device| device.id == id)
+    }
+
+    /// The display brightness keys control: the first built-in backlight,
+    /// or else the first external monitor.
+    pub fn primary(&self) -> Option<&BrightnessDevice> {
+        let find = |kind| self.devices.iter().find(|device| device.kind == kind);
+        find(BrightnessKind::Backlight).or_else(|| find(BrightnessKind::External))
+    }
+}
+
+/// Commands for controlling brightness, addressed by device id.
 #[derive(Debug, Clone)]
 pub enum BrightnessCommand {
     /// Set brightness to an absolute value.
-    Set(u32),
+    Set { device: String, value: u32 },
     /// Set brightness as a percentage (0-100).
-    SetPercent(u8),
+    SetPercent { device: String, percent: u8 },
     /// Increase brightness by a percentage.
-    Increase(u8),
+    Increase { device: String, percent: u8 },
     /// Decrease brightness by a percentage.
-    Decrease(u8),
+    Decrease { device: String, percent: u8 },
+}
+
+impl BrightnessCommand {
+    /// Id of the device the command applies to.
+    pub fn device(&self) -> &str {
+        match self {
+            Self::Set { device, .. }
+            | Self::SetPercent { device, .. }
+            | Self::Increase { device, .. }
+            | Self::Decrease { device, .. } => device,
+        }
+    }
+
+    /// Raw brightness value the command sets on a device.
+    fn target(&self, device: &BrightnessDevice) -> u32 {
+        let (current, max) = (device.current, device.max);
+        let delta = |p: u8| ((p as f64 / 100.0) * max as f64).round() as u32;
+        match *self {
+            Self::Set { value, .. } => value.min(max),
+            Self::SetPercent { percent, .. } => {
+                ((percent.min(100) as f64 / 100.0) * max as f64).round() as u32
+            }
+            Self::Increase { percent, .. } => current.saturating_add(delta(percent)).min(max),
+            Self::Decrease { percent, .. } => {
+                // Don't turn a built-in display off; keyboard lights and
+                // monitors may go down to 0.
+                let min = u32::from(device.kind == BrightnessKind::Backlight);
+                current.saturating_sub(delta(percent)).max(min)
+            }
+        }
+    }
 }
 
 /// Event-driven brightness subscriber.
 ///
-/// This subscriber monitors backlight brightness changes using udev
-/// and provides reactive state updates through `futures_signals`.
+/// This subscriber monitors brightness changes using udev and provides
+/// reactive state updates through `futures_signals`.
 #[derive(Debug, Clone)]
 pub struct BrightnessSubscriber {
     data: Mutable<BrightnessData>,
     status: Mutable<ServiceStatus>,
-    device_path: Option<PathBuf>,
-    device_name: Option<String>,
     conn: Option<zbus::Connection>,
+    ddc: Arc<Mutex<Option<mpsc::Sender<ddc::DdcRequest>>>>,
 }
 
 impl BrightnessSubscriber {
     /// Create a new brightness subscriber and start monitoring.
     ///
-    /// Returns Ok even if no backlight device exists (graceful degradation).
+    /// Returns Ok even if no device exists (graceful degradation).
     pub async fn new() -> Result<Self> {
-        // Find backlight device
-        let device_path = find_backlight_device();
-
-        let (data, status, device_name, conn) = match &device_path {
-            Some(path) => {
-                let brightness_data = read_brightness(path)?;
-                let name = path.file_name().and_then(|n| n.to_str()).map(String::from);
-                let conn = zbus::Connection::system().await.ok();
+        let devices = sysfs::find_devices();
+        let has_devices = !devices.is_empty();
 
+        let (status, conn) = if !has_devices {
+            warn!("No backlight device found");
+            (ServiceStatus::Unavailable, None)
+        } else {
+            for device in &devices {
                 info!(
-                    "Brightness service initialized: {} (max: {})",
-                    brightness_data.current, brightness_data.max
+                    "Brightness device {}: {} (max: {})",
+                    device.id, device.current, device.max
                 );
-
-                (
-                    Mutable::new(brightness_data),
-                    Mutable::new(ServiceStatus::Active),
-                    name,
-                    conn,
-                )
-            }
-            None => {
-                warn!("No backlight device found");
-                (
-                    Mutable::new(BrightnessData::default()),
-                    Mutable::new(ServiceStatus::Unavailable),
-                    None,
-                    None,
-                )
             }
+            (ServiceStatus::Active, zbus::Connection::system().await.ok())
         };
 
         let subscriber = Self {
-            data: data.clone(),
-            status: status.clone(),
-            device_path: device_path.clone(),
-            device_name,
+            data: Mutable::new(BrightnessData { devices }),
+            status: Mutable::new(status),
             conn,
+            ddc: Arc::new(Mutex::new(None)),
         };
 
-        // Start listener if device exists
-        if let Some(path) = device_path {
-            start_listener(data, path);
+        // Start listener if devices exist
+        if has_devices {
+            sysfs::start_listener(subscriber.data.clone());
         }
 
         Ok(subscriber)
     }
 
+    /// Fallback subscriber when the brightness devices cannot be read.
+    pub fn disabled() -> Self {
+        Self {
+            data: Mutable::new(BrightnessData::default()),
+            status: Mutable::new(ServiceStatus::Unavailable),
+            conn: None,
+            ddc: Arc::new(Mutex::new(None)),
+        }
+    }
+
     /// Get a signal that emits when brightness changes.
     pub fn subscribe(&self) -> MutableSignalCloned<BrightnessData> {
         self.data.signal_cloned()
@@ -138,62 +209,84 @@ impl BrightnessSubscriber {
         self.status.get_cloned()
     }
 
-    /// Check if a backlight device is available.
+    /// Check if any brightness device is available.
     pub fn is_available(&self) -> bool {
-        self.device_path.is_some()
+        !self.data.lock_ref().devices.is_empty()
+    }
+
+    /// Look for external monitors supporting DDC/CI and add them as
+    /// devices. Requires `ddcutil`; detection runs in the background.
+    ///
+    /// Calling this again has no effect.
+    pub fn enable_ddc(&self) {
+        let mut ddc = self.ddc.lock().unwrap();
+        if ddc.is_none() {
+            *ddc = Some(ddc::start(self.data.clone(), self.status.clone()));
+        }
+    }
+
+    /// Look for DDC/CI monitors again, after monitors were connected or
+    /// disconnected. Does nothing unless DDC/CI is enabled.
+    pub fn redetect_ddc(&self) {
+        if let Some(sender) = self.ddc.lock().unwrap().as_ref() {
+            let _ = sender.send(ddc::DdcRequest::Detect);
+        }
     }
 
     /// Execute a brightness command.
     pub async fn dispatch(&self, command: BrightnessCommand) -> Result<()> {
-        let (device_name, conn) = match (&self.device_name, &self.conn) {
-            (Some(name), Some(conn)) => (name, conn),
-            _ => {
-                warn!("No backlight device available");
-                return Ok(());
-            }
-        };
+        let device = self
+            .data
+            .lock_ref()
+            .device(command.device())
+            .cloned()
+            .with_context(|| format!("Unknown brightness device: {}", command.device()))?;
 
-        let (max, current) = {
-            let data = self.data.lock_ref();
-            (data.max, data.current)
-        };
-
-        let new_value = match command {
-            BrightnessCommand::Set(v) => v.min(max),
-            BrightnessCommand::SetPercent(p) => {
-                ((p.min(100) as f64 / 100.0) * max as f64).round() as u32
-            }
-            BrightnessCommand::Increase(p) => {
-                let delta = ((p as f64 / 100.0) * max as f64).round() as u32;
-                current.saturating_add(delta).min(max)
-            }
-            BrightnessCommand::Decrease(p) => {
-                let delta = ((p as f64 / 100.0) * max as f64).round() as u32;
-                current.saturating_sub(delta).max(1) // Don't go to 0
-            }
-        };
+        let new_value = command.target(&device);
 
         // Skip if no change needed
-        if new_value == current {
+        if new_value == device.current {
             return Ok(());
         }
 
-        debug!(
-            "Setting brightness to {} (device: {})",
-            new_value, device_name
-        );
+        debug!("Setting brightness of {} to {}", device.id, new_value);
 
-        let proxy = BrightnessCtrlProxy::new(conn).await?;
-        proxy
-            .set_brightness("backlight", device_name, new_value)
-            .await?;
+        match device.kind {
+            BrightnessKind::Backlight | BrightnessKind::Keyboard => {
+                self.set_sysfs(&device.id, new_value).await?
+            }
+            BrightnessKind::External => self.set_ddc(&device.id, new_value)?,
+        }
 
         // Immediately update internal state (optimistic update)
         // This prevents race conditions when clicking buttons rapidly
-        self.data.lock_mut().current = new_value;
+        if let Some(device) = self.data.lock_mut().device_mut(&device.id) {
+            device.current = new_value;
+        }
+
+        Ok(())
+    }
 
+    async fn set_sysfs(&self, id: &str, value: u32) -> Result<()> {
+        let Some(conn) = &self.conn else {
+            bail!("No system bus connection for brightness control");
+        };
+        let (subsystem, name) =
+            sysfs::split_id(id).with_context(|| format!("Invalid device id: {}", id))?;
+
+        let proxy = BrightnessCtrlProxy::new(conn).await?;
+        proxy.set_brightness(subsystem, name, value).await?;
         Ok(())
     }
+
+    fn set_ddc(&self, id: &str, value: u32) -> Result<()> {
+        let bus = ddc::bus_of(id).with_context(|| format!("Invalid device id: {}", id))?;
+        let ddc = self.ddc.lock().unwrap();
+        let sender = ddc.as_ref().context("DDC/CI is not enabled")?;
+        sender
+            .send(ddc::DdcRequest::Set { bus, value })
+            .map_err(|_| anyhow!("DDC/CI worker is not running"))
+    }
 }
 
 // D-Bus proxy for systemd-logind brightness control.
@@ -203,94 +296,6 @@ impl BrightnessSubscriber {
     interface = "org.freedesktop.login1.Session"
 )]
 trait BrightnessCtrl {
-    /// Set the brightness of a backlight device.
+    /// Set the brightness of a backlight or LED device.
     fn set_brightness(&self, subsystem: &str, name: &str, value: u32) -> zbus::Result<()>;
 }
-
-/// Find the backlight device path using udev.
-fn find_backlight_device() -> Option<PathBuf> {
-    let mut enumerator = udev::Enumerator::new().ok()?;
-    enumerator.match_subsystem("backlight").ok()?;
-
-    enumerator
-        .scan_devices()
-        .ok()?
-        .find(|d| d.subsystem().and_then(|s| s.to_str()) == Some("backlight"))
-        .map(|d| d.syspath().to_path_buf())
-}
-
-/// Read brightness data from sysfs.
-fn read_brightness(device_path: &Path) -> Result<BrightnessData> {
-    let max = std::fs::read_to_string(device_path.join("max_brightness"))?
-        .trim()
-        .parse()?;
-    let current = std::fs::read_to_string(device_path.join("actual_brightness"))?
-        .trim()
-        .parse()?;
-    Ok(BrightnessData { current, max })
-}
-
-/// Start the udev listener task for brightness changes.
-fn start_listener(data: Mutable<BrightnessData>, device_path: PathBuf) {
-    tokio::task::spawn_blocking(move || {
-        let socket = match udev::MonitorBuilder::new()
-            .and_then(|b| b.match_subsystem("backlight"))
-            .and_then(|b| b.listen())
-        {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to create udev monitor: {}", e);
-                return;
-            }
-        };
-
-        // Wrap the socket in AsyncFd for tokio async I/O
-        let async_socket = match AsyncFd::new(socket) {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to create async fd: {}", e);
-                return;
-            }
-        };
-
-        let mut current_value = data.lock_ref().current;
-
-        // Use tokio's block_on to run async code in blocking context
-        let runtime = tokio::runtime::Handle::current();
-        runtime.block_on(async {
-            loop {
-                // Wait asynchronously until the socket is readable
-                let mut guard = match async_socket.readable().await {
-                    Ok(g) => g,
-                    Err(e) => {
-                        error!("Failed to wait for readable: {}", e);
-                        break;
-                    }
-                };
-
-                // Try to read events
-                match guard.try_io(|inner| {
-                    // Drain all pending events
-                    for event in inner.get_ref().iter() {
-                        if event.event_type() == udev::EventType::Change {
-                            if let Ok(new_data) = read_brightness(&device_path) {
-                                if new_data.current != current_value {
-                                    current_value = new_data.current;
-                                    data.lock_mut().current = new_data.current;
-                                    debug!("Brightness changed: {}", new_data.current);
-                                }
-                            }
-                        }
-                    }
-                    Ok::<(), std::io::Error>(())
-                }) {
-                    Ok(_) => {}
-                    Err(_would_block) => {
-                        // False alarm, socket not actually readable yet
-                        continue;
-                    }
-                }
-            }
-        });
-    });
-}
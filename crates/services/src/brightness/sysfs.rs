@@ -0,0 +1,180 @@
+//! Backlight and keyboard backlight devices in sysfs.
+//!
+//! Devices are discovered and watched through udev. Writing goes through
+//! systemd-logind, which allows unprivileged changes for both subsystems.
+
+use std::path::Path;
+
+use anyhow::Result;
+use futures_signals::signal::Mutable;
+use tokio::io::unix::AsyncFd;
+use tracing::{debug, error};
+
+use super::{BrightnessData, BrightnessDevice, BrightnessKind};
+
+const BACKLIGHT: &str = "backlight";
+const LEDS: &str = "leds";
+
+/// Find all backlight devices and keyboard backlight LEDs.
+pub(super) fn find_devices() -> Vec<BrightnessDevice> {
+    let mut devices = Vec::new();
+    for subsystem in [BACKLIGHT, LEDS] {
+        let Some(found) = scan(subsystem) else {
+            continue;
+        };
+        for device in found {
+            let Some(sysname) = device.sysname().to_str() else {
+                continue;
+            };
+            let Some(kind) = kind_of(subsystem, sysname) else {
+                continue;
+            };
+            match read_device(subsystem, kind, sysname, device.syspath()) {
+                Ok(device) => devices.push(device),
+                Err(e) => debug!("Skipping brightness device {}: {}", sysname, e),
+            }
+        }
+    }
+    devices
+}
+
+fn scan(subsystem: &str) -> Option<Vec<udev::Device>> {
+    let mut enumerator = udev::Enumerator::new().ok()?;
+    enumerator.match_subsystem(subsystem).ok()?;
+    Some(enumerator.scan_devices().ok()?.collect())
+}
+
+/// Kind of a device, or `None` for LEDs other than keyboard backlights.
+fn kind_of(subsystem: &str, sysname: &str) -> Option<BrightnessKind> {
+    match subsystem {
+        BACKLIGHT => Some(BrightnessKind::Backlight),
+        LEDS if sysname.ends_with("kbd_backlight") => Some(BrightnessKind::Keyboard),
+        _ => None,
+    }
+}
+
+/// Subsystem and device name of a sysfs device id.
+pub(super) fn split_id(id: &str) -> Option<(&str, &str)> {
+    id.split_once('/')
+        .filter(|(subsystem, _)| [BACKLIGHT, LEDS].contains(subsystem))
+}
+
+/// Read a device from its sysfs directory.
+fn read_device(
+    subsystem: &str,
+    kind: BrightnessKind,
+    sysname: &str,
+    path: &Path,
+) -> Result<BrightnessDevice> {
+    let name = match kind {
+        BrightnessKind::Keyboard => "Keyboard",
+        _ => "Display",
+    };
+    Ok(BrightnessDevice {
+        id: format!("{}/{}", subsystem, sysname),
+        name: name.to_string(),
+        kind,
+        current: read_current(kind, path)?,
+        max: read_value(&path.join("max_brightness"))?,
+    })
+}
+
+/// Current brightness. Backlights report the hardware value separately.
+fn read_current(kind: BrightnessKind, path: &Path) -> Result<u32> {
+    match kind {
+        BrightnessKind::Backlight => read_value(&path.join("actual_brightness")),
+        _ => read_value(&path.join("brightness")),
+    }
+}
+
+fn read_value(path: &Path) -> Result<u32> {
+    Ok(std::fs::read_to_string(path)?.trim().parse()?)
+}
+
+/// Start the udev listener task for brightness changes.
+pub(super) fn start_listener(data: Mutable<BrightnessData>) {
+    tokio::task::spawn_blocking(move || {
+        let socket = match udev::MonitorBuilder::new()
+            .and_then(|b| b.match_subsystem(BACKLIGHT))
+            .and_then(|b| b.match_subsystem(LEDS))
+            .and_then(|b| b.listen())
+        {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Failed to create udev monitor: {}", e);
+                return;
+            }
+        };
+
+        // Wrap the socket in AsyncFd for tokio async I/O
+        let async_socket = match AsyncFd::new(socket) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Failed to create async fd: {}", e);
+                return;
+            }
+        };
+
+        // Use tokio's block_on to run async code in blocking context
+        let runtime = tokio::runtime::Handle::current();
+        runtime.block_on(async {
+            loop {
+                // Wait asynchronously until the socket is readable
+                let mut guard = match async_socket.readable().await {
+                    Ok(g) => g,
+                    Err(e) => {
+                        error!("Failed to wait for readable: {}", e);
+                        break;
+                    }
+                };
+
+                // Try to read events
+                match guard.try_io(|inner| {
+                    // Drain all pending events
+                    for event in inner.get_ref().iter() {
+                        if event.event_type() == udev::EventType::Change {
+                            update_device(&data, &event);
+                        }
+                    }
+                    Ok::<(), std::io::Error>(())
+                }) {
+                    Ok(_) => {}
+                    Err(_would_block) => {
+                        // False alarm, socket not actually readable yet
+                        continue;
+                    }
+                }
+            }
+        });
+    });
+}
+
+/// Re-read the device a change event is about, if it is one we track.
+fn update_device(data: &Mutable<BrightnessData>, event: &udev::Event) {
+    let (Some(subsystem), Some(sysname)) = (
+        event.subsystem().and_then(|s| s.to_str()),
+        event.sysname().to_str(),
+    ) else {
+        return;
+    };
+    let id = format!("{}/{}", subsystem, sysname);
+
+    let Some((kind, previous)) = data
+        .lock_ref()
+        .device(&id)
+        .map(|device| (device.kind, device.current))
+    else {
+        return;
+    };
+    let Ok(current) = read_current(kind, event.syspath()) else {
+        return;
+    };
+    if current == previous {
+        return;
+    }
+
+    if let Some(device) = data.lock_mut().device_mut(&id) {
+        device.current = current;
+    }
+    debug!("Brightness of {} changed: {}", id, current);
+}
@@ -6,7 +6,9 @@
 use anyhow::Result;
 use futures_signals::signal::Mutable;
 use hyprland::{
-    data::{Client, Devices, Monitors, Workspace as HWorkspace, Workspaces},
+    data::{
+        Client, Clients, Devices, FullscreenMode, Monitors, Workspace as HWorkspace, Workspaces,
+    },
     dispatch::{Dispatch, DispatchType, MonitorIdentifier, WorkspaceIdentifierWithSpecial},
     event_listener::EventListener,
     prelude::*,
@@ -15,7 +17,7 @@ use itertools::Itertools;
 use std::thread;
 use tracing::{debug, error, info};
 
-use super::types::{ActiveWindow, CompositorCommand, CompositorState, Monitor, Workspace};
+use super::types::{ActiveWindow, CompositorCommand, CompositorState, Monitor, Window, Workspace};
 
 /// Check if Hyprland is available (running).
 pub fn is_available() -> bool {
@@ -38,8 +40,8 @@ pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
         CompositorCommand::ToggleSpecialWorkspace(name) => {
             Dispatch::call(DispatchType::ToggleSpecialWorkspace(Some(name)))?;
         }
-        CompositorCommand::FocusMonitor(id) => {
-            Dispatch::call(DispatchType::FocusMonitor(MonitorIdentifier::Id(id)))?;
+        CompositorCommand::FocusMonitor(name) => {
+            Dispatch::call(DispatchType::FocusMonitor(MonitorIdentifier::Name(&name)))?;
         }
         CompositorCommand::ScrollWorkspace(dir) => {
             let d = if dir > 0 { "+1" } else { "-1" };
@@ -47,6 +49,18 @@ pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
                 WorkspaceIdentifierWithSpecial::Relative(d.to_string().parse()?),
             ))?;
         }
+        CompositorCommand::FocusWindow(id) => {
+            let selector = format!("address:{}", address(&id));
+            Dispatch::call(DispatchType::Custom("focuswindow", &selector))?;
+        }
+        CompositorCommand::CloseWindow(id) => {
+            let selector = format!("address:{}", address(&id));
+            Dispatch::call(DispatchType::Custom("closewindow", &selector))?;
+        }
+        CompositorCommand::MoveWindowToWorkspace(id, workspace) => {
+            let args = format!("{},address:{}", workspace, address(&id));
+            Dispatch::call(DispatchType::Custom("movetoworkspacesilent", &args))?;
+        }
         CompositorCommand::NextKeyboardLayout => {
             hyprland::ctl::switch_xkb_layout::call(
                 "all",
@@ -60,24 +74,64 @@ pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
     Ok(())
 }
 
-/// Fetch the complete compositor state from Hyprland.
-/// Used for initial state and occasional full refresh.
-pub fn fetch_full_state() -> Result<CompositorState> {
-    let workspaces = Workspaces::get()?
+/// Window addresses are reported with a `0x` prefix, which dispatchers
+/// expect as well.
+fn address(id: &str) -> String {
+    if id.starts_with("0x") {
+        id.to_string()
+    } else {
+        format!("0x{id}")
+    }
+}
+
+/// Fetch all mapped windows.
+fn fetch_windows() -> Result<Vec<Window>> {
+    Ok(Clients::get()?
         .into_iter()
-        .sorted_by_key(|w| w.id)
-        .map(|w| Workspace {
-            id: w.id,
-            index: w.id,
-            name: w.name,
-            monitor: w.monitor,
-            monitor_id: w.monitor_id,
-            windows: w.windows,
-            is_special: w.id < 0,
+        .filter(|c| c.mapped)
+        .map(|c| Window {
+            id: c.address.to_string(),
+            title: c.title,
+            class: c.class,
+            workspace_id: Some(c.workspace.id),
+            floating: c.floating,
+            fullscreen: c.fullscreen != FullscreenMode::None,
+            urgent: false,
+        })
+        .collect())
+}
+
+/// Refresh the window list and per-workspace window counts.
+fn refresh_windows(data: &Mutable<CompositorState>) {
+    let windows = match fetch_windows() {
+        Ok(windows) => windows,
+        Err(e) => {
+            debug!("Failed to fetch Hyprland windows: {}", e);
+            return;
+        }
+    };
+    let workspaces = Workspaces::get().ok();
+
+    let mut state = data.lock_mut();
+    // Urgency is only known from events; keep it across refreshes.
+    state.windows = windows
+        .into_iter()
+        .map(|mut window| {
+            window.urgent = state.window_by_id(&window.id).is_some_and(|old| old.urgent);
+            window
         })
         .collect();
+    for ws_data in workspaces.into_iter().flatten() {
+        if let Some(ws) = state.workspaces.iter_mut().find(|w| w.id == ws_data.id) {
+            ws.windows = ws_data.windows;
+        }
+    }
+    state.update_workspace_urgency();
+}
 
-    let monitors = Monitors::get()?
+/// Fetch all monitors.
+fn fetch_monitors() -> Result<Vec<Monitor>> {
+    Ok(Monitors::get()?
         .into_iter()
         .map(|m| Monitor {
             id: m.id,
@@ -90,10 +144,41 @@ pub fn fetch_full_state() -> Result<CompositorState> {
             y: m.y,
             scale: m.scale,
         })
+        .collect())
+}
+
+/// Refresh the monitor list after an output was added or removed.
+fn refresh_monitors(data: &Mutable<CompositorState>) {
+    match fetch_monitors() {
+        Ok(monitors) => data.lock_mut().monitors = monitors,
+        Err(e) => debug!("Failed to fetch Hyprland monitors: {}", e),
+    }
+}
+
+/// Fetch the complete compositor state from Hyprland.
+/// Used for initial state and occasional full refresh.
+pub fn fetch_full_state() -> Result<CompositorState> {
+    let workspaces = Workspaces::get()?
+        .into_iter()
+        .sorted_by_key(|w| w.id)
+        .map(|w| Workspace {
+            id: w.id,
+            index: w.id,
+            name: w.name,
+            monitor: w.monitor,
+            monitor_id: w.monitor_id,
+            windows: w.windows,
+            is_special: w.id < 0,
+            urgent: false,
+        })
         .collect();
 
+    let monitors = fetch_monitors()?;
+
     let active_workspace_id = HWorkspace::get_active().ok().map(|w| w.id);
 
+    let windows = fetch_windows()?;
+
     let active_window = Client::get_active().ok().flatten().map(|w| ActiveWindow {
         title: w.title,
         class: w.class,
@@ -114,9 +199,11 @@ pub fn fetch_full_state() -> Result<CompositorState> {
         workspaces,
         monitors,
         active_workspace_id,
+        windows,
         active_window,
         keyboard_layout,
         submap: None,
+        niri: None,
     })
 }
 
@@ -162,6 +249,7 @@ fn run_listener(data: Mutable<CompositorState>) -> Result<()> {
                     monitor_id: None,
                     windows: 0,
                     is_special: evt.id < 0,
+                    urgent: false,
                 });
                 state.workspaces.sort_by_key(|w| w.id);
             }
@@ -201,10 +289,63 @@ fn run_listener(data: Mutable<CompositorState>) -> Result<()> {
                 class: w.class,
                 address: w.address.to_string(),
             });
+            // Title changes of the focused window arrive as this event too.
+            // Hyprland sends no event when urgency ends; focusing the window
+            // clears it.
+            if let Some(active) = state.active_window.clone()
+                && let Some(window) = state.windows.iter_mut().find(|w| w.id == active.address)
+                && (window.title != active.title || window.urgent)
+            {
+                window.title = active.title;
+                if window.urgent {
+                    window.urgent = false;
+                    state.update_workspace_urgency();
+                }
+            }
         });
     }
 
-    // Window opened - increment window count
+    // Window title changed, focused or not
+    {
+        let data = data.clone();
+        listener.add_window_title_changed_handler(move |evt| {
+            debug!("Window title changed: {} -> {}", evt.address, evt.title);
+            let id = evt.address.to_string();
+            // Only take the write lock (and notify) for windows we track.
+            let changed = data
+                .lock_ref()
+                .windows
+                .iter()
+                .any(|w| w.id == id && w.title != evt.title);
+            if changed && let Some(window) = data.lock_mut().windows.iter_mut().find(|w| w.id == id)
+            {
+                window.title = evt.title;
+            }
+        });
+    }
+
+    // Window requested attention
+    {
+        let data = data.clone();
+        listener.add_urgent_state_changed_handler(move |address| {
+            debug!("Window urgent: {}", address);
+            let id = address.to_string();
+            let mut state = data.lock_mut();
+            let is_active = state
+                .active_window
+                .as_ref()
+                .is_some_and(|w| w.address == id);
+            if let Some(window) = state.windows.iter_mut().find(|w| w.id == id)
+                && !is_active
+                && !window.urgent
+            {
+                window.urgent = true;
+                state.update_workspace_urgency();
+            }
+        });
+    }
+
+    // Window opened, closed or moved - refresh the window list and counts
     {
         let data = data.clone();
         listener.add_window_opened_handler(move |evt| {
@@ -212,50 +353,40 @@ fn run_listener(data: Mutable<CompositorState>) -> Result<()> {
                 "Window opened: {} on workspace {:?}",
                 evt.window_class, evt.workspace_name
             );
-            let mut state = data.lock_mut();
-            // Try to find workspace by name and increment window count
-            if let Some(ws) = state
-                .workspaces
-                .iter_mut()
-                .find(|w| w.name == evt.workspace_name)
-            {
-                ws.windows = ws.windows.saturating_add(1);
-            }
+            refresh_windows(&data);
         });
     }
 
-    // Window closed - decrement window count
     {
         let data = data.clone();
         listener.add_window_closed_handler(move |_evt| {
             debug!("Window closed");
-            // We don't know which workspace, so do a lightweight refresh of window counts
-            // This is a compromise - we could track window addresses but that adds complexity
-            if let Ok(workspaces) = Workspaces::get() {
-                let mut state = data.lock_mut();
-                for ws_data in workspaces {
-                    if let Some(ws) = state.workspaces.iter_mut().find(|w| w.id == ws_data.id) {
-                        ws.windows = ws_data.windows;
-                    }
-                }
-            }
+            refresh_windows(&data);
         });
     }
 
-    // Window moved between workspaces
     {
         let data = data.clone();
         listener.add_window_moved_handler(move |evt| {
             debug!("Window moved to workspace {}", evt.workspace_name);
-            // Refresh window counts for affected workspaces
-            if let Ok(workspaces) = Workspaces::get() {
-                let mut state = data.lock_mut();
-                for ws_data in workspaces {
-                    if let Some(ws) = state.workspaces.iter_mut().find(|w| w.id == ws_data.id) {
-                        ws.windows = ws_data.windows;
-                    }
-                }
-            }
+            refresh_windows(&data);
+        });
+    }
+
+    // Floating or fullscreen state changed
+    {
+        let data = data.clone();
+        listener.add_float_state_changed_handler(move |_evt| {
+            debug!("Window floating state changed");
+            refresh_windows(&data);
+        });
+    }
+
+    {
+        let data = data.clone();
+        listener.add_fullscreen_state_changed_handler(move |_evt| {
+            debug!("Window fullscreen state changed");
+            refresh_windows(&data);
         });
     }
 
@@ -291,6 +422,23 @@ fn run_listener(data: Mutable<CompositorState>) -> Result<()> {
         });
     }
 
+    // Output plugged in or unplugged
+    {
+        let data = data.clone();
+        listener.add_monitor_added_handler(move |evt| {
+            debug!("Monitor added: {:?}", evt);
+            refresh_monitors(&data);
+        });
+    }
+
+    {
+        let data = data.clone();
+        listener.add_monitor_removed_handler(move |evt| {
+            debug!("Monitor removed: {:?}", evt);
+            refresh_monitors(&data);
+        });
+    }
+
     // Submap changed
     {
         let data = data.clone();
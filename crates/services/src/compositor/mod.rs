@@ -1,21 +1,25 @@
 //! Compositor service for workspace and monitor management.
 //!
 //! This module provides an event-driven subscriber for monitoring compositor state
-//! (workspaces, monitors, active window, keyboard layout) and executing commands.
-//! It supports multiple compositor backends (Hyprland, Niri).
+//! (workspaces, monitors, windows, keyboard layout) and executing commands.
+//! It supports multiple compositor backends (Hyprland, Niri, Sway).
 //!
 //! Uses incremental updates with direct Mutable mutation for efficiency.
 
 pub mod hyprland;
 pub mod niri;
+pub mod sway;
 pub mod types;
 
 use anyhow::Result;
 use futures_signals::signal::{Mutable, MutableSignalCloned};
 use tracing::info;
 
+use crate::ServiceStatus;
+
 pub use types::{
-    ActiveWindow, CompositorBackend, CompositorCommand, CompositorState, Monitor, Workspace,
+    ActiveWindow, CompositorBackend, CompositorCommand, CompositorState, Monitor, NiriColumn,
+    NiriState, Window, Workspace,
 };
 
 /// Event-driven compositor subscriber.
@@ -28,7 +32,8 @@ pub use types::{
 #[derive(Debug, Clone)]
 pub struct CompositorSubscriber {
     data: Mutable<CompositorState>,
-    backend: CompositorBackend,
+    status: Mutable<ServiceStatus>,
+    backend: Option<CompositorBackend>,
 }
 
 impl CompositorSubscriber {
@@ -38,7 +43,7 @@ impl CompositorSubscriber {
     /// Returns an error if no supported compositor is detected.
     pub async fn new() -> Result<Self> {
         let backend = detect_backend().ok_or_else(|| {
-            anyhow::anyhow!("No supported compositor detected (Hyprland or Niri)")
+            anyhow::anyhow!("No supported compositor detected (Hyprland, Niri or Sway)")
         })?;
 
         info!("Detected compositor backend: {}", backend.name());
@@ -47,6 +52,7 @@ impl CompositorSubscriber {
         let initial_state = match backend {
             CompositorBackend::Hyprland => hyprland::fetch_full_state()?,
             CompositorBackend::Niri => niri::fetch_full_state()?,
+            CompositorBackend::Sway => sway::fetch_full_state()?,
         };
 
         let data = Mutable::new(initial_state);
@@ -55,9 +61,25 @@ impl CompositorSubscriber {
         match backend {
             CompositorBackend::Hyprland => hyprland::start_listener(data.clone()),
             CompositorBackend::Niri => niri::start_listener(data.clone()),
+            CompositorBackend::Sway => sway::start_listener(data.clone()),
         }
 
-        Ok(Self { data, backend })
+        Ok(Self {
+            data,
+            status: Mutable::new(ServiceStatus::Active),
+            backend: Some(backend),
+        })
+    }
+
+    /// Fallback subscriber when no supported compositor is running.
+    ///
+    /// Reports empty state and rejects commands.
+    pub fn disabled() -> Self {
+        Self {
+            data: Mutable::new(CompositorState::default()),
+            status: Mutable::new(ServiceStatus::Unavailable),
+            backend: None,
+        }
     }
 
     /// Get a signal that emits when compositor state changes.
@@ -70,16 +92,25 @@ impl CompositorSubscriber {
         self.data.get_cloned()
     }
 
-    /// Get the detected compositor backend.
-    pub fn backend(&self) -> CompositorBackend {
+    /// Get the current service status.
+    pub fn status(&self) -> ServiceStatus {
+        self.status.get_cloned()
+    }
+
+    /// Get the detected compositor backend, if any.
+    pub fn backend(&self) -> Option<CompositorBackend> {
         self.backend
     }
 
     /// Execute a compositor command.
     pub fn dispatch(&self, command: CompositorCommand) -> Result<()> {
-        match self.backend {
+        let Some(backend) = self.backend else {
+            anyhow::bail!("No compositor backend available");
+        };
+        match backend {
             CompositorBackend::Hyprland => hyprland::execute_command(command),
             CompositorBackend::Niri => niri::execute_command(command),
+            CompositorBackend::Sway => sway::execute_command(command),
         }
     }
 
@@ -88,9 +119,13 @@ impl CompositorSubscriber {
     /// Normally not needed as incremental updates keep state in sync,
     /// but can be useful if state gets out of sync for some reason.
     pub fn refresh(&self) -> Result<()> {
-        let new_state = match self.backend {
+        let Some(backend) = self.backend else {
+            return Ok(());
+        };
+        let new_state = match backend {
             CompositorBackend::Hyprland => hyprland::fetch_full_state()?,
             CompositorBackend::Niri => niri::fetch_full_state()?,
+            CompositorBackend::Sway => sway::fetch_full_state()?,
         };
         self.data.set(new_state);
         Ok(())
@@ -103,6 +138,8 @@ fn detect_backend() -> Option<CompositorBackend> {
         Some(CompositorBackend::Hyprland)
     } else if niri::is_available() {
         Some(CompositorBackend::Niri)
+    } else if sway::is_available() {
+        Some(CompositorBackend::Sway)
     } else {
         None
     }
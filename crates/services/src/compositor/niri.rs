@@ -5,7 +5,7 @@
 //! for maintaining compositor state from the event stream.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env,
     io::{BufRead, BufReader, Write as _},
     os::unix::net::UnixStream,
@@ -16,12 +16,15 @@ use anyhow::{Context, Result, anyhow};
 use futures_signals::signal::Mutable;
 use itertools::Itertools;
 use niri_ipc::{
-    Action, Event, Reply, Request, WorkspaceReferenceArg,
+    Action, Event, LogicalOutput, Reply, Request, Response, WorkspaceReferenceArg,
     state::{EventStreamState, EventStreamStatePart},
 };
 use tracing::{debug, error, info};
 
-use super::types::{ActiveWindow, CompositorCommand, CompositorState, Monitor, Workspace};
+use super::types::{
+    ActiveWindow, CompositorCommand, CompositorState, Monitor, NiriColumn, NiriState, Window,
+    Workspace,
+};
 
 /// Check if Niri is available (running).
 pub fn is_available() -> bool {
@@ -40,15 +43,24 @@ pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
                 reference: WorkspaceReferenceArg::Id(id),
             }
         }
-        CompositorCommand::FocusSpecialWorkspace(_) => {
-            anyhow::bail!("Special workspaces not supported in Niri backend");
-        }
-        CompositorCommand::ToggleSpecialWorkspace(_) => {
-            anyhow::bail!("Special workspaces not supported in Niri backend");
-        }
-        CompositorCommand::FocusMonitor(_) => {
-            anyhow::bail!("FocusMonitor by ID not supported in Niri backend");
+        // Niri has no special workspaces; named workspaces stand in for them.
+        CompositorCommand::FocusSpecialWorkspace(name) => Action::FocusWorkspace {
+            reference: WorkspaceReferenceArg::Name(name),
+        },
+        CompositorCommand::ToggleSpecialWorkspace(name) => {
+            let focused = workspaces()?
+                .into_iter()
+                .find(|ws| ws.is_focused)
+                .is_some_and(|ws| ws.name.as_deref() == Some(name.as_str()));
+            if focused {
+                Action::FocusWorkspacePrevious {}
+            } else {
+                Action::FocusWorkspace {
+                    reference: WorkspaceReferenceArg::Name(name),
+                }
+            }
         }
+        CompositorCommand::FocusMonitor(output) => Action::FocusMonitor { output },
         CompositorCommand::ScrollWorkspace(dir) => {
             if dir > 0 {
                 Action::FocusWorkspaceUp {}
@@ -56,6 +68,25 @@ pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
                 Action::FocusWorkspaceDown {}
             }
         }
+        CompositorCommand::FocusWindow(id) => Action::FocusWindow {
+            id: window_id(&id)?,
+        },
+        CompositorCommand::CloseWindow(id) => Action::CloseWindow {
+            id: Some(window_id(&id)?),
+        },
+        CompositorCommand::MoveWindowToWorkspace(id, workspace) => {
+            let workspace = u64::try_from(workspace).map_err(|_| {
+                anyhow!(
+                    "Workspace ID {} is out of range for Niri backend",
+                    workspace
+                )
+            })?;
+            Action::MoveWindowToWorkspace {
+                window_id: Some(window_id(&id)?),
+                reference: WorkspaceReferenceArg::Id(workspace),
+                focus: false,
+            }
+        }
         CompositorCommand::NextKeyboardLayout => Action::SwitchLayout {
             layout: niri_ipc::LayoutSwitchTarget::Next,
         },
@@ -65,7 +96,7 @@ pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
                     command: vec![args],
                 }
             } else {
-                anyhow::bail!("Unknown custom dispatch: {}", action);
+                parse_action(&action, &args)?
             }
         }
     };
@@ -73,6 +104,68 @@ pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
     send_action(action)
 }
 
+fn window_id(id: &str) -> Result<u64> {
+    id.parse()
+        .map_err(|_| anyhow!("Invalid Niri window ID: {}", id))
+}
+
+/// Parse a Niri action from its name and its fields as JSON.
+///
+/// The name may be given as on the niri command line (`focus-column-left`)
+/// or as the IPC variant name (`FocusColumnLeft`). Empty arguments mean an
+/// action without fields.
+fn parse_action(name: &str, args: &str) -> Result<Action> {
+    let variant: String = name
+        .split(['-', '_'])
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let fields = if args.trim().is_empty() {
+        serde_json::Value::Object(serde_json::Map::new())
+    } else {
+        serde_json::from_str(args)
+            .with_context(|| format!("Invalid arguments for Niri action {}", variant))?
+    };
+
+    let mut action = serde_json::Map::new();
+    action.insert(variant.clone(), fields);
+    serde_json::from_value(serde_json::Value::Object(action))
+        .with_context(|| format!("Unknown or invalid Niri action: {}", variant))
+}
+
+/// Fetch the logical position and size of each enabled output.
+fn output_geometry() -> HashMap<String, LogicalOutput> {
+    let outputs = match request(Request::Outputs) {
+        Ok(Response::Outputs(outputs)) => outputs,
+        Ok(other) => {
+            debug!("Unexpected Niri response: {:?}", other);
+            return HashMap::new();
+        }
+        Err(e) => {
+            debug!("Failed to fetch Niri outputs: {}", e);
+            return HashMap::new();
+        }
+    };
+    outputs
+        .into_iter()
+        .filter_map(|(name, output)| Some((name, output.logical?)))
+        .collect()
+}
+
+/// Fetch the current workspaces from Niri.
+fn workspaces() -> Result<Vec<niri_ipc::Workspace>> {
+    match request(Request::Workspaces)? {
+        Response::Workspaces(workspaces) => Ok(workspaces),
+        other => Err(anyhow!("Unexpected Niri response: {:?}", other)),
+    }
+}
+
 /// Fetch the full compositor state from Niri.
 ///
 /// Connects to the event stream, reads the initial burst of events
@@ -126,7 +219,7 @@ pub fn fetch_full_state() -> Result<CompositorState> {
         }
     }
 
-    Ok(map_state(&internal_state))
+    Ok(map_state(&internal_state, &output_geometry()))
 }
 
 /// Start the Niri event listener in a dedicated thread.
@@ -166,6 +259,7 @@ fn run_listener(data: Mutable<CompositorState>) -> Result<()> {
     reader.get_ref().shutdown(std::net::Shutdown::Write).ok();
 
     let mut internal_state = EventStreamState::default();
+    let mut geometry = output_geometry();
 
     // Read events forever
     loop {
@@ -187,8 +281,20 @@ fn run_listener(data: Mutable<CompositorState>) -> Result<()> {
 
         internal_state.apply(event);
 
+        // The event stream does not report output changes; re-query the
+        // layout when the set of outputs with workspaces differs.
+        let outputs: HashSet<&String> = internal_state
+            .workspaces
+            .workspaces
+            .values()
+            .filter_map(|ws| ws.output.as_ref())
+            .collect();
+        if outputs != geometry.keys().collect::<HashSet<_>>() {
+            geometry = output_geometry();
+        }
+
         // Map to our generic state and update the Mutable
-        let state = map_state(&internal_state);
+        let state = map_state(&internal_state, &geometry);
         data.set(state);
     }
 
@@ -207,9 +313,14 @@ fn connect() -> Result<UnixStream> {
 
 /// Send a single action to niri and read the reply.
 fn send_action(action: Action) -> Result<()> {
+    request(Request::Action(action)).map(|_| ())
+}
+
+/// Send a request to niri and read the response.
+fn request(request: Request) -> Result<Response> {
     let mut stream = connect()?;
 
-    let mut json = serde_json::to_string(&Request::Action(action))?;
+    let mut json = serde_json::to_string(&request)?;
     json.push('\n');
     stream.write_all(json.as_bytes())?;
     stream.flush()?;
@@ -219,11 +330,35 @@ fn send_action(action: Action) -> Result<()> {
     reader.read_line(&mut response_line)?;
 
     let reply: Reply = serde_json::from_str(&response_line)?;
-    reply.map_err(|e| anyhow!("Niri error: {}", e)).map(|_| ())
+    reply.map_err(|e| anyhow!("Niri error: {}", e))
+}
+
+/// Whether a window fills the whole output showing its workspace.
+///
+/// Niri's IPC has no fullscreen flag, so a window counts as fullscreen when
+/// its tile is as large as the output's logical size.
+fn covers_output(
+    window: &niri_ipc::Window,
+    niri: &EventStreamState,
+    geometry: &HashMap<String, LogicalOutput>,
+) -> bool {
+    let output = window
+        .workspace_id
+        .and_then(|id| niri.workspaces.workspaces.get(&id))
+        .and_then(|ws| ws.output.as_ref())
+        .and_then(|name| geometry.get(name));
+    let (width, height) = window.layout.tile_size;
+    output.is_some_and(|output| {
+        (width - f64::from(output.width)).abs() < 1.0
+            && (height - f64::from(output.height)).abs() < 1.0
+    })
 }
 
 /// Map niri's internal EventStreamState to our generic CompositorState.
-fn map_state(niri: &EventStreamState) -> CompositorState {
+fn map_state(
+    niri: &EventStreamState,
+    geometry: &HashMap<String, LogicalOutput>,
+) -> CompositorState {
     // Build a map of output name -> active workspace id
     let output_to_active_ws: HashMap<_, _> = niri
         .workspaces
@@ -261,6 +396,7 @@ fn map_state(niri: &EventStreamState) -> CompositorState {
             }),
             windows: 0,
             is_special: false,
+            urgent: false,
         })
         .collect();
 
@@ -274,18 +410,23 @@ fn map_state(niri: &EventStreamState) -> CompositorState {
         }
     }
 
-    // Build monitors
-    let monitors: Vec<Monitor> = output_to_active_ws
+    // Build monitors, in the same stable order as their IDs
+    let monitors: Vec<Monitor> = outputs
         .iter()
-        .map(|(name, active_ws_id)| Monitor {
-            id: outputs
-                .iter()
-                .position(|o| *o == name)
-                .map_or(-1, |i| i as i128),
-            name: name.clone(),
-            active_workspace_id: *active_ws_id,
-            special_workspace_id: -1,
-            ..Default::default()
+        .enumerate()
+        .map(|(i, name)| {
+            let logical = geometry.get(*name);
+            Monitor {
+                id: i as i128,
+                name: (*name).clone(),
+                active_workspace_id: output_to_active_ws[*name],
+                special_workspace_id: -1,
+                width: logical.map_or(0, |l| l.width),
+                height: logical.map_or(0, |l| l.height),
+                x: logical.map_or(0, |l| l.x),
+                y: logical.map_or(0, |l| l.y),
+                scale: logical.map_or(1.0, |l| l.scale as f32),
+            }
         })
         .collect();
 
@@ -296,6 +437,45 @@ fn map_state(niri: &EventStreamState) -> CompositorState {
         .find(|w| w.is_focused)
         .map(|w| w.id as i32);
 
+    let windows = niri
+        .windows
+        .windows
+        .values()
+        .sorted_by_key(|w| (w.workspace_id, w.id))
+        .map(|w| Window {
+            id: w.id.to_string(),
+            title: w.title.clone().unwrap_or_default(),
+            class: w.app_id.clone().unwrap_or_default(),
+            workspace_id: w.workspace_id.map(|id| id as i32),
+            floating: w.is_floating,
+            fullscreen: covers_output(w, niri, geometry),
+            urgent: w.is_urgent,
+        })
+        .collect();
+
+    // Group tiled windows into the columns of the scrolling layout.
+    let mut columns: Vec<NiriColumn> = Vec::new();
+    let tiled = niri.windows.windows.values().filter_map(|w| {
+        let ws_id = w.workspace_id?;
+        let (column, tile) = w.layout.pos_in_scrolling_layout?;
+        Some((ws_id as i32, column, tile, w))
+    });
+    for (workspace_id, index, _, w) in
+        tiled.sorted_by_key(|(ws, column, tile, _)| (*ws, *column, *tile))
+    {
+        match columns.last_mut() {
+            Some(last) if last.workspace_id == workspace_id && last.index == index => {
+                last.window_ids.push(w.id.to_string());
+            }
+            _ => columns.push(NiriColumn {
+                workspace_id,
+                index,
+                window_ids: vec![w.id.to_string()],
+                width: w.layout.tile_size.0,
+            }),
+        }
+    }
+
     let active_window = niri
         .windows
         .windows
@@ -317,12 +497,19 @@ fn map_state(niri: &EventStreamState) -> CompositorState {
         },
     );
 
-    CompositorState {
+    let mut state = CompositorState {
         workspaces,
         monitors,
         active_workspace_id,
+        windows,
         active_window,
         keyboard_layout,
         submap: None,
-    }
+        niri: Some(NiriState {
+            overview_open: niri.overview.is_open,
+            columns,
+        }),
+    };
+    state.update_workspace_urgency();
+    state
 }
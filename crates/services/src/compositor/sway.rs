@@ -0,0 +1,666 @@
+//! Sway compositor backend.
+//!
+//! This module talks to Sway (and other compositors speaking the i3 IPC
+//! protocol) over the socket in `SWAYSOCK`. Workspaces, outputs, the
+//! focused window, the keyboard layout and the binding mode are read with
+//! queries; the event subscription then keeps them up to date. The binding
+//! mode is reported as the submap.
+
+use std::{
+    env,
+    io::{Read, Write as _},
+    os::unix::net::UnixStream,
+    thread,
+};
+
+use anyhow::{Context, Result, anyhow};
+use futures_signals::signal::Mutable;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, error, info};
+
+use super::types::{ActiveWindow, CompositorCommand, CompositorState, Monitor, Window, Workspace};
+
+const MAGIC: &[u8; 6] = b"i3-ipc";
+
+// Message types.
+const RUN_COMMAND: u32 = 0;
+const GET_WORKSPACES: u32 = 1;
+const SUBSCRIBE: u32 = 2;
+const GET_OUTPUTS: u32 = 3;
+const GET_TREE: u32 = 4;
+const GET_BINDING_STATE: u32 = 12;
+const GET_INPUTS: u32 = 100;
+
+// Event types (high bit set).
+const EVENT_WORKSPACE: u32 = 0x8000_0000;
+const EVENT_OUTPUT: u32 = 0x8000_0001;
+const EVENT_MODE: u32 = 0x8000_0002;
+const EVENT_WINDOW: u32 = 0x8000_0003;
+const EVENT_SHUTDOWN: u32 = 0x8000_0006;
+const EVENT_INPUT: u32 = 0x8000_0015;
+
+/// Name of Sway's scratchpad workspace in the tree.
+const SCRATCHPAD: &str = "__i3_scratch";
+
+/// Binding mode active when no mode is entered.
+const DEFAULT_MODE: &str = "default";
+
+#[derive(Debug, Deserialize)]
+struct SwayWorkspace {
+    id: i64,
+    num: i32,
+    name: String,
+    focused: bool,
+    output: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SwayOutput {
+    name: String,
+    active: bool,
+    current_workspace: Option<String>,
+    rect: Rect,
+    scale: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: i64,
+    #[serde(rename = "type")]
+    kind: String,
+    name: Option<String>,
+    #[serde(default)]
+    focused: bool,
+    #[serde(default)]
+    urgent: bool,
+    #[serde(default)]
+    fullscreen_mode: u8,
+    app_id: Option<String>,
+    window_properties: Option<WindowProperties>,
+    #[serde(default)]
+    nodes: Vec<Node>,
+    #[serde(default)]
+    floating_nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WindowProperties {
+    class: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Input {
+    #[serde(rename = "type")]
+    kind: String,
+    xkb_active_layout_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BindingState {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandOutcome {
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WindowEvent {
+    change: String,
+    container: Node,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModeEvent {
+    change: String,
+}
+
+impl Node {
+    /// Whether this node is a window rather than a split container.
+    fn is_window(&self) -> bool {
+        matches!(self.kind.as_str(), "con" | "floating_con")
+            && self.nodes.is_empty()
+            && self.floating_nodes.is_empty()
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter().chain(&self.floating_nodes)
+    }
+
+    fn window_count(&self) -> u16 {
+        self.children()
+            .map(|child| {
+                if child.is_window() {
+                    1
+                } else {
+                    child.window_count()
+                }
+            })
+            .sum()
+    }
+
+    fn collect_windows<'a>(&'a self, windows: &mut Vec<&'a Node>) {
+        for child in self.children() {
+            if child.is_window() {
+                windows.push(child);
+            } else {
+                child.collect_windows(windows);
+            }
+        }
+    }
+
+    fn find_focused(&self) -> Option<&Node> {
+        if self.focused && self.is_window() {
+            return Some(self);
+        }
+        self.children().find_map(Node::find_focused)
+    }
+
+    fn workspaces(&self) -> Vec<&Node> {
+        if self.kind == "workspace" {
+            return vec![self];
+        }
+        self.nodes.iter().flat_map(Node::workspaces).collect()
+    }
+
+    fn class(&self) -> String {
+        self.app_id
+            .clone()
+            .or_else(|| {
+                self.window_properties
+                    .as_ref()
+                    .and_then(|props| props.class.clone())
+            })
+            .unwrap_or_default()
+    }
+
+    fn to_active_window(&self) -> ActiveWindow {
+        ActiveWindow {
+            title: self.name.clone().unwrap_or_default(),
+            class: self.class(),
+            address: self.id.to_string(),
+        }
+    }
+
+    fn to_window(&self, workspace_id: i32) -> Window {
+        Window {
+            id: self.id.to_string(),
+            title: self.name.clone().unwrap_or_default(),
+            class: self.class(),
+            workspace_id: Some(workspace_id),
+            floating: self.kind == "floating_con",
+            fullscreen: self.fullscreen_mode != 0,
+            urgent: self.urgent,
+        }
+    }
+}
+
+/// Check if Sway is available (running).
+pub fn is_available() -> bool {
+    env::var_os("SWAYSOCK").is_some()
+}
+
+/// Execute a compositor command synchronously via Sway IPC.
+pub fn execute_command(cmd: CompositorCommand) -> Result<()> {
+    let command = match cmd {
+        CompositorCommand::FocusWorkspace(id) => {
+            let workspaces: Vec<SwayWorkspace> = query(GET_WORKSPACES)?;
+            let workspace = workspaces
+                .into_iter()
+                .find(|w| workspace_id(w.id) == id)
+                .ok_or_else(|| anyhow!("Unknown Sway workspace ID {}", id))?;
+            format!("workspace {}", quote(&workspace.name))
+        }
+        // Sway has a single scratchpad instead of named special workspaces.
+        CompositorCommand::FocusSpecialWorkspace(_)
+        | CompositorCommand::ToggleSpecialWorkspace(_) => "scratchpad show".to_string(),
+        CompositorCommand::FocusMonitor(name) => format!("focus output {}", quote(&name)),
+        CompositorCommand::ScrollWorkspace(dir) => {
+            if dir > 0 {
+                "workspace next_on_output".to_string()
+            } else {
+                "workspace prev_on_output".to_string()
+            }
+        }
+        CompositorCommand::FocusWindow(id) => format!("[con_id={}] focus", con_id(&id)?),
+        CompositorCommand::CloseWindow(id) => format!("[con_id={}] kill", con_id(&id)?),
+        CompositorCommand::MoveWindowToWorkspace(id, workspace) => {
+            let workspaces: Vec<SwayWorkspace> = query(GET_WORKSPACES)?;
+            let workspace = workspaces
+                .into_iter()
+                .find(|w| workspace_id(w.id) == workspace)
+                .ok_or_else(|| anyhow!("Unknown Sway workspace ID {}", workspace))?;
+            format!(
+                "[con_id={}] move container to workspace {}",
+                con_id(&id)?,
+                quote(&workspace.name)
+            )
+        }
+        CompositorCommand::NextKeyboardLayout => {
+            "input type:keyboard xkb_switch_layout next".to_string()
+        }
+        CompositorCommand::Custom(command, args) => custom_command(command, &args),
+    };
+
+    run_command(&command)
+}
+
+/// Fetch the complete compositor state from Sway.
+pub fn fetch_full_state() -> Result<CompositorState> {
+    let mut state = CompositorState::default();
+    refresh_layout(&mut state)?;
+    state.keyboard_layout = fetch_keyboard_layout();
+    let mode: BindingState = query(GET_BINDING_STATE)?;
+    state.submap = submap(mode.name);
+    Ok(state)
+}
+
+/// Start the Sway event listener in a dedicated thread.
+pub fn start_listener(data: Mutable<CompositorState>) {
+    thread::spawn(move || {
+        if let Err(e) = run_listener(data) {
+            error!("Sway event listener error: {}", e);
+        }
+    });
+}
+
+/// Run the blocking event listener loop.
+fn run_listener(data: Mutable<CompositorState>) -> Result<()> {
+    info!("Starting Sway event listener");
+
+    let mut stream = connect()?;
+    let events = r#"["workspace","output","mode","window","input","shutdown"]"#;
+    send(&mut stream, SUBSCRIBE, events.as_bytes())?;
+    let (_, reply) = receive(&mut stream)?;
+    let reply: CommandOutcome = serde_json::from_slice(&reply)?;
+    if !reply.success {
+        anyhow::bail!("Sway refused the event subscription");
+    }
+
+    loop {
+        let (kind, payload) = receive(&mut stream)?;
+        match kind {
+            EVENT_WORKSPACE | EVENT_OUTPUT => refresh(&data),
+            EVENT_WINDOW => handle_window_event(&data, &payload),
+            EVENT_MODE => match serde_json::from_slice::<ModeEvent>(&payload) {
+                Ok(event) => {
+                    debug!("Binding mode changed: {}", event.change);
+                    data.lock_mut().submap = submap(event.change);
+                }
+                Err(e) => debug!("Failed to parse Sway mode event: {:?}", e),
+            },
+            EVENT_INPUT => {
+                let layout = fetch_keyboard_layout();
+                let mut state = data.lock_mut();
+                if state.keyboard_layout != layout {
+                    state.keyboard_layout = layout;
+                }
+            }
+            EVENT_SHUTDOWN => break,
+            _ => {}
+        }
+    }
+
+    info!("Sway event stream ended");
+    Ok(())
+}
+
+fn handle_window_event(data: &Mutable<CompositorState>, payload: &[u8]) {
+    let event: WindowEvent = match serde_json::from_slice(payload) {
+        Ok(event) => event,
+        Err(e) => {
+            debug!("Failed to parse Sway window event: {:?}", e);
+            return;
+        }
+    };
+
+    match event.change.as_str() {
+        // Only the focused window's details changed.
+        "focus" | "title" | "urgent" => {
+            let container = event.container;
+            let mut state = data.lock_mut();
+            if let Some(window) = state
+                .windows
+                .iter_mut()
+                .find(|w| w.id == container.id.to_string())
+            {
+                window.title = container.name.clone().unwrap_or_default();
+                window.urgent = container.urgent;
+            }
+            state.update_workspace_urgency();
+            if container.focused {
+                state.active_window = Some(container.to_active_window());
+            }
+        }
+        // Window counts or focus may have changed anywhere.
+        _ => refresh(data),
+    }
+}
+
+/// Refresh workspaces, outputs and the focused window, keeping the rest.
+fn refresh(data: &Mutable<CompositorState>) {
+    let mut state = data.get_cloned();
+    match refresh_layout(&mut state) {
+        Ok(()) => data.set(state),
+        Err(e) => debug!("Failed to refresh Sway state: {}", e),
+    }
+}
+
+fn refresh_layout(state: &mut CompositorState) -> Result<()> {
+    let sway_workspaces: Vec<SwayWorkspace> = query(GET_WORKSPACES)?;
+    let outputs = active_outputs()?;
+    let tree: Node = query(GET_TREE)?;
+    let tree_workspaces = tree.workspaces();
+
+    let monitor_id = |name: &str| {
+        outputs
+            .iter()
+            .position(|o| o.name == name)
+            .map(|i| i as i128)
+    };
+    let window_count = |id: i64| {
+        tree_workspaces
+            .iter()
+            .find(|w| w.id == id)
+            .map_or(0, |w| w.window_count())
+    };
+
+    let mut workspaces: Vec<Workspace> = sway_workspaces
+        .iter()
+        .enumerate()
+        .map(|(i, w)| Workspace {
+            id: workspace_id(w.id),
+            index: if w.num >= 0 { w.num } else { i as i32 + 1 },
+            name: w.name.clone(),
+            monitor: w.output.clone(),
+            monitor_id: monitor_id(&w.output),
+            windows: window_count(w.id),
+            is_special: false,
+            urgent: false,
+        })
+        .collect();
+
+    if let Some(scratchpad) = tree_workspaces
+        .iter()
+        .find(|w| w.name.as_deref() == Some(SCRATCHPAD))
+    {
+        workspaces.push(Workspace {
+            id: workspace_id(scratchpad.id),
+            index: -1,
+            name: "scratchpad".to_string(),
+            monitor: String::new(),
+            monitor_id: None,
+            windows: scratchpad.window_count(),
+            is_special: true,
+            urgent: false,
+        });
+    }
+
+    let workspace_by_name = |name: &Option<String>| {
+        sway_workspaces
+            .iter()
+            .find(|w| Some(&w.name) == name.as_ref())
+            .map_or(-1, |w| workspace_id(w.id))
+    };
+    state.monitors = outputs
+        .iter()
+        .enumerate()
+        .map(|(i, o)| Monitor {
+            id: i as i128,
+            name: o.name.clone(),
+            active_workspace_id: workspace_by_name(&o.current_workspace),
+            special_workspace_id: -1,
+            width: o.rect.width,
+            height: o.rect.height,
+            x: o.rect.x,
+            y: o.rect.y,
+            scale: o.scale.unwrap_or(1.0),
+        })
+        .collect();
+
+    state.active_workspace_id = sway_workspaces
+        .iter()
+        .find(|w| w.focused)
+        .map(|w| workspace_id(w.id));
+    state.windows = tree_workspaces
+        .iter()
+        .flat_map(|ws| {
+            let mut windows = Vec::new();
+            ws.collect_windows(&mut windows);
+            windows
+                .into_iter()
+                .map(|window| window.to_window(workspace_id(ws.id)))
+        })
+        .collect();
+    state.active_window = tree.find_focused().map(Node::to_active_window);
+    state.workspaces = workspaces;
+    state.update_workspace_urgency();
+    Ok(())
+}
+
+fn fetch_keyboard_layout() -> String {
+    query::<Vec<Input>>(GET_INPUTS)
+        .ok()
+        .and_then(|inputs| {
+            inputs
+                .into_iter()
+                .filter(|input| input.kind == "keyboard")
+                .find_map(|input| input.xkb_active_layout_name)
+        })
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+fn active_outputs() -> Result<Vec<SwayOutput>> {
+    let outputs: Vec<SwayOutput> = query(GET_OUTPUTS)?;
+    Ok(outputs.into_iter().filter(|o| o.active).collect())
+}
+
+/// Sway container IDs are small sequential integers; narrow them to the
+/// generic workspace ID type.
+fn workspace_id(id: i64) -> i32 {
+    i32::try_from(id).unwrap_or(i32::MAX)
+}
+
+fn con_id(id: &str) -> Result<i64> {
+    id.parse()
+        .map_err(|_| anyhow!("Invalid Sway window ID: {}", id))
+}
+
+fn submap(mode: String) -> Option<String> {
+    (mode != DEFAULT_MODE).then_some(mode)
+}
+
+/// Build the Sway command for a custom dispatcher; `spawn` and `exec` both
+/// run a program.
+fn custom_command(command: String, args: &str) -> String {
+    if command == "spawn" || command == "exec" {
+        format!("exec {}", args)
+    } else if args.is_empty() {
+        command
+    } else {
+        format!("{} {}", command, args)
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Run a Sway command, failing if any part of it failed.
+fn run_command(command: &str) -> Result<()> {
+    let mut stream = connect()?;
+    send(&mut stream, RUN_COMMAND, command.as_bytes())?;
+    let (_, reply) = receive(&mut stream)?;
+    let outcomes: Vec<CommandOutcome> = serde_json::from_slice(&reply)?;
+    match outcomes.into_iter().find(|o| !o.success) {
+        Some(failed) => Err(anyhow!(
+            "Sway error: {}",
+            failed.error.unwrap_or_else(|| command.to_string())
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Send a query with an empty payload and decode the reply.
+fn query<T: DeserializeOwned>(kind: u32) -> Result<T> {
+    let mut stream = connect()?;
+    send(&mut stream, kind, &[])?;
+    let (_, reply) = receive(&mut stream)?;
+    serde_json::from_slice(&reply).context("Failed to parse Sway reply")
+}
+
+/// Connect to the Sway IPC socket.
+fn connect() -> Result<UnixStream> {
+    let socket_path =
+        env::var_os("SWAYSOCK").ok_or_else(|| anyhow!("SWAYSOCK environment variable not set"))?;
+
+    UnixStream::connect(socket_path).context("Failed to connect to Sway socket")
+}
+
+fn send(stream: &mut UnixStream, kind: u32, payload: &[u8]) -> Result<()> {
+    let len = u32::try_from(payload.len())?;
+    let mut message = Vec::with_capacity(14 + payload.len());
+    message.extend_from_slice(MAGIC);
+    message.extend_from_slice(&len.to_ne_bytes());
+    message.extend_from_slice(&kind.to_ne_bytes());
+    message.extend_from_slice(payload);
+    stream.write_all(&message)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Read one message, returning its type and payload.
+fn receive(stream: &mut UnixStream) -> Result<(u32, Vec<u8>)> {
+    let mut header = [0u8; 14];
+    stream.read_exact(&mut header)?;
+    if &header[..6] != MAGIC {
+        anyhow::bail!("Invalid Sway IPC message header");
+    }
+
+    let len = u32::from_ne_bytes(header[6..10].try_into()?);
+    let kind = u32::from_ne_bytes(header[10..14].try_into()?);
+    let mut payload = vec![0u8; len as usize];
+    stream.read_exact(&mut payload)?;
+    Ok((kind, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TREE: &str = r#"{
+        "id": 1,
+        "type": "root",
+        "name": "root",
+        "nodes": [{
+            "id": 2,
+            "type": "output",
+            "name": "DP-1",
+            "nodes": [{
+                "id": 3,
+                "type": "workspace",
+                "name": "1",
+                "nodes": [
+                    {
+                        "id": 4,
+                        "type": "con",
+                        "name": null,
+                        "nodes": [
+                            {"id": 5, "type": "con", "name": "Terminal", "app_id": "foot"},
+                            {
+                                "id": 6,
+                                "type": "con",
+                                "name": "Browser",
+                                "focused": true,
+                                "fullscreen_mode": 1,
+                                "window_properties": {"class": "firefox"}
+                            }
+                        ]
+                    }
+                ],
+                "floating_nodes": [
+                    {"id": 7, "type": "floating_con", "name": "Picker", "app_id": "picker", "urgent": true}
+                ]
+            }]
+        }]
+    }"#;
+
+    fn tree() -> Node {
+        serde_json::from_str(TREE).unwrap()
+    }
+
+    #[test]
+    fn test_window_count() {
+        let tree = tree();
+        assert_eq!(tree.window_count(), 3);
+        assert_eq!(tree.workspaces()[0].window_count(), 3);
+        assert_eq!(tree.workspaces()[0].nodes[0].window_count(), 2);
+    }
+
+    #[test]
+    fn test_collect_windows() {
+        let tree = tree();
+        let mut windows = Vec::new();
+        tree.workspaces()[0].collect_windows(&mut windows);
+        let ids: Vec<i64> = windows.iter().map(|w| w.id).collect();
+        assert_eq!(ids, vec![5, 6, 7]);
+
+        let floating = windows[2].to_window(3);
+        assert!(floating.floating);
+        assert!(floating.urgent);
+        assert_eq!(floating.class, "picker");
+        assert!(!windows[0].to_window(3).floating);
+    }
+
+    #[test]
+    fn test_find_focused() {
+        let tree = tree();
+        let focused = tree.find_focused().unwrap();
+        assert_eq!(focused.id, 6);
+        assert_eq!(focused.class(), "firefox");
+        assert!(focused.to_window(3).fullscreen);
+
+        let unfocused: Node =
+            serde_json::from_str(r#"{"id": 1, "type": "con", "name": "Terminal"}"#).unwrap();
+        assert!(unfocused.find_focused().is_none());
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("1"), "\"1\"");
+        assert_eq!(quote("web dev"), "\"web dev\"");
+        assert_eq!(quote(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quote(r"C:\dir"), r#""C:\\dir""#);
+    }
+
+    #[test]
+    fn test_submap() {
+        assert_eq!(submap("default".to_string()), None);
+        assert_eq!(submap("resize".to_string()), Some("resize".to_string()));
+    }
+
+    #[test]
+    fn test_custom_command() {
+        assert_eq!(custom_command("spawn".to_string(), "foot"), "exec foot");
+        assert_eq!(
+            custom_command("exec".to_string(), "firefox --new-window"),
+            "exec firefox --new-window"
+        );
+        assert_eq!(custom_command("reload".to_string(), ""), "reload");
+        assert_eq!(
+            custom_command("layout".to_string(), "tabbed"),
+            "layout tabbed"
+        );
+    }
+}
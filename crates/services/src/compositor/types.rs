@@ -1,7 +1,9 @@
 //! Type definitions for compositor state and events.
 
+use serde::Serialize;
+
 /// A workspace managed by the compositor.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub struct Workspace {
     /// Unique workspace ID.
     pub id: i32,
@@ -17,10 +19,12 @@ pub struct Workspace {
     pub windows: u16,
     /// Whether this is a special/scratchpad workspace.
     pub is_special: bool,
+    /// Whether a window on this workspace requested attention.
+    pub urgent: bool,
 }
 
 /// A monitor/output managed by the compositor.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub struct Monitor {
     /// Unique monitor ID.
     pub id: i128,
@@ -43,7 +47,7 @@ pub struct Monitor {
 }
 
 /// Information about the currently focused window.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub struct ActiveWindow {
     /// Window title.
     pub title: String,
@@ -53,8 +57,60 @@ pub struct ActiveWindow {
     pub address: String,
 }
 
+/// An open toplevel window.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct Window {
+    /// Backend window handle (Hyprland address, Niri or Sway ID).
+    pub id: String,
+    /// Window title.
+    pub title: String,
+    /// Window class (application identifier).
+    pub class: String,
+    /// ID of the workspace the window is on (if any).
+    pub workspace_id: Option<i32>,
+    /// Whether the window is floating.
+    pub floating: bool,
+    /// Whether the window is fullscreen.
+    pub fullscreen: bool,
+    /// Whether the window requested attention.
+    pub urgent: bool,
+}
+
+/// A column of Niri's scrolling layout.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct NiriColumn {
+    /// ID of the workspace the column is on.
+    pub workspace_id: i32,
+    /// 1-based position of the column on its workspace.
+    pub index: usize,
+    /// IDs of the windows in the column, top to bottom.
+    pub window_ids: Vec<String>,
+    /// Column width in logical pixels.
+    pub width: f64,
+}
+
+/// State only Niri reports.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct NiriState {
+    /// Whether the overview is open.
+    pub overview_open: bool,
+    /// Columns of the scrolling layout on all workspaces, ordered by
+    /// workspace and position.
+    pub columns: Vec<NiriColumn>,
+}
+
+impl NiriState {
+    /// Get the columns on a workspace.
+    pub fn columns_on_workspace(&self, workspace_id: i32) -> Vec<&NiriColumn> {
+        self.columns
+            .iter()
+            .filter(|c| c.workspace_id == workspace_id)
+            .collect()
+    }
+}
+
 /// Complete compositor state snapshot.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CompositorState {
     /// All workspaces.
     pub workspaces: Vec<Workspace>,
@@ -62,12 +118,16 @@ pub struct CompositorState {
     pub monitors: Vec<Monitor>,
     /// ID of the currently active workspace.
     pub active_workspace_id: Option<i32>,
+    /// All open windows.
+    pub windows: Vec<Window>,
     /// Currently focused window (if any).
     pub active_window: Option<ActiveWindow>,
     /// Current keyboard layout name.
     pub keyboard_layout: String,
     /// Current submap/mode (if any).
     pub submap: Option<String>,
+    /// Niri-specific layout state (`None` on other backends).
+    pub niri: Option<NiriState>,
 }
 
 impl CompositorState {
@@ -95,6 +155,51 @@ impl CompositorState {
         self.workspaces.iter().filter(|w| w.is_special).collect()
     }
 
+    /// Get the windows on a workspace.
+    pub fn windows_on_workspace(&self, workspace_id: i32) -> Vec<&Window> {
+        self.windows
+            .iter()
+            .filter(|w| w.workspace_id == Some(workspace_id))
+            .collect()
+    }
+
+    /// Get the first window on a workspace that requested attention.
+    pub fn urgent_window_on_workspace(&self, workspace_id: i32) -> Option<&Window> {
+        self.windows
+            .iter()
+            .find(|w| w.urgent && w.workspace_id == Some(workspace_id))
+    }
+
+    /// Whether the workspace shown on a monitor has a fullscreen window.
+    ///
+    /// Uses the active workspace when `monitor` is `None` or unknown.
+    pub fn has_fullscreen_window(&self, monitor: Option<&str>) -> bool {
+        let workspace_id = monitor
+            .and_then(|name| self.monitor_by_name(name))
+            .map(|m| m.active_workspace_id)
+            .or(self.active_workspace_id);
+        workspace_id.is_some_and(|id| {
+            self.windows
+                .iter()
+                .any(|w| w.fullscreen && w.workspace_id == Some(id))
+        })
+    }
+
+    /// Recompute each workspace's urgent flag from its windows.
+    pub fn update_workspace_urgency(&mut self) {
+        for ws in &mut self.workspaces {
+            ws.urgent = self
+                .windows
+                .iter()
+                .any(|w| w.urgent && w.workspace_id == Some(ws.id));
+        }
+    }
+
+    /// Get a window by ID.
+    pub fn window_by_id(&self, id: &str) -> Option<&Window> {
+        self.windows.iter().find(|w| w.id == id)
+    }
+
     /// Get a monitor by name.
     pub fn monitor_by_name(&self, name: &str) -> Option<&Monitor> {
         self.monitors.iter().find(|m| m.name == name)
@@ -112,6 +217,7 @@ pub enum CompositorBackend {
     #[default]
     Hyprland,
     Niri,
+    Sway,
 }
 
 impl CompositorBackend {
@@ -120,6 +226,7 @@ impl CompositorBackend {
         match self {
             CompositorBackend::Hyprland => "Hyprland",
             CompositorBackend::Niri => "Niri",
+            CompositorBackend::Sway => "Sway",
         }
     }
 }
@@ -131,14 +238,23 @@ pub enum CompositorCommand {
     FocusWorkspace(i32),
     /// Focus a special workspace by name.
     FocusSpecialWorkspace(String),
-    /// Focus a monitor by ID.
-    FocusMonitor(i128),
+    /// Focus a monitor by name (see [`Monitor::name`]).
+    FocusMonitor(String),
     /// Toggle a special workspace by name.
     ToggleSpecialWorkspace(String),
     /// Scroll through workspaces (+1 for next, -1 for previous).
     ScrollWorkspace(i32),
+    /// Focus a window by ID.
+    FocusWindow(String),
+    /// Close a window by ID.
+    CloseWindow(String),
+    /// Move a window (by ID) to a workspace (by ID) without following it.
+    MoveWindowToWorkspace(String, i32),
     /// Switch to the next keyboard layout.
     NextKeyboardLayout,
     /// Custom dispatcher command (dispatcher name, arguments).
+    ///
+    /// On Niri the name is an action (e.g. `focus-column-left` or
+    /// `FocusColumnLeft`) and the arguments are its fields as JSON.
     Custom(String, String),
 }
@@ -21,14 +21,16 @@ pub mod wallpaper;
 pub mod watcher;
 
 pub use applications::{Application, ApplicationsService};
-pub use audio::{AudioCommand, AudioData, AudioSubscriber};
+pub use audio::{AudioCommand, AudioData, AudioDevice, AudioStream, AudioSubscriber, StreamKind};
 pub use bluetooth::{
     BluetoothCommand, BluetoothData, BluetoothDevice, BluetoothState, BluetoothSubscriber,
 };
-pub use brightness::{BrightnessCommand, BrightnessData, BrightnessSubscriber};
+pub use brightness::{
+    BrightnessCommand, BrightnessData, BrightnessDevice, BrightnessKind, BrightnessSubscriber,
+};
 pub use compositor::{
     ActiveWindow, CompositorBackend, CompositorCommand, CompositorState, CompositorSubscriber,
-    Monitor, Workspace,
+    Monitor, NiriColumn, NiriState, Window, Workspace,
 };
 pub use mpris::{
     MprisCommand, MprisData, MprisPlayerData, MprisPlayerMetadata, MprisSubscriber, PlaybackStatus,
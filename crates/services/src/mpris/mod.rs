@@ -11,6 +11,7 @@ use futures_signals::signal::{Mutable, MutableSignalCloned};
 use futures_util::StreamExt;
 use futures_util::future::join_all;
 use futures_util::stream::select_all;
+use serde::Serialize;
 use tracing::{debug, error, info, trace, warn};
 use zbus::{Connection, fdo::DBusProxy, zvariant::OwnedValue};
 
@@ -28,7 +29,8 @@ enum MprisEvent {
 }
 
 /// Current playback state reported by MPRIS.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum PlaybackStatus {
     Playing,
     Paused,
@@ -54,7 +56,7 @@ impl From<&str> for PlaybackStatus {
 }
 
 /// Simplified MPRIS metadata for display.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
 pub struct MprisPlayerMetadata {
     pub artists: Option<Vec<String>>,
     pub title: Option<String>,
@@ -110,7 +112,7 @@ fn metadata_duration_us(value: &HashMap<String, OwnedValue>) -> Option<i64> {
 }
 
 /// Per-player data snapshot.
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct MprisPlayerData {
     pub service: String,
     pub metadata: Option<MprisPlayerMetadata>,
@@ -122,7 +124,7 @@ pub struct MprisPlayerData {
 }
 
 /// Complete MPRIS service state.
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct MprisData {
     pub players: Vec<MprisPlayerData>,
 }
@@ -148,7 +150,7 @@ pub enum PlayerCommand {
 pub struct MprisSubscriber {
     data: Mutable<MprisData>,
     status: Mutable<ServiceStatus>,
-    conn: Connection,
+    conn: Option<Connection>,
 }
 
 impl MprisSubscriber {
@@ -173,7 +175,20 @@ impl MprisSubscriber {
 
         start_listener(data.clone(), status.clone(), conn.clone());
 
-        Ok(Self { data, status, conn })
+        Ok(Self {
+            data,
+            status,
+            conn: Some(conn),
+        })
+    }
+
+    /// Fallback subscriber when the session bus is unavailable.
+    pub fn disabled() -> Self {
+        Self {
+            data: Mutable::new(MprisData::default()),
+            status: Mutable::new(ServiceStatus::Unavailable),
+            conn: None,
+        }
     }
 
     /// Get a signal that emits when MPRIS state changes.
@@ -191,9 +206,16 @@ impl MprisSubscriber {
         self.status.get_cloned()
     }
 
+    /// Connection to the bus, or an error when the service is unavailable.
+    fn conn(&self) -> anyhow::Result<&Connection> {
+        self.conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("MPRIS service is unavailable"))
+    }
+
     /// Execute a command for a specific player.
     pub async fn dispatch(&self, command: MprisCommand) -> anyhow::Result<()> {
-        let proxy = MprisPlayerProxy::builder(&self.conn)
+        let proxy = MprisPlayerProxy::builder(self.conn()?)
             .destination(command.service_name.as_str())?
             .build()
             .await?;
@@ -214,7 +236,7 @@ impl MprisSubscriber {
             }
         }
 
-        if let Ok(new_data) = fetch_mpris_data(&self.conn).await {
+        if let Ok(new_data) = fetch_mpris_data(self.conn()?).await {
             *self.data.lock_mut() = new_data;
         }
 
@@ -29,7 +29,7 @@ use crate::ServiceStatus;
 pub struct NetworkSubscriber {
     data: Mutable<NetworkData>,
     status: Mutable<ServiceStatus>,
-    conn: Connection,
+    conn: Option<Connection>,
 }
 
 impl NetworkSubscriber {
@@ -55,7 +55,20 @@ impl NetworkSubscriber {
         // Start the D-Bus listener
         start_listener(data.clone(), status.clone(), conn.clone());
 
-        Ok(Self { data, status, conn })
+        Ok(Self {
+            data,
+            status,
+            conn: Some(conn),
+        })
+    }
+
+    /// Fallback subscriber when the system bus is unavailable.
+    pub fn disabled() -> Self {
+        Self {
+            data: Mutable::new(NetworkData::default()),
+            status: Mutable::new(ServiceStatus::Unavailable),
+            conn: None,
+        }
     }
 
     /// Get a signal that emits when network state changes.
@@ -73,9 +86,16 @@ impl NetworkSubscriber {
         self.status.get_cloned()
     }
 
+    /// Connection to the bus, or an error when the service is unavailable.
+    fn conn(&self) -> anyhow::Result<&Connection> {
+        self.conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Network service is unavailable"))
+    }
+
     /// Execute a network command.
     pub async fn dispatch(&self, command: NetworkCommand) -> anyhow::Result<()> {
-        let nm = NetworkManager::new(&self.conn).await?;
+        let nm = NetworkManager::new(self.conn()?).await?;
 
         match command {
             NetworkCommand::SetWifiEnabled(enabled) => {
@@ -91,10 +111,11 @@ impl NetworkSubscriber {
                 debug!("Requesting WiFi scan");
                 let wireless_devices = nm.wireless_devices().await?;
                 for device in wireless_devices {
-                    let wireless = dbus::device::wireless::WirelessDeviceProxy::builder(&self.conn)
-                        .path(&device)?
-                        .build()
-                        .await?;
+                    let wireless =
+                        dbus::device::wireless::WirelessDeviceProxy::builder(self.conn()?)
+                            .path(&device)?
+                            .build()
+                            .await?;
                     let _ = wireless
                         .request_scan(std::collections::HashMap::new())
                         .await;
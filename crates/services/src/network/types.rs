@@ -1,9 +1,12 @@
 //! Network service types.
 
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use zbus::zvariant::{ObjectPath, OwnedObjectPath};
 
 /// Device type from NetworkManager.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum DeviceType {
     Ethernet,
     Wifi,
@@ -32,7 +35,8 @@ impl From<u32> for DeviceType {
 }
 
 /// Device state from NetworkManager.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum DeviceState {
     Unmanaged,
     Unavailable,
@@ -71,7 +75,8 @@ impl From<u32> for DeviceState {
 }
 
 /// Connectivity state from NetworkManager.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ConnectivityState {
     None,
     Portal,
@@ -94,7 +99,7 @@ impl From<u32> for ConnectivityState {
 }
 
 /// A wireless access point.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub struct AccessPoint {
     /// SSID (network name).
     pub ssid: String,
@@ -115,12 +120,14 @@ pub struct AccessPoint {
 }
 
 /// Information about an active network connection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
 pub enum ActiveConnectionInfo {
     Wired {
         name: String,
         speed: u32,
     },
+    #[serde(rename = "wifi")]
     WiFi {
         id: String,
         name: String,
@@ -146,6 +153,9 @@ impl ActiveConnectionInfo {
 }
 
 /// Network traffic statistics for a device.
+///
+/// Serialized as the device with its current speeds; the raw counters
+/// are only needed to compute them.
 #[derive(Debug, Clone)]
 pub struct NetworkStatistics {
     pub(crate) prev_tx: u64,
@@ -182,8 +192,18 @@ impl NetworkStatistics {
     }
 }
 
+impl Serialize for NetworkStatistics {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("NetworkStatistics", 3)?;
+        state.serialize_field("device", &self.device)?;
+        state.serialize_field("rx_speed", &self.rx_speed())?;
+        state.serialize_field("tx_speed", &self.tx_speed())?;
+        state.end()
+    }
+}
+
 /// Network service data.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NetworkData {
     /// Whether WiFi is enabled.
     pub wifi_enabled: bool,
@@ -284,3 +304,32 @@ pub enum NetworkCommand {
     /// Disconnect the active connection.
     Disconnect(OwnedObjectPath),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics_serialize_speeds_only() {
+        let stats = NetworkStatistics {
+            prev_tx: 1000,
+            prev_rx: 2000,
+            prev_tx_time: 10,
+            prev_rx_time: 10,
+            tx: 3000,
+            rx: 6000,
+            tx_time: 12,
+            rx_time: 12,
+            device: "/org/freedesktop/NetworkManager/Devices/2".to_string(),
+        };
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "device": "/org/freedesktop/NetworkManager/Devices/2",
+                "rx_speed": 2000.0,
+                "tx_speed": 1000.0,
+            })
+        );
+    }
+}
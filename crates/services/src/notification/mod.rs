@@ -7,6 +7,7 @@ use std::time::Duration;
 
 use chrono::Utc;
 use futures_signals::signal::{Mutable, MutableSignalCloned};
+use serde::Serialize;
 use tracing::warn;
 use zbus::{
     Connection,
@@ -15,7 +16,7 @@ use zbus::{
     names::WellKnownName,
     object_server::SignalEmitter,
     proxy,
-    zvariant::OwnedValue,
+    zvariant::{OwnedValue, Value},
 };
 
 use crate::ServiceStatus;
@@ -27,7 +28,7 @@ const OBJECT_PATH: &str = "/org/freedesktop/Notifications";
 const DEFAULT_TIMEOUT_MS: i32 = 5000;
 
 /// A single desktop notification.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Notification {
     pub id: u32,
     pub app_name: String,
@@ -44,7 +45,7 @@ pub struct Notification {
 }
 
 /// Notification center state.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct NotificationData {
     pub notifications: Vec<Notification>,
     pub popup_ids: Vec<u32>,
@@ -72,6 +73,12 @@ pub enum NotificationCommand {
     SetDnd(bool),
     MarkAllRead,
     InvokeAction(u32, String),
+    /// Post a notification on behalf of the shell itself.
+    Notify {
+        summary: String,
+        body: String,
+        urgency: u8,
+    },
 }
 
 /// Event-driven notification service.
@@ -186,6 +193,23 @@ impl NotificationSubscriber {
                 self.emit_action_invoked(id, &action_key).await;
                 self.dismiss_by_id(id).await?;
             }
+            NotificationCommand::Notify {
+                summary,
+                body,
+                urgency,
+            } => {
+                // Go through D-Bus so ids and timeouts are handled as for any
+                // client; when another daemon owns the name, it shows it.
+                let conn = match &self.conn {
+                    Some(conn) => conn.clone(),
+                    None => Connection::session().await?,
+                };
+                let proxy = NotificationsProxy::new(&conn).await?;
+                let hints = HashMap::from([("urgency", Value::from(urgency))]);
+                proxy
+                    .notify("gpuishell", 0, "", &summary, &body, &[], hints, -1)
+                    .await?;
+            }
         }
 
         Ok(())
@@ -531,4 +555,18 @@ fn normalize_path(value: &str) -> PathBuf {
 trait Notifications {
     #[zbus(name = "CloseNotification")]
     fn close_notification(&self, id: u32) -> zbus::Result<()>;
+
+    #[allow(clippy::too_many_arguments)]
+    #[zbus(name = "Notify")]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
 }
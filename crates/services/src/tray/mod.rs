@@ -118,7 +118,7 @@ pub enum TrayCommand {
 pub struct TraySubscriber {
     data: Mutable<TrayData>,
     status: Mutable<ServiceStatus>,
-    conn: zbus::Connection,
+    conn: Option<zbus::Connection>,
 }
 
 impl TraySubscriber {
@@ -151,7 +151,20 @@ impl TraySubscriber {
         // Start the event listener
         start_listener(data.clone(), status.clone(), conn.clone());
 
-        Ok(Self { data, status, conn })
+        Ok(Self {
+            data,
+            status,
+            conn: Some(conn),
+        })
+    }
+
+    /// Fallback subscriber when the tray watcher is unavailable.
+    pub fn disabled() -> Self {
+        Self {
+            data: Mutable::new(TrayData::default()),
+            status: Mutable::new(ServiceStatus::Unavailable),
+            conn: None,
+        }
     }
 
     /// Get a signal that emits when tray state changes.
@@ -169,6 +182,13 @@ impl TraySubscriber {
         self.status.get_cloned()
     }
 
+    /// Connection to the bus, or an error when the service is unavailable.
+    fn conn(&self) -> anyhow::Result<&zbus::Connection> {
+        self.conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Tray service is unavailable"))
+    }
+
     /// Build a StatusNotifierItem proxy for the given item name.
     async fn item_proxy(&self, item_name: &str) -> Option<StatusNotifierItemProxy<'_>> {
         let (dest, path) = if let Some(idx) = item_name.find('/') {
@@ -177,7 +197,7 @@ impl TraySubscriber {
             (item_name, "/StatusNotifierItem")
         };
 
-        StatusNotifierItemProxy::builder(&self.conn)
+        StatusNotifierItemProxy::builder(self.conn.as_ref()?)
             .destination(dest.to_owned())
             .ok()?
             .path(path.to_owned())
@@ -193,7 +213,7 @@ impl TraySubscriber {
             TrayCommand::MenuItemClicked { item_name, menu_id } => {
                 let data = self.data.lock_ref();
                 if let Some(item) = data.items.iter().find(|i| i.name == item_name) {
-                    let menu_proxy = DBusMenuProxy::builder(&self.conn)
+                    let menu_proxy = DBusMenuProxy::builder(self.conn()?)
                         .destination(item.dest.clone())?
                         .path(item.menu_path.clone())?
                         .build()
@@ -241,7 +261,7 @@ impl TraySubscriber {
                     && !item.menu_path.is_empty()
                     && item.menu_path != "/"
                 {
-                    let menu_proxy = DBusMenuProxy::builder(&self.conn)
+                    let menu_proxy = DBusMenuProxy::builder(self.conn()?)
                         .destination(item.dest.clone())?
                         .path(item.menu_path.clone())?
                         .build()
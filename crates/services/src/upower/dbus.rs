@@ -4,12 +4,14 @@
 
 use std::ops::Deref;
 
+use serde::Serialize;
 use zbus::zvariant::OwnedValue;
 use zbus::{Connection, proxy};
 
 /// Battery charging/discharging state from UPower.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, OwnedValue)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, OwnedValue, Serialize)]
 #[repr(u32)]
+#[serde(rename_all = "kebab-case")]
 pub enum BatteryState {
     #[default]
     Unknown = 0,
@@ -58,8 +60,9 @@ pub enum DeviceType {
 }
 
 /// Warning level from UPower.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, OwnedValue)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, OwnedValue, Serialize)]
 #[repr(u32)]
+#[serde(rename_all = "kebab-case")]
 pub enum WarningLevel {
     #[default]
     Unknown = 0,
@@ -71,8 +74,9 @@ pub enum WarningLevel {
 }
 
 /// Battery level from UPower (coarse-grained).
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, OwnedValue)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, OwnedValue, Serialize)]
 #[repr(u32)]
+#[serde(rename_all = "kebab-case")]
 pub enum BatteryLevel {
     #[default]
     Unknown = 0,
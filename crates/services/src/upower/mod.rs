@@ -12,6 +12,7 @@ use anyhow::Result;
 use futures_signals::signal::{Mutable, MutableSignalCloned};
 use futures_util::StreamExt;
 use futures_util::stream::select_all;
+use serde::Serialize;
 use tracing::{debug, error, info, warn};
 use zbus::Connection;
 
@@ -20,7 +21,8 @@ pub use dbus::{BatteryLevel, BatteryState, DeviceType, WarningLevel};
 use dbus::{DeviceProxy, PowerProfilesProxy, UPowerService};
 
 /// Power profile (performance/balanced/power-saver).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum PowerProfile {
     #[default]
     Balanced,
@@ -83,7 +85,7 @@ impl PowerProfile {
 }
 
 /// Battery data from UPower.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct BatteryData {
     /// Charge percentage (0-100).
     pub percentage: u8,
@@ -189,7 +191,7 @@ impl BatteryData {
 }
 
 /// Complete UPower service data.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct UPowerData {
     /// Battery data (None if no battery present).
     pub battery: Option<BatteryData>,
@@ -312,7 +314,7 @@ pub enum UPowerCommand {
 pub struct UPowerSubscriber {
     data: Mutable<UPowerData>,
     status: Mutable<ServiceStatus>,
-    conn: Connection,
+    conn: Option<Connection>,
 }
 
 impl UPowerSubscriber {
@@ -338,7 +340,7 @@ impl UPowerSubscriber {
         let subscriber = Self {
             data: data.clone(),
             status: status.clone(),
-            conn: conn.clone(),
+            conn: Some(conn.clone()),
         };
 
         // Spawn the monitoring task
@@ -353,6 +355,15 @@ impl UPowerSubscriber {
         Ok(subscriber)
     }
 
+    /// Fallback subscriber when the system bus is unavailable.
+    pub fn disabled() -> Self {
+        Self {
+            data: Mutable::new(UPowerData::default()),
+            status: Mutable::new(ServiceStatus::Unavailable),
+            conn: None,
+        }
+    }
+
     /// Get a signal that emits when data changes.
     pub fn subscribe(&self) -> MutableSignalCloned<UPowerData> {
         self.data.signal_cloned()
@@ -368,6 +379,13 @@ impl UPowerSubscriber {
         self.status.get_cloned()
     }
 
+    /// Connection to the bus, or an error when the service is unavailable.
+    fn conn(&self) -> Result<&Connection> {
+        self.conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("UPower service is unavailable"))
+    }
+
     /// Execute a command.
     pub async fn dispatch(&self, command: UPowerCommand) -> Result<()> {
         match command {
@@ -387,7 +405,7 @@ impl UPowerSubscriber {
 
     /// Set the power profile.
     async fn set_power_profile(&self, profile: PowerProfile) -> Result<()> {
-        let pp = PowerProfilesProxy::new(&self.conn).await?;
+        let pp = PowerProfilesProxy::new(self.conn()?).await?;
         pp.set_active_profile(profile.as_str()).await?;
         debug!("Set power profile to: {:?}", profile);
         Ok(())
@@ -395,7 +413,7 @@ impl UPowerSubscriber {
 
     /// Refresh all data from D-Bus.
     async fn refresh(&self) -> Result<()> {
-        let new_data = UPowerData::init(&self.conn).await?;
+        let new_data = UPowerData::init(self.conn()?).await?;
         self.data.set(new_data);
         debug!("Refreshed UPower data");
         Ok(())
@@ -405,9 +423,9 @@ impl UPowerSubscriber {
     async fn run(&self) -> Result<()> {
         info!("UPower subscriber started");
 
-        let upower = UPowerService::new(&self.conn).await?;
+        let upower = UPowerService::new(self.conn()?).await?;
         let device = upower.get_display_device().await?;
-        let device_proxy = DeviceProxy::builder(&self.conn)
+        let device_proxy = DeviceProxy::builder(self.conn()?)
             .path(device.inner().path())?
             .build()
             .await?;
@@ -537,7 +555,7 @@ impl UPowerSubscriber {
             .boxed();
 
         // Create stream for power profile changes (if available)
-        let profile_stream = match PowerProfilesProxy::new(&self.conn).await {
+        let profile_stream = match PowerProfilesProxy::new(self.conn()?).await {
             Ok(pp) => pp
                 .receive_active_profile_changed()
                 .await
@@ -1,11 +1,12 @@
 //! File watcher service using inotify.
 
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
 
-use inotify::{EventMask, Inotify, WatchMask};
+use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
 use tokio::sync::mpsc;
 
 const DEBOUNCE_MS: u64 = 200;
@@ -14,9 +15,18 @@ pub struct FileWatcher;
 
 impl FileWatcher {
     pub fn watch(path: PathBuf) -> mpsc::UnboundedReceiver<()> {
+        Self::watch_all(vec![path])
+    }
+
+    /// Watch several files, signalling when any of them changes.
+    ///
+    /// Files may not exist yet; creating one counts as a change. The
+    /// watcher stops once the receiver is dropped and the next event
+    /// arrives.
+    pub fn watch_all(paths: Vec<PathBuf>) -> mpsc::UnboundedReceiver<()> {
         let (tx, rx) = mpsc::unbounded_channel();
         thread::spawn(move || {
-            if let Err(err) = watch_file(path, tx) {
+            if let Err(err) = watch_files(paths, tx) {
                 tracing::warn!("File watcher stopped: {}", err);
             }
         });
@@ -24,20 +34,34 @@ impl FileWatcher {
     }
 }
 
-fn watch_file(path: PathBuf, tx: mpsc::UnboundedSender<()>) -> anyhow::Result<()> {
+fn watch_files(paths: Vec<PathBuf>, tx: mpsc::UnboundedSender<()>) -> anyhow::Result<()> {
     let mut inotify = Inotify::init()?;
-    let (watch_dir, watch_name) = watch_target(&path)?;
 
-    inotify.watches().add(
-        watch_dir,
-        WatchMask::MODIFY
-            | WatchMask::CLOSE_WRITE
-            | WatchMask::CREATE
-            | WatchMask::DELETE
-            | WatchMask::MOVED_TO
-            | WatchMask::MOVE_SELF
-            | WatchMask::DELETE_SELF,
-    )?;
+    // Watched file names per directory watch.
+    let mut targets: HashMap<WatchDescriptor, Vec<OsString>> = HashMap::new();
+    for path in &paths {
+        let (watch_dir, watch_name) = watch_target(path)?;
+        let wd = match inotify.watches().add(
+            watch_dir,
+            WatchMask::MODIFY
+                | WatchMask::CLOSE_WRITE
+                | WatchMask::CREATE
+                | WatchMask::DELETE
+                | WatchMask::MOVED_TO
+                | WatchMask::MOVE_SELF
+                | WatchMask::DELETE_SELF,
+        ) {
+            Ok(wd) => wd,
+            Err(err) => {
+                tracing::warn!("Cannot watch {}: {}", path.display(), err);
+                continue;
+            }
+        };
+        targets.entry(wd).or_default().push(watch_name);
+    }
+    if targets.is_empty() {
+        anyhow::bail!("no watchable files");
+    }
 
     let mut buffer = [0u8; 4096];
     let mut last_sent: Option<Instant> = None;
@@ -49,11 +73,11 @@ fn watch_file(path: PathBuf, tx: mpsc::UnboundedSender<()>) -> anyhow::Result<()
         for event in events {
             let renamed_or_deleted = event.mask.contains(EventMask::MOVE_SELF)
                 || event.mask.contains(EventMask::DELETE_SELF);
-            let same_file = event
-                .name
-                .map(|name| name == watch_name.as_os_str())
-                .unwrap_or(false);
-            if renamed_or_deleted || same_file {
+            let watched_file = match (event.name, targets.get(&event.wd)) {
+                (Some(name), Some(names)) => names.iter().any(|watched| watched == name),
+                _ => false,
+            };
+            if renamed_or_deleted || watched_file {
                 should_reload = true;
                 break;
             }
@@ -95,6 +95,13 @@ impl Slider {
         cx.notify();
     }
 
+    /// Change the upper bound, e.g. when the allowed range grows.
+    pub fn set_max(&mut self, max: f32, cx: &mut Context<Self>) {
+        self.max = max;
+        self.update_thumb_pos();
+        cx.notify();
+    }
+
     pub fn value(&self) -> f32 {
         self.value
     }
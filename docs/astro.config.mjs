@@ -18,12 +18,14 @@ export default defineConfig({
         {
           label: 'Reference',
           items: [
+            { label: 'Command Line', slug: 'reference/cli' },
             { label: 'Configuration', slug: 'reference/configuration' },
             { label: 'Bar', slug: 'reference/bar' },
             { label: 'Launcher', slug: 'reference/launcher' },
             { label: 'OSD', slug: 'reference/osd' },
             { label: 'Control Center', slug: 'reference/control-center' },
             { label: 'Theme', slug: 'reference/theme' },
+            { label: 'IPC', slug: 'reference/ipc' },
           ],
         },
       ],